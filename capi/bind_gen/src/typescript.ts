@@ -185,7 +185,7 @@ export type FontStretch =
 
 /**
  * A Timing Method describes which form of timing is used. This can either be
- * Real Time or Game Time.
+ * Real Time, Game Time or Load Removed Time.
  */
 export enum TimingMethod {
     /**
@@ -199,6 +199,13 @@ export enum TimingMethod {
      * times removed or some time provided by the game.
      */
     GameTime = 1,
+    /**
+     * Load Removed Time describes Real Time with the loading times removed. It
+     * is tracked separately from Game Time, so that both can be used at the
+     * same time, for example if the game provides its own in-game timer that
+     * differs from the Real Time without the loading times.
+     */
+    LoadRemovedTime = 2,
 }
 
 /**
@@ -259,6 +266,30 @@ export enum Event {
     LoadingTimesSet = 16,
     /** A custom variable has been set. */
     CustomVariableSet = 17,
+    /** The most recent operation has been undone. */
+    OperationUndone = 18,
+    /** The most recently undone operation has been redone. */
+    OperationRedone = 19,
+    /** An attempt has been restored from a journal. */
+    AttemptRestored = 20,
+    /** The load removed time has been initialized. */
+    LoadRemovedTimeInitialized = 21,
+    /** The load removed time has been paused. */
+    LoadRemovedTimePaused = 22,
+    /** The load removed time has been resumed. */
+    LoadRemovedTimeResumed = 23,
+    /**
+     * The split time of a segment of an attempt that has ended has been
+     * corrected.
+     */
+    SplitRetimed = 24,
+    /**
+     * A start of the timer has been scheduled and the countdown to it has
+     * begun.
+     */
+    StartScheduled = 25,
+    /** The scheduled start of the timer has been cancelled. */
+    ScheduledStartCancelled = 26,
 }
 
 /** An error that occurred when a command was being processed. */
@@ -297,6 +328,22 @@ export enum CommandError {
     TimerPaused = -16,
     /** The runner decided to not reset the run. */
     RunnerDecidedAgainstReset = -17,
+    /** There is no operation to undo. */
+    NothingToUndo = -18,
+    /** There is no operation to redo. */
+    NothingToRedo = -19,
+    /** The load removed time is already initialized. */
+    LoadRemovedTimeAlreadyInitialized = -20,
+    /** The load removed time is already paused. */
+    LoadRemovedTimeAlreadyPaused = -21,
+    /** The load removed time is not paused. */
+    LoadRemovedTimeNotPaused = -22,
+    /** The run is still in progress. */
+    RunNotFinished = -23,
+    /** There is no start of the timer scheduled. */
+    NoStartScheduled = -24,
+    /** The index is out of bounds. */
+    InvalidIndex = -25,
 }
 
 /** The result of a command that was processed. */
@@ -817,9 +864,9 @@ export type AccuracyJson = "Seconds" | "Tenths" | "Hundredths" | "Milliseconds";
 
 /**
  * A Timing Method describes which form of timing is used. This can either be
- * Real Time or Game Time.
+ * Real Time, Game Time or Load Removed Time.
  */
-export type TimingMethodJson = "RealTime" | "GameTime";
+export type TimingMethodJson = "RealTime" | "GameTime" | "LoadRemovedTime";
 
 /**
  * A Digits Format describes how many digits of a time to always shown. The
@@ -975,6 +1022,11 @@ export interface RunEditorRowJson {
     icon: ImageId,
     /** The name of the segment. */
     name: string,
+    /**
+     * The name of the runner the segment belongs to. It is empty if the
+     * segment doesn't belong to any runner in particular.
+     */
+    runner: string,
     /** The segment's split time for the active timing method. */
     split_time: string,
     /** The segment time for the active timing method. */
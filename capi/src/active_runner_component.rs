@@ -0,0 +1,54 @@
+//! The Active Runner Component is a component for relay and team runs that
+//! shows the runner of the current segment, along with a prediction of the sum
+//! of their segment times, if their pace matches the chosen comparison for the
+//! remainder of their segments.
+
+use super::{output_vec, Json};
+use crate::component::OwnedComponent;
+use crate::key_value_component_state::OwnedKeyValueComponentState;
+use livesplit_core::component::active_runner::Component as ActiveRunnerComponent;
+use livesplit_core::Timer;
+
+/// type
+pub type OwnedActiveRunnerComponent = Box<ActiveRunnerComponent>;
+
+/// Creates a new Active Runner Component.
+#[unsafe(no_mangle)]
+pub extern "C" fn ActiveRunnerComponent_new() -> OwnedActiveRunnerComponent {
+    Box::new(ActiveRunnerComponent::new())
+}
+
+/// drop
+#[unsafe(no_mangle)]
+pub extern "C" fn ActiveRunnerComponent_drop(this: OwnedActiveRunnerComponent) {
+    drop(this);
+}
+
+/// Converts the component into a generic component suitable for using with a
+/// layout.
+#[unsafe(no_mangle)]
+pub extern "C" fn ActiveRunnerComponent_into_generic(
+    this: OwnedActiveRunnerComponent,
+) -> OwnedComponent {
+    Box::new((*this).into())
+}
+
+/// Encodes the component's state information as JSON.
+#[unsafe(no_mangle)]
+pub extern "C" fn ActiveRunnerComponent_state_as_json(
+    this: &ActiveRunnerComponent,
+    timer: &Timer,
+) -> Json {
+    output_vec(|o| {
+        this.state(&timer.snapshot()).write_json(o).unwrap();
+    })
+}
+
+/// Calculates the component's state based on the timer provided.
+#[unsafe(no_mangle)]
+pub extern "C" fn ActiveRunnerComponent_state(
+    this: &ActiveRunnerComponent,
+    timer: &Timer,
+) -> OwnedKeyValueComponentState {
+    Box::new(this.state(&timer.snapshot()))
+}
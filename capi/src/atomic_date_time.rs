@@ -1,8 +1,8 @@
 //! An Atomic Date Time represents a UTC Date Time that tries to be as close to
 //! an atomic clock as possible.
 
-use crate::output_vec;
-use livesplit_core::AtomicDateTime;
+use crate::{output_vec, str};
+use livesplit_core::{AtomicDateTime, DateTime};
 use std::os::raw::c_char;
 use time::format_description::well_known::Rfc3339;
 
@@ -11,6 +11,18 @@ pub type OwnedAtomicDateTime = Box<AtomicDateTime>;
 /// type
 pub type NullableOwnedAtomicDateTime = Option<OwnedAtomicDateTime>;
 
+/// Parses an Atomic Date Time from a RFC 3339 formatted date time. The Atomic
+/// Date Time is not considered to be synchronized with an atomic clock. If the
+/// text can't be parsed, <NULL> is returned instead.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn AtomicDateTime_parse_rfc3339(
+    text: *const c_char,
+) -> NullableOwnedAtomicDateTime {
+    // SAFETY: The caller guarantees that `text` is valid.
+    let time = DateTime::parse(unsafe { str(text) }, &Rfc3339).ok()?;
+    Some(Box::new(AtomicDateTime::new(time, false)))
+}
+
 /// drop
 #[unsafe(no_mangle)]
 pub extern "C" fn AtomicDateTime_drop(this: OwnedAtomicDateTime) {
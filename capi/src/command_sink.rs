@@ -11,8 +11,8 @@
 use std::{borrow::Cow, future::Future, ops::Deref, pin::Pin, sync::Arc};
 
 use livesplit_core::{
-    event::{self, Result},
-    TimeSpan, Timer, TimingMethod,
+    event::{self, Event, Result},
+    AtomicDateTime, TimeSpan, Timer, TimingMethod,
 };
 
 use crate::shared_timer::OwnedSharedTimer;
@@ -39,6 +39,9 @@ pub extern "C" fn CommandSink_drop(this: OwnedCommandSink) {
 pub(crate) trait CommandSinkAndQuery: Send + Sync + 'static {
     fn dyn_query<'a>(&'a self) -> Box<dyn Deref<Target = Timer> + 'a>;
     fn dyn_start(&self) -> Fut;
+    fn dyn_schedule_start(&self, at: AtomicDateTime) -> Fut;
+    fn dyn_cancel_scheduled_start(&self) -> Fut;
+    fn dyn_poll_scheduled_start(&self) -> PollFut;
     fn dyn_split(&self) -> Fut;
     fn dyn_split_or_start(&self) -> Fut;
     fn dyn_reset(&self, save_attempt: Option<bool>) -> Fut;
@@ -58,10 +61,16 @@ pub(crate) trait CommandSinkAndQuery: Send + Sync + 'static {
     fn dyn_pause_game_time(&self) -> Fut;
     fn dyn_resume_game_time(&self) -> Fut;
     fn dyn_set_loading_times(&self, time: TimeSpan) -> Fut;
+    fn dyn_initialize_load_removed_time(&self) -> Fut;
+    fn dyn_pause_load_removed_time(&self) -> Fut;
+    fn dyn_resume_load_removed_time(&self) -> Fut;
     fn dyn_set_custom_variable(&self, name: Cow<'_, str>, value: Cow<'_, str>) -> Fut;
+    fn dyn_undo(&self) -> Fut;
+    fn dyn_redo(&self) -> Fut;
 }
 
 type Fut = Pin<Box<dyn Future<Output = Result> + 'static>>;
+type PollFut = Pin<Box<dyn Future<Output = Result<Option<Event>>> + 'static>>;
 
 impl<T> CommandSinkAndQuery for T
 where
@@ -74,6 +83,15 @@ where
     fn dyn_start(&self) -> Fut {
         Box::pin(self.start())
     }
+    fn dyn_schedule_start(&self, at: AtomicDateTime) -> Fut {
+        Box::pin(self.schedule_start(at))
+    }
+    fn dyn_cancel_scheduled_start(&self) -> Fut {
+        Box::pin(self.cancel_scheduled_start())
+    }
+    fn dyn_poll_scheduled_start(&self) -> PollFut {
+        Box::pin(self.poll_scheduled_start())
+    }
     fn dyn_split(&self) -> Fut {
         Box::pin(self.split())
     }
@@ -131,9 +149,24 @@ where
     fn dyn_set_loading_times(&self, time: TimeSpan) -> Fut {
         Box::pin(self.set_loading_times(time))
     }
+    fn dyn_initialize_load_removed_time(&self) -> Fut {
+        Box::pin(self.initialize_load_removed_time())
+    }
+    fn dyn_pause_load_removed_time(&self) -> Fut {
+        Box::pin(self.pause_load_removed_time())
+    }
+    fn dyn_resume_load_removed_time(&self) -> Fut {
+        Box::pin(self.resume_load_removed_time())
+    }
     fn dyn_set_custom_variable(&self, name: Cow<'_, str>, value: Cow<'_, str>) -> Fut {
         Box::pin(self.set_custom_variable(name, value))
     }
+    fn dyn_undo(&self) -> Fut {
+        Box::pin(self.undo())
+    }
+    fn dyn_redo(&self) -> Fut {
+        Box::pin(self.redo())
+    }
 }
 
 impl event::CommandSink for CommandSink {
@@ -141,6 +174,18 @@ impl event::CommandSink for CommandSink {
         self.0.dyn_start()
     }
 
+    fn schedule_start(&self, at: AtomicDateTime) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_schedule_start(at)
+    }
+
+    fn cancel_scheduled_start(&self) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_cancel_scheduled_start()
+    }
+
+    fn poll_scheduled_start(&self) -> impl Future<Output = Result<Option<Event>>> + 'static {
+        self.0.dyn_poll_scheduled_start()
+    }
+
     fn split(&self) -> impl Future<Output = Result> + 'static {
         self.0.dyn_split()
     }
@@ -223,6 +268,18 @@ impl event::CommandSink for CommandSink {
         self.0.dyn_set_loading_times(time)
     }
 
+    fn initialize_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_initialize_load_removed_time()
+    }
+
+    fn pause_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_pause_load_removed_time()
+    }
+
+    fn resume_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_resume_load_removed_time()
+    }
+
     fn set_custom_variable(
         &self,
         name: Cow<'_, str>,
@@ -230,6 +287,14 @@ impl event::CommandSink for CommandSink {
     ) -> impl Future<Output = Result> + 'static {
         self.0.dyn_set_custom_variable(name, value)
     }
+
+    fn undo(&self) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_undo()
+    }
+
+    fn redo(&self) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_redo()
+    }
 }
 
 impl event::TimerQuery for CommandSink {
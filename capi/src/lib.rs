@@ -20,6 +20,7 @@ use std::{
     ptr, slice,
 };
 
+pub mod active_runner_component;
 pub mod analysis;
 pub mod atomic_date_time;
 pub mod attempt;
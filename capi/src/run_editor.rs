@@ -323,6 +323,14 @@ pub unsafe extern "C" fn RunEditor_active_set_name(this: &mut RunEditor, name: *
     this.active_segment().set_name(unsafe { str(name) });
 }
 
+/// Sets the name of the runner the active segment belongs to. An empty name
+/// means that the segment doesn't belong to any runner in particular.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunEditor_active_set_runner(this: &mut RunEditor, runner: *const c_char) {
+    // SAFETY: The caller guarantees that `runner` is valid.
+    this.active_segment().set_runner(unsafe { str(runner) });
+}
+
 /// Parses a split time from a string and sets it for the active segment with
 /// the chosen timing method.
 #[unsafe(no_mangle)]
@@ -472,6 +480,25 @@ pub unsafe extern "C" fn RunEditor_copy_comparison(
         .is_ok()
 }
 
+/// Parses a split time from the string provided and uses it to correct the
+/// split time of a segment in the most recent attempt for the selected timing
+/// method. This is meant for retiming an attempt after the fact, for example to
+/// account for loads that got retimed from a recording of the attempt. The
+/// segment history, the best segments and the Personal Best are updated
+/// accordingly. An empty string removes the split time. Returns <FALSE> if
+/// the split time couldn't be parsed, the attempt never reached the segment or
+/// the split times would be out of order.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunEditor_parse_and_retime_last_attempt(
+    this: &mut RunEditor,
+    segment_index: usize,
+    time: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `time` is valid.
+    this.parse_and_retime_last_attempt(segment_index, unsafe { str(time) })
+        .is_ok()
+}
+
 /// Clears out the Attempt History and the Segment Histories of all the
 /// segments.
 #[unsafe(no_mangle)]
@@ -28,6 +28,13 @@ pub extern "C" fn Segment_name(this: &Segment) -> *const c_char {
     output_str(this.name())
 }
 
+/// Accesses the name of the runner the segment belongs to. It is empty if the
+/// segment doesn't belong to any runner in particular.
+#[unsafe(no_mangle)]
+pub extern "C" fn Segment_runner(this: &Segment) -> *const c_char {
+    output_str(this.runner())
+}
+
 /// Accesses the segment icon's data. If there is no segment icon, this returns
 /// an empty buffer.
 #[unsafe(no_mangle)]
@@ -27,4 +27,30 @@ impl ServerProtocol {
     pub fn encodeEvent(event: u32) -> Option<String> {
         Some(server_protocol::encode_event(event.try_into().ok()?))
     }
+
+    /// Handles an incoming command and returns the response to be sent. The
+    /// response has a `type` field that specifies that it is a response, so it
+    /// can be told apart from the events encoded with `encodeTaggedEvent`.
+    pub async unsafe fn handleTaggedCommand(
+        command: &str,
+        commandSink: *const CommandSink,
+    ) -> String {
+        // SAFETY: The caller must ensure that the pointer is valid.
+        server_protocol::handle_command_with_format(
+            command,
+            unsafe { &*commandSink },
+            server_protocol::MessageFormat::Tagged,
+        )
+        .await
+    }
+
+    /// Encodes an event that happened to be sent. The event has a `type` field
+    /// that specifies that it is an event, so it can be told apart from the
+    /// responses of `handleTaggedCommand`.
+    pub fn encodeTaggedEvent(event: u32) -> Option<String> {
+        Some(server_protocol::encode_event_with_format(
+            event.try_into().ok()?,
+            server_protocol::MessageFormat::Tagged,
+        ))
+    }
 }
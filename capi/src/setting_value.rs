@@ -134,6 +134,7 @@ pub unsafe extern "C" fn SettingValue_from_optional_timing_method(
         let value = match value {
             "RealTime" => TimingMethod::RealTime,
             "GameTime" => TimingMethod::GameTime,
+            "LoadRemovedTime" => TimingMethod::LoadRemovedTime,
             _ => return None,
         };
         Some(Box::new(Some(value).into()))
@@ -38,6 +38,16 @@ pub extern "C" fn Time_game_time(this: &Time) -> *const NullableTimeSpan {
         .unwrap_or_else(ptr::null)
 }
 
+/// The Load Removed Time value. This may be <NULL> if this time has no Load
+/// Removed Time value.
+#[unsafe(no_mangle)]
+pub extern "C" fn Time_load_removed_time(this: &Time) -> *const NullableTimeSpan {
+    this.load_removed_time
+        .as_ref()
+        .map(|t| t as *const _)
+        .unwrap_or_else(ptr::null)
+}
+
 /// Access the time's value for the timing method specified.
 #[unsafe(no_mangle)]
 pub extern "C" fn Time_index(this: &Time, timing_method: TimingMethod) -> *const NullableTimeSpan {
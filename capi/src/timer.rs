@@ -2,11 +2,13 @@
 
 use super::{output_str, output_time, output_time_span, output_vec, str};
 use crate::{
+    atomic_date_time::NullableOwnedAtomicDateTime,
     run::{NullableOwnedRun, OwnedRun},
     shared_timer::OwnedSharedTimer,
+    time_span::NullableTimeSpan,
 };
 use livesplit_core::{
-    Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod,
+    AtomicDateTime, Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod,
     event::{Error, Event},
     run::saver::{self, livesplit::IoWrite},
 };
@@ -122,6 +124,50 @@ pub extern "C" fn Timer_start(this: &mut Timer) -> i32 {
     convert(this.start())
 }
 
+/// Schedules the Timer to start at the point in time provided, such as the
+/// start of a race or a marathon slot. Until then the Timer counts down.
+/// Scheduling a start replaces any start that was scheduled before. This
+/// requires that there is no attempt in progress. Once the scheduled point in
+/// time has passed, the next command the Timer receives starts the attempt
+/// first. Timer_poll_scheduled_start can be called regularly to start it
+/// without waiting for a command.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_schedule_start(this: &mut Timer, at: &AtomicDateTime) -> i32 {
+    convert(this.schedule_start(*at))
+}
+
+/// Cancels the start that is scheduled, if there is one.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_cancel_scheduled_start(this: &mut Timer) -> i32 {
+    convert(this.cancel_scheduled_start())
+}
+
+/// Returns the point in time the Timer is scheduled to start at. If no start
+/// is scheduled, <NULL> is returned instead.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_scheduled_start(this: &Timer) -> NullableOwnedAtomicDateTime {
+    this.scheduled_start().map(Box::new)
+}
+
+/// Returns the time that is left until the scheduled start of the Timer. If no
+/// start is scheduled, <NULL> is returned instead.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_countdown(this: &Timer) -> *const NullableTimeSpan {
+    if let Some(countdown) = this.snapshot().countdown() {
+        output_time_span(countdown)
+    } else {
+        std::ptr::null()
+    }
+}
+
+/// Starts the Timer if a start is scheduled and its point in time has been
+/// reached. The attempt is treated as if it started exactly at the scheduled
+/// point in time. Returns whether the attempt got started.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_poll_scheduled_start(this: &mut Timer) -> bool {
+    this.poll_scheduled_start().is_some()
+}
+
 /// If an attempt is in progress, stores the current time as the time of the
 /// current split. The attempt ends if the last split time is stored.
 #[unsafe(no_mangle)]
@@ -152,9 +198,9 @@ pub extern "C" fn Timer_undo_split(this: &mut Timer) -> i32 {
 }
 
 /// Checks whether the current attempt has new best segment times in any of the
-/// segments (for both TimingMethods) or a new Personal Best (for the current
-/// TimingMethod). This can be used to ask the user whether to update the splits
-/// when resetting.
+/// segments (for any of the TimingMethods) or a new Personal Best (for the
+/// current TimingMethod). This can be used to ask the user whether to update
+/// the splits when resetting.
 #[unsafe(no_mangle)]
 pub extern "C" fn Timer_current_attempt_has_new_best_times(this: &Timer) -> bool {
     this.current_attempt_has_new_best_times()
@@ -203,20 +249,62 @@ pub extern "C" fn Timer_toggle_pause_or_start(this: &mut Timer) -> i32 {
 }
 
 /// Removes all the pause times from the current time. If the current
-/// attempt is paused, it also resumes that attempt. Additionally, if the
-/// attempt is finished, the final split time is adjusted to not include the
-/// pause times as well.
-///
-/// # Warning
-///
-/// This behavior is not entirely optimal, as generally only the final split
-/// time is modified, while all other split times are left unmodified, which
-/// may not be what actually happened during the run.
+/// attempt is paused, it also resumes that attempt. Every split time that
+/// got split after a pause is adjusted to include the time the attempt was
+/// paused for, as if the attempt had never been paused.
 #[unsafe(no_mangle)]
 pub extern "C" fn Timer_undo_all_pauses(this: &mut Timer) -> i32 {
     convert(this.undo_all_pauses())
 }
 
+/// Corrects the split time of a segment of an attempt that has ended, for
+/// example to account for loads that got retimed from a recording of the
+/// attempt. The corrected split time replaces the split time of the timing
+/// method specified, so once the attempt gets reset, it is what gets stored in
+/// the segment history and compared against the best segments and the
+/// Personal Best. The split time can't be earlier than any of the split times
+/// before it or later than any of the split times after it. If the time is
+/// <NULL>, the split time is cleared, which isn't possible for the last
+/// segment, as it is the final time of the attempt. An error is returned if the
+/// segment index is out of bounds.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Timer_retime_split(
+    this: &mut Timer,
+    segment_index: usize,
+    timing_method: TimingMethod,
+    time: *const NullableTimeSpan,
+) -> i32 {
+    // SAFETY: The caller guarantees that `time` is either <NULL> or valid.
+    let time = unsafe { time.as_ref() }.copied();
+    convert(this.retime_split(segment_index, timing_method, time))
+}
+
+/// Undoes the most recent operation that can be undone. These are resetting
+/// the attempt, skipping a split, pausing and resuming the attempt, changing
+/// the game time, retiming a split and changing the comparison.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_undo(this: &mut Timer) -> i32 {
+    convert(this.undo())
+}
+
+/// Redoes the most recent operation that got undone.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_redo(this: &mut Timer) -> i32 {
+    convert(this.redo())
+}
+
+/// Returns whether there is an operation that can be undone.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_can_undo(this: &Timer) -> bool {
+    this.can_undo()
+}
+
+/// Returns whether there is an operation that can be redone.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_can_redo(this: &Timer) -> bool {
+    this.can_redo()
+}
+
 /// Returns the currently selected Timing Method.
 #[unsafe(no_mangle)]
 pub extern "C" fn Timer_current_timing_method(this: &Timer) -> TimingMethod {
@@ -329,6 +417,43 @@ pub extern "C" fn Timer_set_loading_times(this: &mut Timer, time: &TimeSpan) ->
     convert(this.set_loading_times(*time))
 }
 
+/// Returns whether the Load Removed Time is currently initialized. The Load
+/// Removed Time automatically gets uninitialized for each new attempt.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_is_load_removed_time_initialized(this: &Timer) -> bool {
+    this.is_load_removed_time_initialized()
+}
+
+/// Initializes the Load Removed Time for the current attempt. The Load Removed
+/// Time automatically gets uninitialized for each new attempt.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_initialize_load_removed_time(this: &mut Timer) -> i32 {
+    convert(this.initialize_load_removed_time())
+}
+
+/// Returns whether the Load Removed Time is currently paused, which is the case
+/// while the game is loading. If it is not paused, it automatically increments
+/// similar to Real Time.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_is_load_removed_time_paused(this: &Timer) -> bool {
+    this.is_load_removed_time_paused()
+}
+
+/// Pauses the Load Removed Time when the game starts loading, such that it
+/// doesn't automatically increment similar to Real Time.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_pause_load_removed_time(this: &mut Timer) -> i32 {
+    convert(this.pause_load_removed_time())
+}
+
+/// Resumes the Load Removed Time when the game stops loading, such that it
+/// automatically increments similar to Real Time, starting from the Load
+/// Removed Time it was paused at.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_resume_load_removed_time(this: &mut Timer) -> i32 {
+    convert(this.resume_load_removed_time())
+}
+
 /// Sets the value of a custom variable with the name specified. If the variable
 /// does not exist, a temporary variable gets created that will not be stored in
 /// the splits file.
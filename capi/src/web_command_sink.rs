@@ -5,7 +5,7 @@
 use std::{borrow::Cow, cell::Cell, convert::TryFrom, future::Future, sync::Arc};
 
 use livesplit_core::{
-    TimeSpan, Timer, TimingMethod,
+    AtomicDateTime, TimeSpan, Timer, TimingMethod,
     event::{CommandSink, Error, Event, Result, TimerQuery},
 };
 use wasm_bindgen::prelude::*;
@@ -21,6 +21,9 @@ use crate::command_sink;
 pub struct WebCommandSink {
     obj: JsValue,
     start: Option<Function>,
+    schedule_start: Option<Function>,
+    cancel_scheduled_start: Option<Function>,
+    poll_scheduled_start: Option<Function>,
     split: Option<Function>,
     split_or_start: Option<Function>,
     reset: Option<Function>,
@@ -40,7 +43,12 @@ pub struct WebCommandSink {
     pause_game_time: Option<Function>,
     resume_game_time: Option<Function>,
     set_loading_times: Option<Function>,
+    initialize_load_removed_time: Option<Function>,
+    pause_load_removed_time: Option<Function>,
+    resume_load_removed_time: Option<Function>,
     set_custom_variable: Option<Function>,
+    undo: Option<Function>,
+    redo: Option<Function>,
 
     get_timer: Function,
     locked: Cell<bool>,
@@ -53,6 +61,9 @@ impl WebCommandSink {
     pub fn new(obj: JsValue) -> Self {
         Self {
             start: get_func(&obj, "start"),
+            schedule_start: get_func(&obj, "scheduleStart"),
+            cancel_scheduled_start: get_func(&obj, "cancelScheduledStart"),
+            poll_scheduled_start: get_func(&obj, "pollScheduledStart"),
             split: get_func(&obj, "split"),
             split_or_start: get_func(&obj, "splitOrStart"),
             reset: get_func(&obj, "reset"),
@@ -72,7 +83,12 @@ impl WebCommandSink {
             pause_game_time: get_func(&obj, "pauseGameTime"),
             resume_game_time: get_func(&obj, "resumeGameTime"),
             set_loading_times: get_func(&obj, "setLoadingTimes"),
+            initialize_load_removed_time: get_func(&obj, "initializeLoadRemovedTime"),
+            pause_load_removed_time: get_func(&obj, "pauseLoadRemovedTime"),
+            resume_load_removed_time: get_func(&obj, "resumeLoadRemovedTime"),
             set_custom_variable: get_func(&obj, "setCustomVariable"),
+            undo: get_func(&obj, "undo"),
+            redo: get_func(&obj, "redo"),
 
             get_timer: get_func(&obj, "getTimer").unwrap(),
             locked: Cell::new(false),
@@ -127,6 +143,37 @@ impl CommandSink for WebCommandSink {
         handle_action_value(self.start.as_ref().and_then(|f| f.call0(&self.obj).ok()))
     }
 
+    fn schedule_start(&self, at: AtomicDateTime) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(self.schedule_start.as_ref().and_then(|f| {
+            f.call1(&self.obj, &JsValue::from_f64(&raw const at as usize as f64))
+                .ok()
+        }))
+    }
+
+    fn cancel_scheduled_start(&self) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(
+            self.cancel_scheduled_start
+                .as_ref()
+                .and_then(|f| f.call0(&self.obj).ok()),
+        )
+    }
+
+    fn poll_scheduled_start(&self) -> impl Future<Output = Result<Option<Event>>> + 'static {
+        debug_assert!(!self.locked.get());
+        let value = self
+            .poll_scheduled_start
+            .as_ref()
+            .and_then(|f| f.call0(&self.obj).ok());
+        async move {
+            match value {
+                Some(value) if value.is_undefined() || value.is_null() => Ok(None),
+                value => handle_action_value(value).await.map(Some),
+            }
+        }
+    }
+
     fn split(&self) -> impl Future<Output = Result> + 'static {
         debug_assert!(!self.locked.get());
         handle_action_value(self.split.as_ref().and_then(|f| f.call0(&self.obj).ok()))
@@ -301,6 +348,33 @@ impl CommandSink for WebCommandSink {
         }))
     }
 
+    fn initialize_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(
+            self.initialize_load_removed_time
+                .as_ref()
+                .and_then(|f| f.call0(&self.obj).ok()),
+        )
+    }
+
+    fn pause_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(
+            self.pause_load_removed_time
+                .as_ref()
+                .and_then(|f| f.call0(&self.obj).ok()),
+        )
+    }
+
+    fn resume_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(
+            self.resume_load_removed_time
+                .as_ref()
+                .and_then(|f| f.call0(&self.obj).ok()),
+        )
+    }
+
     fn set_custom_variable(
         &self,
         name: Cow<'_, str>,
@@ -316,6 +390,16 @@ impl CommandSink for WebCommandSink {
             .ok()
         }))
     }
+
+    fn undo(&self) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(self.undo.as_ref().and_then(|f| f.call0(&self.obj).ok()))
+    }
+
+    fn redo(&self) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(self.redo.as_ref().and_then(|f| f.call0(&self.obj).ok()))
+    }
 }
 
 /// type
@@ -0,0 +1,185 @@
+//! Provides functions for analyzing relay and team runs, where the segments
+//! are run by different runners. The time of a segment counts towards the
+//! runner the segment belongs to. If splits are skipped, the combined time of
+//! the skipped segments counts towards the runner of the segment that got
+//! split next.
+
+use crate::{
+    Run, TimeSpan, Timer, TimerPhase, TimingMethod, platform::prelude::*, timing::Snapshot,
+};
+
+/// Returns the names of all the runners that segments belong to, in the order
+/// they first appear in.
+pub fn runners(run: &Run) -> Vec<&str> {
+    let mut runners = Vec::new();
+    for segment in run.segments() {
+        let runner = segment.runner();
+        if !runner.is_empty() && !runners.contains(&runner) {
+            runners.push(runner);
+        }
+    }
+    runners
+}
+
+/// Returns the runner of the segment that is currently being run. If there is
+/// no attempt in progress, this is the runner of the first segment. Once the
+/// attempt ended, it is the runner of the last segment. Returns `None` if the
+/// segment doesn't belong to any runner.
+pub fn active_runner(timer: &Timer) -> Option<&str> {
+    let run = timer.run();
+    let segment_index = timer
+        .current_split_index()
+        .map_or(0, |index| index.min(run.len() - 1));
+    let runner = run.segment(segment_index).runner();
+    (!runner.is_empty()).then_some(runner)
+}
+
+/// Calculates the sum of the comparison's segment times of all the segments
+/// belonging to the runner. Returns `None` if the runner has no segments or
+/// the comparison doesn't have a segment time for any of them.
+pub fn comparison_sum(
+    run: &Run,
+    runner: &str,
+    comparison: &str,
+    method: TimingMethod,
+) -> Option<TimeSpan> {
+    let mut sum = None;
+    let mut previous = TimeSpan::zero();
+
+    for segment in run.segments() {
+        let split_time = segment.comparison(comparison)[method];
+        if segment.runner() == runner {
+            sum = Some(sum.unwrap_or_default() + (split_time? - previous));
+        }
+        if let Some(split_time) = split_time {
+            previous = split_time;
+        }
+    }
+
+    sum
+}
+
+/// Calculates how much time the runner spent in the current attempt so far.
+/// This is the sum of the segment times of the runner's segments that are
+/// already split, as well as the time spent in the current segment if it
+/// belongs to the runner. Returns `None` if there is no attempt in progress or
+/// the runner hasn't run any segment yet.
+pub fn current_sum(timer: &Snapshot<'_>, runner: &str) -> Option<TimeSpan> {
+    let method = timer.current_timing_method();
+    let segments = timer.run().segments();
+    let split_index = timer.current_split_index()?;
+
+    let mut sum = None;
+    let mut previous = TimeSpan::zero();
+
+    for segment in &segments[..split_index.min(segments.len())] {
+        if let Some(split_time) = segment.split_time()[method] {
+            if segment.runner() == runner {
+                sum = Some(sum.unwrap_or_default() + (split_time - previous));
+            }
+            previous = split_time;
+        }
+    }
+
+    if let Some(segment) = segments.get(split_index) {
+        if segment.runner() == runner {
+            if let Some(current_time) = timer.current_time()[method] {
+                sum = Some(sum.unwrap_or_default() + (current_time - previous));
+            }
+        }
+    }
+
+    sum
+}
+
+/// Calculates the runner's delta in the current attempt compared to the
+/// comparison provided. Only the runner's segments that are already split are
+/// taken into account. Returns `None` if the runner hasn't finished any
+/// segment yet that can be compared.
+pub fn delta(timer: &Snapshot<'_>, runner: &str, comparison: &str) -> Option<TimeSpan> {
+    progress(timer, runner, comparison).delta
+}
+
+/// Calculates the runner's pace, which is a prediction of the sum of the
+/// runner's segment times if the runner's pace matches the comparison for the
+/// remainder of their segments. Additionally a value is returned that indicates
+/// whether the pace is currently changing, as the runner is slower than the
+/// comparison in the current segment. If there's no attempt in progress, the
+/// comparison's sum of the runner's segments is returned instead.
+pub fn pace(timer: &Snapshot<'_>, runner: &str, comparison: &str) -> (Option<TimeSpan>, bool) {
+    let method = timer.current_timing_method();
+
+    let phase = timer.current_phase();
+    match phase {
+        TimerPhase::NotRunning => (
+            comparison_sum(timer.run(), runner, comparison, method),
+            false,
+        ),
+        TimerPhase::Ended => (current_sum(timer, runner), false),
+        TimerPhase::Running | TimerPhase::Paused => {
+            let progress = progress(timer, runner, comparison);
+            let mut delta = progress.delta.unwrap_or_default();
+            let mut is_live = false;
+
+            catch! {
+                let segment = timer.current_split()?;
+                if segment.runner() == runner {
+                    let live_delta = (timer.current_time()[method]? - progress.split_time)
+                        - (segment.comparison(comparison)[method]? - progress.comparison_time);
+                    if live_delta > TimeSpan::zero() {
+                        delta += live_delta;
+                        is_live = true;
+                    }
+                }
+            };
+
+            let value = catch! {
+                comparison_sum(timer.run(), runner, comparison, method)? + delta
+            };
+
+            (
+                value,
+                is_live && phase.updates_frequently(method) && value.is_some(),
+            )
+        }
+    }
+}
+
+struct Progress {
+    delta: Option<TimeSpan>,
+    split_time: TimeSpan,
+    comparison_time: TimeSpan,
+}
+
+/// Walks through the segments that are already split and accumulates the
+/// runner's delta. It also keeps track of the last split where both the split
+/// time and the comparison's time are known, as that's where the delta of the
+/// next segment is measured from.
+fn progress(timer: &Snapshot<'_>, runner: &str, comparison: &str) -> Progress {
+    let method = timer.current_timing_method();
+    let segments = timer.run().segments();
+    let split_index = timer.current_split_index().unwrap_or_default();
+
+    let mut progress = Progress {
+        delta: None,
+        split_time: TimeSpan::zero(),
+        comparison_time: TimeSpan::zero(),
+    };
+
+    for segment in &segments[..split_index.min(segments.len())] {
+        if let (Some(split_time), Some(comparison_time)) = (
+            segment.split_time()[method],
+            segment.comparison(comparison)[method],
+        ) {
+            if segment.runner() == runner {
+                let delta = (split_time - progress.split_time)
+                    - (comparison_time - progress.comparison_time);
+                progress.delta = Some(progress.delta.unwrap_or_default() + delta);
+            }
+            progress.split_time = split_time;
+            progress.comparison_time = comparison_time;
+        }
+    }
+
+    progress
+}
@@ -1,2 +1,3 @@
 mod empty_run;
+mod runners;
 mod semantic_colors;
@@ -0,0 +1,81 @@
+use super::super::runners;
+use crate::{
+    Timer, TimingMethod, comparison,
+    util::tests_helper::{create_run, run_with_splits, span, start_run},
+};
+
+fn create_timer() -> Timer {
+    let mut run = create_run(&["A", "B", "C"]);
+    run.segment_mut(0).set_runner("Alice");
+    run.segment_mut(1).set_runner("Bob");
+    run.segment_mut(2).set_runner("Alice");
+    let mut timer = Timer::new(run).unwrap();
+    run_with_splits(&mut timer, &[10.0, 25.0, 45.0]);
+    timer
+}
+
+#[test]
+fn runners_are_listed_in_order_of_appearance() {
+    let timer = create_timer();
+    assert_eq!(runners::runners(timer.run()), ["Alice", "Bob"]);
+}
+
+#[test]
+fn comparison_sum() {
+    let timer = create_timer();
+    let run = timer.run();
+    let pb = comparison::personal_best::NAME;
+    assert_eq!(
+        runners::comparison_sum(run, "Alice", pb, TimingMethod::GameTime),
+        Some(span(30.0))
+    );
+    assert_eq!(
+        runners::comparison_sum(run, "Bob", pb, TimingMethod::GameTime),
+        Some(span(15.0))
+    );
+    assert_eq!(
+        runners::comparison_sum(run, "Carol", pb, TimingMethod::GameTime),
+        None
+    );
+}
+
+#[test]
+fn active_runner_follows_the_current_segment() {
+    let mut timer = create_timer();
+    assert_eq!(runners::active_runner(&timer), Some("Alice"));
+
+    start_run(&mut timer);
+    timer.set_game_time(span(12.0)).unwrap();
+    timer.split().unwrap();
+    assert_eq!(runners::active_runner(&timer), Some("Bob"));
+
+    timer.set_game_time(span(30.0)).unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(50.0)).unwrap();
+    timer.split().unwrap();
+    assert_eq!(runners::active_runner(&timer), Some("Alice"));
+}
+
+#[test]
+fn sums_and_deltas_of_an_attempt_in_progress() {
+    let mut timer = create_timer();
+    let pb = comparison::personal_best::NAME;
+
+    start_run(&mut timer);
+    timer.set_game_time(span(12.0)).unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(20.0)).unwrap();
+
+    let snapshot = timer.snapshot();
+    assert_eq!(runners::current_sum(&snapshot, "Alice"), Some(span(12.0)));
+    assert_eq!(runners::current_sum(&snapshot, "Bob"), Some(span(8.0)));
+    assert_eq!(runners::delta(&snapshot, "Alice", pb), Some(span(2.0)));
+    assert_eq!(runners::delta(&snapshot, "Bob", pb), None);
+    assert_eq!(runners::pace(&snapshot, "Alice", pb).0, Some(span(32.0)));
+    assert_eq!(runners::pace(&snapshot, "Bob", pb).0, Some(span(15.0)));
+
+    // Bob is now slower than the comparison in his current segment.
+    timer.set_game_time(span(30.0)).unwrap();
+    let snapshot = timer.snapshot();
+    assert_eq!(runners::pace(&snapshot, "Bob", pb).0, Some(span(18.0)));
+}
@@ -49,19 +49,14 @@ impl ComparisonGenerator for BalancedPB {
     fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
         let mut skill_curve = SkillCurve::new();
 
-        goal::generate_for_timing_method_with_buf(
-            segments,
-            TimingMethod::RealTime,
-            None,
-            NAME,
-            &mut skill_curve,
-        );
-        goal::generate_for_timing_method_with_buf(
-            segments,
-            TimingMethod::GameTime,
-            None,
-            NAME,
-            &mut skill_curve,
-        );
+        for method in TimingMethod::all() {
+            goal::generate_for_timing_method_with_buf(
+                segments,
+                method,
+                None,
+                NAME,
+                &mut skill_curve,
+            );
+        }
     }
 }
@@ -53,8 +53,9 @@ impl ComparisonGenerator for BestSplitTimes {
                 *segment.comparison_mut(NAME) = segment.personal_best_split_time();
             }
 
-            generate(segments, attempts, TimingMethod::RealTime);
-            generate(segments, attempts, TimingMethod::GameTime);
+            for method in TimingMethod::all() {
+                generate(segments, attempts, method);
+            }
         }
     }
 }
@@ -0,0 +1,184 @@
+//! Provides the Active Runner Component and relevant types for using it. The
+//! Active Runner Component is a component for relay and team runs that shows
+//! the runner of the current segment, along with a prediction of the sum of
+//! their segment times, if their pace matches the chosen comparison for the
+//! remainder of their segments.
+
+use super::key_value;
+use crate::{
+    analysis::runners,
+    comparison,
+    platform::prelude::*,
+    settings::{Color, Field, Gradient, SettingsDescription, Value},
+    timing::{
+        Snapshot,
+        formatter::{Accuracy, Regular, TimeFormatter},
+    },
+};
+use core::fmt::Write;
+use serde_derive::{Deserialize, Serialize};
+
+/// The Active Runner Component is a component for relay and team runs that
+/// shows the runner of the current segment, along with a prediction of the sum
+/// of their segment times, if their pace matches the chosen comparison for the
+/// remainder of their segments.
+#[derive(Default, Clone)]
+pub struct Component {
+    settings: Settings,
+}
+
+/// The Settings for this component.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// The comparison chosen. Uses the Timer's current comparison if set to
+    /// `None`.
+    pub comparison_override: Option<String>,
+    /// Specifies whether to display the name of the runner and their pace in
+    /// two separate rows.
+    pub display_two_rows: bool,
+    /// The color of the label. If `None` is specified, the color is taken from
+    /// the layout.
+    pub label_color: Option<Color>,
+    /// The color of the value. If `None` is specified, the color is taken from
+    /// the layout.
+    pub value_color: Option<Color>,
+    /// The accuracy of the time shown.
+    pub accuracy: Accuracy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            background: key_value::DEFAULT_GRADIENT,
+            comparison_override: None,
+            display_two_rows: false,
+            label_color: None,
+            value_color: None,
+            accuracy: Accuracy::Seconds,
+        }
+    }
+}
+
+impl Component {
+    /// Creates a new Active Runner Component.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new Active Runner Component with the given settings.
+    pub const fn with_settings(settings: Settings) -> Self {
+        Self { settings }
+    }
+
+    /// Accesses the settings of the component.
+    pub const fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Grants mutable access to the settings of the component.
+    pub const fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    /// Accesses the name of the component.
+    pub const fn name(&self) -> &'static str {
+        "Active Runner"
+    }
+
+    /// Updates the component's state based on the timer provided.
+    pub fn update_state(&self, state: &mut key_value::State, timer: &Snapshot<'_>) {
+        let comparison = comparison::resolve(&self.settings.comparison_override, timer);
+        let comparison = comparison::or_current(comparison, timer);
+        let runner = runners::active_runner(timer);
+
+        let (pace, updates_frequently) = match runner {
+            Some(runner) => runners::pace(timer, runner, comparison),
+            None => (None, false),
+        };
+
+        state.background = self.settings.background;
+        state.key_color = self.settings.label_color;
+        state.value_color = self.settings.value_color;
+        state.semantic_color = Default::default();
+
+        state.key.clear();
+        state.key.push_str(runner.unwrap_or("Active Runner"));
+
+        state.value.clear();
+        let _ = write!(
+            state.value,
+            "{}",
+            Regular::with_accuracy(self.settings.accuracy).format(pace)
+        );
+
+        state.key_abbreviations.clear();
+        state.display_two_rows = self.settings.display_two_rows;
+        state.updates_frequently = updates_frequently;
+    }
+
+    /// Calculates the component's state based on the timer provided.
+    pub fn state(&self, timer: &Snapshot<'_>) -> key_value::State {
+        let mut state = Default::default();
+        self.update_state(&mut state, timer);
+        state
+    }
+
+    /// Accesses a generic description of the settings available for this
+    /// component and their current values.
+    pub fn settings_description(&self) -> SettingsDescription {
+        SettingsDescription::with_fields(vec![
+            Field::new(
+                "Background".into(),
+                "The background shown behind the component.".into(),
+                self.settings.background.into(),
+            ),
+            Field::new(
+                "Comparison".into(),
+                "The comparison to predict the runner's time from. If not specified, the current comparison is used.".into(),
+                self.settings.comparison_override.clone().into(),
+            ),
+            Field::new(
+                "Display 2 Rows".into(),
+                "Specifies whether to display the name of the runner and their predicted time in two separate rows.".into(),
+                self.settings.display_two_rows.into(),
+            ),
+            Field::new(
+                "Label Color".into(),
+                "The color of the runner's name. If not specified, the color is taken from the layout.".into(),
+                self.settings.label_color.into(),
+            ),
+            Field::new(
+                "Value Color".into(),
+                "The color of the predicted time. If not specified, the color is taken from the layout.".into(),
+                self.settings.value_color.into(),
+            ),
+            Field::new(
+                "Accuracy".into(),
+                "The accuracy of the predicted time shown.".into(),
+                self.settings.accuracy.into(),
+            ),
+        ])
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Value) {
+        match index {
+            0 => self.settings.background = value.into(),
+            1 => self.settings.comparison_override = value.into(),
+            2 => self.settings.display_two_rows = value.into(),
+            3 => self.settings.label_color = value.into(),
+            4 => self.settings.value_color = value.into(),
+            5 => self.settings.accuracy = value.into(),
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! information is provided as state objects in a way that can easily be
 //! visualized by any kind of User Interface.
 
+pub mod active_runner;
 pub mod blank_space;
 pub mod current_comparison;
 pub mod current_pace;
@@ -23,6 +24,7 @@ pub mod total_playtime;
 
 pub mod key_value;
 
+pub use active_runner::Component as ActiveRunner;
 pub use blank_space::Component as BlankSpace;
 pub use current_comparison::Component as CurrentComparison;
 pub use current_pace::Component as CurrentPace;
@@ -210,7 +210,7 @@ impl Component {
             };
             let mut segment_time = calculate_live_segment_time(timer, method, last_split_index);
 
-            if segment_time.is_none() && method == TimingMethod::GameTime {
+            if segment_time.is_none() && method != TimingMethod::RealTime {
                 segment_time =
                     calculate_live_segment_time(timer, TimingMethod::RealTime, last_split_index);
             }
@@ -296,7 +296,8 @@ impl Component {
             formatter::Fraction::with_accuracy(self.settings.accuracy).format(time),
         );
 
-        state.updates_frequently = phase.updates_frequently(method) && time.is_some();
+        state.updates_frequently =
+            (phase.updates_frequently(method) || timer.countdown().is_some()) && time.is_some();
         state.semantic_color = semantic_color;
         state.height = self.settings.height;
     }
@@ -13,7 +13,7 @@ use core::{future::Future, ops::Deref};
 
 use alloc::{borrow::Cow, sync::Arc};
 
-use crate::{TimeSpan, Timer, TimingMethod};
+use crate::{AtomicDateTime, TimeSpan, Timer, TimingMethod};
 
 /// An event informs you about a change in the timer.
 #[derive(
@@ -59,6 +59,26 @@ pub enum Event {
     LoadingTimesSet = 16,
     /// A custom variable has been set.
     CustomVariableSet = 17,
+    /// The most recent operation has been undone.
+    OperationUndone = 18,
+    /// The most recently undone operation has been redone.
+    OperationRedone = 19,
+    /// An attempt has been restored from a journal.
+    AttemptRestored = 20,
+    /// The load removed time has been initialized.
+    LoadRemovedTimeInitialized = 21,
+    /// The load removed time has been paused.
+    LoadRemovedTimePaused = 22,
+    /// The load removed time has been resumed.
+    LoadRemovedTimeResumed = 23,
+    /// The split time of a segment of an attempt that has ended has been
+    /// corrected.
+    SplitRetimed = 24,
+    /// A start of the timer has been scheduled and the countdown to it has
+    /// begun.
+    StartScheduled = 25,
+    /// The scheduled start of the timer has been cancelled.
+    ScheduledStartCancelled = 26,
     /// An unknown event occurred.
     #[serde(other)]
     Unknown,
@@ -85,6 +105,15 @@ impl From<u32> for Event {
             15 => Event::GameTimeResumed,
             16 => Event::LoadingTimesSet,
             17 => Event::CustomVariableSet,
+            18 => Event::OperationUndone,
+            19 => Event::OperationRedone,
+            20 => Event::AttemptRestored,
+            21 => Event::LoadRemovedTimeInitialized,
+            22 => Event::LoadRemovedTimePaused,
+            23 => Event::LoadRemovedTimeResumed,
+            24 => Event::SplitRetimed,
+            25 => Event::StartScheduled,
+            26 => Event::ScheduledStartCancelled,
             _ => Event::Unknown,
         }
     }
@@ -139,6 +168,22 @@ pub enum Error {
     TimerPaused = 15,
     /// The runner decided to not reset the run.
     RunnerDecidedAgainstReset = 16,
+    /// There is no operation to undo.
+    NothingToUndo = 17,
+    /// There is no operation to redo.
+    NothingToRedo = 18,
+    /// The load removed time is already initialized.
+    LoadRemovedTimeAlreadyInitialized = 19,
+    /// The load removed time is already paused.
+    LoadRemovedTimeAlreadyPaused = 20,
+    /// The load removed time is not paused.
+    LoadRemovedTimeNotPaused = 21,
+    /// The run is still in progress.
+    RunNotFinished = 22,
+    /// There is no start of the timer scheduled.
+    NoStartScheduled = 23,
+    /// The index is out of bounds.
+    InvalidIndex = 24,
     /// An unknown error occurred.
     #[serde(other)]
     Unknown,
@@ -164,6 +209,14 @@ impl From<u32> for Error {
             14 => Error::CouldNotParseTime,
             15 => Error::TimerPaused,
             16 => Error::RunnerDecidedAgainstReset,
+            17 => Error::NothingToUndo,
+            18 => Error::NothingToRedo,
+            19 => Error::LoadRemovedTimeAlreadyInitialized,
+            20 => Error::LoadRemovedTimeAlreadyPaused,
+            21 => Error::LoadRemovedTimeNotPaused,
+            22 => Error::RunNotFinished,
+            23 => Error::NoStartScheduled,
+            24 => Error::InvalidIndex,
             _ => Error::Unknown,
         }
     }
@@ -195,6 +248,18 @@ pub trait CommandSink {
     /// Starts the timer if there is no attempt in progress. If that's not the
     /// case, nothing happens.
     fn start(&self) -> impl Future<Output = Result> + 'static;
+    /// Schedules the timer to start at the point in time provided, if there is
+    /// no attempt in progress. Until then the timer counts down. This replaces
+    /// any start that was scheduled before.
+    fn schedule_start(&self, at: AtomicDateTime) -> impl Future<Output = Result> + 'static;
+    /// Cancels the start of the timer that is scheduled, if there is one.
+    fn cancel_scheduled_start(&self) -> impl Future<Output = Result> + 'static;
+    /// Starts the timer if a start is scheduled and its point in time has been
+    /// reached. The attempt is treated as if it started exactly at the
+    /// scheduled point in time. If no start is due, nothing happens and
+    /// [`None`] is returned. Other commands start the attempt as well, but this
+    /// allows starting it without waiting for one.
+    fn poll_scheduled_start(&self) -> impl Future<Output = Result<Option<Event>>> + 'static;
     /// If an attempt is in progress, stores the current time as the time of the
     /// current split. The attempt ends if the last split time is stored.
     fn split(&self) -> impl Future<Output = Result> + 'static;
@@ -223,15 +288,9 @@ pub trait CommandSink {
     /// Resumes an attempt that is paused.
     fn resume(&self) -> impl Future<Output = Result> + 'static;
     /// Removes all the pause times from the current time. If the current
-    /// attempt is paused, it also resumes that attempt. Additionally, if the
-    /// attempt is finished, the final split time is adjusted to not include the
-    /// pause times as well.
-    ///
-    /// # Warning
-    ///
-    /// This behavior is not entirely optimal, as generally only the final split
-    /// time is modified, while all other split times are left unmodified, which
-    /// may not be what actually happened during the run.
+    /// attempt is paused, it also resumes that attempt. Every split time that
+    /// got split after a pause is adjusted to include the time the attempt was
+    /// paused for, as if the attempt had never been paused.
     fn undo_all_pauses(&self) -> impl Future<Output = Result> + 'static;
     /// Switches the current comparison to the previous comparison in the list.
     fn switch_to_previous_comparison(&self) -> impl Future<Output = Result> + 'static;
@@ -243,7 +302,9 @@ pub trait CommandSink {
         &self,
         comparison: Cow<'_, str>,
     ) -> impl Future<Output = Result> + 'static;
-    /// Toggles between the `Real Time` and `Game Time` timing methods.
+    /// Toggles between the `Real Time` and `Game Time` timing methods. The
+    /// `Load Removed Time` is not part of the cycle and switches to `Real
+    /// Time`.
     fn toggle_timing_method(&self) -> impl Future<Output = Result> + 'static;
     /// Sets the current timing method to the timing method provided.
     fn set_current_timing_method(
@@ -268,6 +329,16 @@ pub trait CommandSink {
     /// just specify the amount of time the game has been loading. The game time
     /// is then automatically determined by Real Time - Loading Times.
     fn set_loading_times(&self, time: TimeSpan) -> impl Future<Output = Result> + 'static;
+    /// Initializes the load removed time for the current attempt. The load
+    /// removed time automatically gets uninitialized for each new attempt.
+    fn initialize_load_removed_time(&self) -> impl Future<Output = Result> + 'static;
+    /// Pauses the load removed time when the game starts loading, such that it
+    /// doesn't automatically increment similar to real time.
+    fn pause_load_removed_time(&self) -> impl Future<Output = Result> + 'static;
+    /// Resumes the load removed time when the game stops loading, such that it
+    /// automatically increments similar to real time, starting from the load
+    /// removed time it was paused at.
+    fn resume_load_removed_time(&self) -> impl Future<Output = Result> + 'static;
     /// Sets the value of a custom variable with the name specified. If the
     /// variable does not exist, a temporary variable gets created that will not
     /// be stored in the splits file.
@@ -276,6 +347,12 @@ pub trait CommandSink {
         name: Cow<'_, str>,
         value: Cow<'_, str>,
     ) -> impl Future<Output = Result> + 'static;
+    /// Undoes the most recent operation that can be undone. These are
+    /// resetting the attempt, skipping a split, pausing and resuming the
+    /// attempt, changing the game time and changing the comparison.
+    fn undo(&self) -> impl Future<Output = Result> + 'static;
+    /// Redoes the most recent operation that got undone.
+    fn redo(&self) -> impl Future<Output = Result> + 'static;
 }
 
 /// This trait provides functionality for querying information from the timer.
@@ -296,6 +373,21 @@ impl CommandSink for crate::SharedTimer {
         async move { result }
     }
 
+    fn schedule_start(&self, at: AtomicDateTime) -> impl Future<Output = Result> + 'static {
+        let result = self.write().unwrap().schedule_start(at);
+        async move { result }
+    }
+
+    fn cancel_scheduled_start(&self) -> impl Future<Output = Result> + 'static {
+        let result = self.write().unwrap().cancel_scheduled_start();
+        async move { result }
+    }
+
+    fn poll_scheduled_start(&self) -> impl Future<Output = Result<Option<Event>>> + 'static {
+        let event = self.write().unwrap().poll_scheduled_start();
+        async move { Ok(event) }
+    }
+
     fn split(&self) -> impl Future<Output = Result> + 'static {
         let result = self.write().unwrap().split();
         async move { result }
@@ -397,6 +489,21 @@ impl CommandSink for crate::SharedTimer {
         async move { result }
     }
 
+    fn initialize_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        let result = self.write().unwrap().initialize_load_removed_time();
+        async move { result }
+    }
+
+    fn pause_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        let result = self.write().unwrap().pause_load_removed_time();
+        async move { result }
+    }
+
+    fn resume_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        let result = self.write().unwrap().resume_load_removed_time();
+        async move { result }
+    }
+
     fn set_custom_variable(
         &self,
         name: Cow<'_, str>,
@@ -405,6 +512,16 @@ impl CommandSink for crate::SharedTimer {
         self.write().unwrap().set_custom_variable(name, value);
         async { Ok(Event::CustomVariableSet) }
     }
+
+    fn undo(&self) -> impl Future<Output = Result> + 'static {
+        let result = self.write().unwrap().undo();
+        async move { result }
+    }
+
+    fn redo(&self) -> impl Future<Output = Result> + 'static {
+        let result = self.write().unwrap().redo();
+        async move { result }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -420,6 +537,18 @@ impl<T: CommandSink + ?Sized> CommandSink for Arc<T> {
         CommandSink::start(&**self)
     }
 
+    fn schedule_start(&self, at: AtomicDateTime) -> impl Future<Output = Result> + 'static {
+        CommandSink::schedule_start(&**self, at)
+    }
+
+    fn cancel_scheduled_start(&self) -> impl Future<Output = Result> + 'static {
+        CommandSink::cancel_scheduled_start(&**self)
+    }
+
+    fn poll_scheduled_start(&self) -> impl Future<Output = Result<Option<Event>>> + 'static {
+        CommandSink::poll_scheduled_start(&**self)
+    }
+
     fn split(&self) -> impl Future<Output = Result> + 'static {
         CommandSink::split(&**self)
     }
@@ -502,6 +631,18 @@ impl<T: CommandSink + ?Sized> CommandSink for Arc<T> {
         CommandSink::set_loading_times(&**self, time)
     }
 
+    fn initialize_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        CommandSink::initialize_load_removed_time(&**self)
+    }
+
+    fn pause_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        CommandSink::pause_load_removed_time(&**self)
+    }
+
+    fn resume_load_removed_time(&self) -> impl Future<Output = Result> + 'static {
+        CommandSink::resume_load_removed_time(&**self)
+    }
+
     fn set_custom_variable(
         &self,
         name: Cow<'_, str>,
@@ -509,6 +650,14 @@ impl<T: CommandSink + ?Sized> CommandSink for Arc<T> {
     ) -> impl Future<Output = Result> + 'static {
         CommandSink::set_custom_variable(&**self, name, value)
     }
+
+    fn undo(&self) -> impl Future<Output = Result> + 'static {
+        CommandSink::undo(&**self)
+    }
+
+    fn redo(&self) -> impl Future<Output = Result> + 'static {
+        CommandSink::redo(&**self)
+    }
 }
 
 impl<T: TimerQuery + ?Sized> TimerQuery for Arc<T> {
@@ -31,6 +31,11 @@ pub struct HotkeyConfig {
     /// The key to use for toggling between the `Real Time` and `Game Time`
     /// timing methods.
     pub toggle_timing_method: Option<Hotkey>,
+    /// The key to use for undoing the most recent operation, such as an
+    /// accidental reset.
+    pub undo_operation: Option<Hotkey>,
+    /// The key to use for redoing the most recently undone operation.
+    pub redo_operation: Option<Hotkey>,
 }
 
 impl Default for HotkeyConfig {
@@ -46,6 +51,8 @@ impl Default for HotkeyConfig {
             previous_comparison: Some(Numpad4.into()),
             next_comparison: Some(Numpad6.into()),
             toggle_timing_method: None,
+            undo_operation: None,
+            redo_operation: None,
         }
     }
 }
@@ -100,6 +107,16 @@ impl HotkeyConfig {
                 r#"The hotkey to use for toggling between the "Real Time" and "Game Time" timing methods."#.into(),
                 self.toggle_timing_method.into(),
             ),
+            Field::new(
+                "Undo Operation".into(),
+                "The hotkey to use for undoing the most recent operation. Resetting, skipping a split, pausing, resuming, changing the game time and changing the comparison can be undone. This is useful in case you accidentally reset the attempt.".into(),
+                self.undo_operation.into(),
+            ),
+            Field::new(
+                "Redo Operation".into(),
+                "The hotkey to use for redoing the most recently undone operation.".into(),
+                self.redo_operation.into(),
+            ),
         ])
     }
 
@@ -129,6 +146,8 @@ impl HotkeyConfig {
                 self.previous_comparison,
                 self.next_comparison,
                 self.toggle_timing_method,
+                self.undo_operation,
+                self.redo_operation,
             ]
             .into_iter()
             .enumerate()
@@ -150,6 +169,8 @@ impl HotkeyConfig {
             6 => self.previous_comparison = value,
             7 => self.next_comparison = value,
             8 => self.toggle_timing_method = value,
+            9 => self.undo_operation = value,
+            10 => self.redo_operation = value,
             _ => panic!("Unsupported Setting Index"),
         }
 
@@ -29,6 +29,10 @@ enum Action {
     /// The key to use for toggling between the `Real Time` and `Game Time`
     /// timing methods.
     ToggleTimingMethod,
+    /// The key to use for undoing the most recent operation.
+    UndoOperation,
+    /// The key to use for redoing the most recently undone operation.
+    RedoOperation,
 }
 
 impl Action {
@@ -43,6 +47,8 @@ impl Action {
             Action::PreviousComparison => config.previous_comparison = hotkey,
             Action::NextComparison => config.next_comparison = hotkey,
             Action::ToggleTimingMethod => config.toggle_timing_method = hotkey,
+            Action::UndoOperation => config.undo_operation = hotkey,
+            Action::RedoOperation => config.redo_operation = hotkey,
         }
     }
 
@@ -57,6 +63,8 @@ impl Action {
             Action::PreviousComparison => config.previous_comparison,
             Action::NextComparison => config.next_comparison,
             Action::ToggleTimingMethod => config.toggle_timing_method,
+            Action::UndoOperation => config.undo_operation,
+            Action::RedoOperation => config.redo_operation,
         }
     }
 
@@ -92,6 +100,12 @@ impl Action {
             Action::ToggleTimingMethod => Box::new(move || {
                 drop(command_sink.toggle_timing_method());
             }),
+            Action::UndoOperation => Box::new(move || {
+                drop(command_sink.undo());
+            }),
+            Action::RedoOperation => Box::new(move || {
+                drop(command_sink.redo());
+            }),
         }
     }
 }
@@ -219,6 +233,16 @@ impl<S: event::CommandSink + Clone + Send + 'static> HotkeySystem<S> {
         self.set_hotkey(Action::ToggleTimingMethod, hotkey)
     }
 
+    /// Sets the key to use for undoing the most recent operation.
+    pub fn set_undo_operation(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::UndoOperation, hotkey)
+    }
+
+    /// Sets the key to use for redoing the most recently undone operation.
+    pub fn set_redo_operation(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::RedoOperation, hotkey)
+    }
+
     /// Deactivates the Hotkey System. No hotkeys will go through until it gets
     /// activated again. If it's already deactivated, nothing happens.
     pub fn deactivate(&mut self) -> Result<()> {
@@ -232,6 +256,8 @@ impl<S: event::CommandSink + Clone + Send + 'static> HotkeySystem<S> {
             self.unregister_inner(Action::PreviousComparison)?;
             self.unregister_inner(Action::NextComparison)?;
             self.unregister_inner(Action::ToggleTimingMethod)?;
+            self.unregister_inner(Action::UndoOperation)?;
+            self.unregister_inner(Action::RedoOperation)?;
         }
         self.is_active = false;
         Ok(())
@@ -250,6 +276,8 @@ impl<S: event::CommandSink + Clone + Send + 'static> HotkeySystem<S> {
             self.register_inner(Action::PreviousComparison)?;
             self.register_inner(Action::NextComparison)?;
             self.register_inner(Action::ToggleTimingMethod)?;
+            self.register_inner(Action::UndoOperation)?;
+            self.register_inner(Action::RedoOperation)?;
         }
         self.is_active = true;
         Ok(())
@@ -279,6 +307,8 @@ impl<S: event::CommandSink + Clone + Send + 'static> HotkeySystem<S> {
         self.set_next_comparison(config.next_comparison)?;
         self.set_undo_all_pauses(config.undo_all_pauses)?;
         self.set_toggle_timing_method(config.toggle_timing_method)?;
+        self.set_undo_operation(config.undo_operation)?;
+        self.set_redo_operation(config.redo_operation)?;
 
         Ok(())
     }
@@ -1,9 +1,9 @@
 use super::{ComponentSettings, ComponentState, GeneralSettings};
 use crate::{
     component::{
-        blank_space, current_comparison, current_pace, delta, detailed_timer, graph, pb_chance,
-        possible_time_save, previous_segment, segment_time, separator, splits, sum_of_best, text,
-        timer, title, total_playtime,
+        active_runner, blank_space, current_comparison, current_pace, delta, detailed_timer, graph,
+        pb_chance, possible_time_save, previous_segment, segment_time, separator, splits,
+        sum_of_best, text, timer, title, total_playtime,
     },
     platform::prelude::*,
     settings::{ImageCache, SettingsDescription, Value},
@@ -15,6 +15,8 @@ use alloc::borrow::Cow;
 /// visualize. This type can store any of the components provided by this crate.
 #[derive(Clone)]
 pub enum Component {
+    /// The Active Runner Component.
+    ActiveRunner(active_runner::Component),
     /// The Blank Space Component.
     BlankSpace(blank_space::Component),
     /// The Current Comparison Component.
@@ -51,6 +53,12 @@ pub enum Component {
     TotalPlaytime(total_playtime::Component),
 }
 
+impl From<active_runner::Component> for Component {
+    fn from(component: active_runner::Component) -> Self {
+        Self::ActiveRunner(component)
+    }
+}
+
 impl From<blank_space::Component> for Component {
     fn from(component: blank_space::Component) -> Self {
         Self::BlankSpace(component)
@@ -169,6 +177,9 @@ impl Component {
         layout_settings: &GeneralSettings,
     ) {
         match (state, self) {
+            (ComponentState::KeyValue(state), Component::ActiveRunner(component)) => {
+                component.update_state(state, timer)
+            }
             (ComponentState::BlankSpace(state), Component::BlankSpace(component)) => {
                 component.update_state(state)
             }
@@ -239,6 +250,7 @@ impl Component {
         layout_settings: &GeneralSettings,
     ) -> ComponentState {
         match self {
+            Component::ActiveRunner(component) => ComponentState::KeyValue(component.state(timer)),
             Component::BlankSpace(component) => ComponentState::BlankSpace(component.state()),
             Component::CurrentComparison(component) => {
                 ComponentState::KeyValue(component.state(timer))
@@ -283,6 +295,9 @@ impl Component {
     /// Settings Description instead.
     pub fn settings(&self) -> ComponentSettings {
         match self {
+            Component::ActiveRunner(component) => {
+                ComponentSettings::ActiveRunner(component.settings().clone())
+            }
             Component::BlankSpace(component) => {
                 ComponentSettings::BlankSpace(component.settings().clone())
             }
@@ -326,6 +341,7 @@ impl Component {
     /// Accesses the name of the component.
     pub fn name(&self) -> Cow<'_, str> {
         match self {
+            Component::ActiveRunner(component) => component.name().into(),
             Component::BlankSpace(component) => component.name().into(),
             Component::CurrentComparison(component) => component.name().into(),
             Component::CurrentPace(component) => component.name(),
@@ -368,6 +384,7 @@ impl Component {
     /// interface independent way of changing the settings.
     pub fn settings_description(&self) -> SettingsDescription {
         match self {
+            Component::ActiveRunner(component) => component.settings_description(),
             Component::BlankSpace(component) => component.settings_description(),
             Component::CurrentComparison(component) => component.settings_description(),
             Component::CurrentPace(component) => component.settings_description(),
@@ -398,6 +415,7 @@ impl Component {
     /// have a compatible type.
     pub fn set_value(&mut self, index: usize, value: Value) {
         match self {
+            Component::ActiveRunner(component) => component.set_value(index, value),
             Component::BlankSpace(component) => component.set_value(index, value),
             Component::CurrentComparison(component) => component.set_value(index, value),
             Component::CurrentPace(component) => component.set_value(index, value),
@@ -1,9 +1,9 @@
 use super::Component;
 use crate::{
     component::{
-        blank_space, current_comparison, current_pace, delta, detailed_timer, graph, pb_chance,
-        possible_time_save, previous_segment, segment_time, separator, splits, sum_of_best, text,
-        timer, title, total_playtime,
+        active_runner, blank_space, current_comparison, current_pace, delta, detailed_timer, graph,
+        pb_chance, possible_time_save, previous_segment, segment_time, separator, splits,
+        sum_of_best, text, timer, title, total_playtime,
     },
     platform::prelude::*,
 };
@@ -12,6 +12,8 @@ use serde_derive::{Deserialize, Serialize};
 /// The settings for one of the components available.
 #[derive(Clone, Serialize, Deserialize)]
 pub enum ComponentSettings {
+    /// The Settings for the Active Runner Component.
+    ActiveRunner(active_runner::Settings),
     /// The Settings for the Blank Space Component.
     BlankSpace(blank_space::Settings),
     /// The Settings for the Current Comparison Component.
@@ -51,6 +53,9 @@ pub enum ComponentSettings {
 impl From<ComponentSettings> for Component {
     fn from(settings: ComponentSettings) -> Self {
         match settings {
+            ComponentSettings::ActiveRunner(settings) => {
+                Component::ActiveRunner(active_runner::Component::with_settings(settings))
+            }
             ComponentSettings::BlankSpace(settings) => {
                 Component::BlankSpace(blank_space::Component::with_settings(settings))
             }
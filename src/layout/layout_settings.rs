@@ -12,21 +12,109 @@ pub struct LayoutSettings {
     pub general: GeneralSettings,
 }
 
+/// The error type for decoding the layout's settings from JSON.
+#[cfg(feature = "std")]
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum FromJsonError {
+    /// The layout was saved by a newer version of livesplit-core that uses a
+    /// newer version of the format.
+    #[snafu(display(
+        "The layout has version {version} of the format, but only versions up to {} are supported.",
+        LayoutSettings::VERSION
+    ))]
+    UnsupportedVersion {
+        /// The version of the format the layout was saved with.
+        version: u64,
+    },
+    /// The version of the format is not a valid number.
+    InvalidVersion,
+    /// The JSON couldn't be decoded.
+    Json {
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+}
+
+#[cfg(feature = "std")]
+impl From<serde_json::Error> for FromJsonError {
+    fn from(source: serde_json::Error) -> Self {
+        Self::Json { source }
+    }
+}
+
+/// Upgrades the JSON of a layout from one version of the format to the next.
+/// The migration at index `n` upgrades version `n` to version `n + 1`. Layouts
+/// from before the format got versioned are considered to be version 0.
+#[cfg(feature = "std")]
+const MIGRATIONS: &[fn(&mut serde_json::Value)] = &[
+    // Version 1 only introduced the version itself.
+    |_| {},
+];
+
+#[cfg(feature = "std")]
+const _: () = assert!(MIGRATIONS.len() == LayoutSettings::VERSION as usize);
+
+#[cfg(feature = "std")]
+#[derive(Serialize)]
+struct Versioned<'a> {
+    version: u32,
+    #[serde(flatten)]
+    settings: &'a LayoutSettings,
+}
+
+impl LayoutSettings {
+    /// The version of the JSON format that the layout's settings are encoded
+    /// with. It gets increased whenever the settings change in a way that
+    /// requires older layouts to be migrated.
+    pub const VERSION: u32 = 1;
+}
+
 #[cfg(feature = "std")]
 impl LayoutSettings {
-    /// Decodes the layout's settings from JSON.
-    pub fn from_json<R>(reader: R) -> serde_json::Result<LayoutSettings>
+    /// Decodes the layout's settings from JSON. Layouts saved with older
+    /// versions of the format are migrated to the current version. Layouts
+    /// saved with newer versions of the format are rejected.
+    pub fn from_json<R>(reader: R) -> Result<LayoutSettings, FromJsonError>
     where
         R: std::io::Read,
     {
-        serde_json::from_reader(reader)
+        Self::from_json_value(serde_json::from_reader(reader)?)
+    }
+
+    /// Decodes the layout's settings from JSON that is already parsed. Just
+    /// like with [`from_json`](Self::from_json), layouts saved with older
+    /// versions of the format are migrated to the current version.
+    pub fn from_json_value(mut value: serde_json::Value) -> Result<LayoutSettings, FromJsonError> {
+        use snafu::OptionExt;
+
+        let version = match value.get("version") {
+            Some(version) => version.as_u64().context(InvalidVersion)?,
+            None => 0,
+        };
+        if version > Self::VERSION as u64 {
+            return Err(FromJsonError::UnsupportedVersion { version });
+        }
+
+        for migrate in &MIGRATIONS[version as usize..] {
+            migrate(&mut value);
+        }
+
+        Ok(serde_json::from_value(value)?)
     }
 
-    /// Encodes the layout's settings as JSON.
+    /// Encodes the layout's settings as JSON, along with the version of the
+    /// format.
     pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
     where
         W: std::io::Write,
     {
-        serde_json::to_writer(writer, self)
+        serde_json::to_writer(
+            writer,
+            &Versioned {
+                version: Self::VERSION,
+                settings: self,
+            },
+        )
     }
 }
@@ -11,6 +11,7 @@ mod layout_direction;
 mod layout_settings;
 mod layout_state;
 pub mod parser;
+pub mod saver;
 
 pub use self::{
     component::Component, component_settings::ComponentSettings, component_state::ComponentState,
@@ -18,6 +19,9 @@ pub use self::{
     layout_settings::LayoutSettings, layout_state::LayoutState,
 };
 
+#[cfg(feature = "std")]
+pub use self::layout_settings::FromJsonError;
+
 use crate::{
     component::{previous_segment, splits, timer, title},
     platform::prelude::*,
@@ -45,9 +45,9 @@ mod font_resolving;
 // 1.0 units high in component space.
 // 24 pixels high in LiveSplit One's pixel coordinate space.
 // ~30.5 pixels high in the original LiveSplit's pixel coordinate space.
-const PIXEL_SPACE_RATIO: f32 = 24.0 / 30.5;
+pub(super) const PIXEL_SPACE_RATIO: f32 = 24.0 / 30.5;
 
-fn translate_size(v: u32) -> u32 {
+pub(super) fn translate_size(v: u32) -> u32 {
     (v as f32 * PIXEL_SPACE_RATIO + 0.5) as u32
 }
 
@@ -301,30 +301,34 @@ impl<T: GradientType> GradientBuilder<T> {
     }
 }
 
+/// Converts a color stored as `AARRGGBB` into a [`Color`].
+pub(super) fn color_from_argb(argb: u32) -> Color {
+    let [a, r, g, b] = argb.to_be_bytes();
+    let mut color = Color::rgba8(r, g, b, a);
+    let [r, g, b, a] = color.to_array();
+
+    // Adjust alpha based on the lightness of the color. The formula is
+    // based on two sRGB curves measured for white on top of a black
+    // background and for black on top of a white background. We interpolate
+    // between the two curves based on the lightness of the color. The
+    // problem is that we only have the foreground color, so based on the
+    // actual background color, this may be wrong. Therefore this is only a
+    // heuristic. We often have white on dark grey, instead of white on
+    // black. Because of that, we use 1.75 as the exponent denominator for
+    // the white on black case instead of the usual 2.2 for sRGB.
+    let lightness = (r + g + b) * (1.0 / 3.0);
+    color.alpha = (1.0 - lightness) * (1.0 - stable_powf(1.0 - a, 1.0 / 2.2))
+        + lightness * stable_powf(a, 1.0 / 1.75);
+
+    color
+}
+
 fn color<F>(reader: &mut Reader<'_>, func: F) -> Result<()>
 where
     F: FnOnce(Color),
 {
     text_as_escaped_string_err(reader, |text| {
-        let number = u32::from_str_radix(text, 16)?;
-        let [a, r, g, b] = number.to_be_bytes();
-        let mut color = Color::rgba8(r, g, b, a);
-        let [r, g, b, a] = color.to_array();
-
-        // Adjust alpha based on the lightness of the color. The formula is
-        // based on two sRGB curves measured for white on top of a black
-        // background and for black on top of a white background. We interpolate
-        // between the two curves based on the lightness of the color. The
-        // problem is that we only have the foreground color, so based on the
-        // actual background color, this may be wrong. Therefore this is only a
-        // heuristic. We often have white on dark grey, instead of white on
-        // black. Because of that, we use 1.75 as the exponent denominator for
-        // the white on black case instead of the usual 2.2 for sRGB.
-        let lightness = (r + g + b) * (1.0 / 3.0);
-        color.alpha = (1.0 - lightness) * (1.0 - stable_powf(1.0 - a, 1.0 / 2.2))
-            + lightness * stable_powf(a, 1.0 / 1.75);
-
-        func(color);
+        func(color_from_argb(u32::from_str_radix(text, 16)?));
         Ok(())
     })
 }
@@ -666,6 +670,7 @@ where
                 // Otherwise we need to cache the settings and load them later.
                 if let Some(component) = &mut component {
                     match component {
+                        Component::ActiveRunner(_) => end_tag(reader),
                         Component::BlankSpace(c) => blank_space::settings(reader, c),
                         Component::CurrentComparison(c) => current_comparison::settings(reader, c),
                         Component::CurrentPace(c) => current_pace::settings(reader, c),
@@ -0,0 +1,27 @@
+use super::{background, boolean, color_override, text, version};
+use crate::{component::active_runner::Component, util::xml::Writer};
+use core::fmt;
+
+// The original LiveSplit has no equivalent of the Active Runner component, so
+// it is saved as a Text component that shows its name instead.
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.4")?;
+    color_override(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.label_color,
+    )?;
+    color_override(
+        writer,
+        "TimeColor",
+        "OverrideTimeColor",
+        settings.value_color,
+    )?;
+    background(writer, &settings.background)?;
+    text(writer, "Text1", component.name())?;
+    text(writer, "Text2", "")?;
+    boolean(writer, "Display2Rows", settings.display_two_rows)
+}
@@ -0,0 +1,11 @@
+use super::{background, size, version};
+use crate::{component::blank_space::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.7")?;
+    size(writer, "SpaceHeight", settings.size)?;
+    background(writer, &settings.background)
+}
@@ -0,0 +1,23 @@
+use super::{background, boolean, color_override, version};
+use crate::{component::current_comparison::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.4")?;
+    color_override(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.label_color,
+    )?;
+    color_override(
+        writer,
+        "TimeColor",
+        "OverrideTimeColor",
+        settings.value_color,
+    )?;
+    background(writer, &settings.background)?;
+    boolean(writer, "Display2Rows", settings.display_two_rows)
+}
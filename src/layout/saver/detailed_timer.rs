@@ -0,0 +1,78 @@
+use super::{
+    accuracy, boolean, color, comparison_override, delta_background, number, original_size,
+    timer_format, timing_method_override, version,
+};
+use crate::{component::detailed_timer::Component, settings::Color, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    // The original LiveSplit stores the total height and the percentage of it
+    // that the segment timer takes up.
+    let total_height = settings.timer.height + settings.segment_timer.height;
+    let segment_timer_ratio = (0..=100u32)
+        .min_by_key(|&v| {
+            let height = (total_height as f32 * (0.01 * v as f32) + 0.5) as u32;
+            height.abs_diff(settings.segment_timer.height)
+        })
+        .unwrap_or(40);
+
+    version(writer, "1.5")?;
+    number(writer, "Height", original_size(total_height))?;
+    number(writer, "SegmentTimerSizeRatio", segment_timer_ratio)?;
+    boolean(writer, "TimerShowGradient", settings.timer.show_gradient)?;
+    boolean(
+        writer,
+        "OverrideTimerColors",
+        settings.timer.color_override.is_some(),
+    )?;
+    boolean(
+        writer,
+        "SegmentTimerShowGradient",
+        settings.segment_timer.show_gradient,
+    )?;
+    timer_format(
+        writer,
+        "TimerFormat",
+        settings.timer.digits_format,
+        settings.timer.accuracy,
+    )?;
+    timer_format(
+        writer,
+        "SegmentTimerFormat",
+        settings.segment_timer.digits_format,
+        settings.segment_timer.accuracy,
+    )?;
+    accuracy(
+        writer,
+        "SegmentTimesAccuracy",
+        settings.comparison_times_accuracy,
+    )?;
+    color(
+        writer,
+        "TimerColor",
+        settings.timer.color_override.unwrap_or_else(Color::white),
+    )?;
+
+    // These colors are always used by the original LiveSplit, so they are only
+    // written if they are specified.
+    for (tag, value) in [
+        ("SegmentTimerColor", settings.segment_timer.color_override),
+        ("SegmentLabelsColor", settings.comparison_names_color),
+        ("SegmentTimesColor", settings.comparison_times_color),
+        ("SplitNameColor", settings.segment_name_color),
+    ] {
+        if let Some(value) = value {
+            color(writer, tag, value)?;
+        }
+    }
+
+    delta_background(writer, &settings.background)?;
+    boolean(writer, "DisplayIcon", settings.display_icon)?;
+    boolean(writer, "ShowSplitName", settings.show_segment_name)?;
+    comparison_override(writer, "Comparison", settings.comparison1.as_deref())?;
+    comparison_override(writer, "Comparison2", settings.comparison2.as_deref())?;
+    boolean(writer, "HideComparison", settings.hide_second_comparison)?;
+    timing_method_override(writer, "TimingMethod", settings.timer.timing_method)
+}
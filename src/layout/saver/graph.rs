@@ -0,0 +1,38 @@
+use super::{boolean, color, comparison_override, size, version};
+use crate::{component::graph::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.5")?;
+    size(writer, "Height", settings.height)?;
+    color(writer, "BehindGraphColor", settings.behind_background_color)?;
+    color(writer, "AheadGraphColor", settings.ahead_background_color)?;
+    color(writer, "GridlinesColor", settings.grid_lines_color)?;
+    color(writer, "PartialFillColorAhead", settings.partial_fill_color)?;
+    color(
+        writer,
+        "CompleteFillColorAhead",
+        settings.complete_fill_color,
+    )?;
+    color(
+        writer,
+        "PartialFillColorBehind",
+        settings.partial_fill_color,
+    )?;
+    color(
+        writer,
+        "CompleteFillColorBehind",
+        settings.complete_fill_color,
+    )?;
+    color(writer, "GraphColor", settings.graph_lines_color)?;
+    boolean(writer, "LiveGraph", settings.live_graph)?;
+    boolean(writer, "FlipGraph", settings.flip_graph)?;
+    comparison_override(
+        writer,
+        "Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    boolean(writer, "ShowBestSegments", settings.show_best_segments)
+}
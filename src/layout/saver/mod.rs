@@ -0,0 +1,552 @@
+//! Provides the saver for layout files of the original LiveSplit.
+//!
+//! # Examples
+//!
+//! Saving the default layout as a layout file of the original LiveSplit.
+//!
+//! ```
+//! use livesplit_core::layout::{parser, saver, Layout};
+//!
+//! let layout = Layout::default_layout();
+//!
+//! let mut buf = String::new();
+//! saver::save_layout(&layout, &mut buf).expect("Couldn't save the layout");
+//!
+//! let parsed = parser::parse(&buf).expect("Couldn't parse the layout");
+//! assert_eq!(parsed.components.len(), layout.components.len());
+//! ```
+
+use super::{
+    Component, Layout, LayoutDirection,
+    parser::{PIXEL_SPACE_RATIO, color_from_argb, translate_size},
+};
+use crate::{
+    component::timer::DeltaGradient,
+    platform::prelude::*,
+    run::saver::livesplit::{LSS_IMAGE_HEADER, encode_image},
+    settings::{
+        Color, Font, FontStretch, FontStyle, FontWeight, Gradient, LayoutBackground, ListGradient,
+    },
+    timing::{
+        TimingMethod,
+        formatter::{Accuracy, DigitsFormat},
+    },
+    util::xml::{DisplayAlreadyEscaped, NO_ATTRIBUTES, Text, Writer},
+};
+use alloc::borrow::Cow;
+use core::{fmt, mem::MaybeUninit};
+
+mod active_runner;
+mod blank_space;
+mod current_comparison;
+mod current_pace;
+mod delta;
+mod detailed_timer;
+mod graph;
+mod pb_chance;
+mod possible_time_save;
+mod previous_segment;
+mod splits;
+mod sum_of_best;
+mod text;
+mod timer;
+mod title;
+mod total_playtime;
+
+const LSL_FONT_HEADER: &[u8; 229] = include_bytes!("lsl_font_header.bin");
+const LSL_FONT_STYLE_HEADER: &[u8] =
+    b"\x05\xFC\xFF\xFF\xFF\x18System.Drawing.FontStyle\x01\0\0\0\x07value__\0\x08\x02\0\0\0";
+const LSL_GRAPHICS_UNIT_HEADER: &[u8] =
+    b"\x05\xFB\xFF\xFF\xFF\x1BSystem.Drawing.GraphicsUnit\x01\0\0\0\x07value__\0\x08\x02\0\0\0";
+
+const GRAPHICS_UNIT_PIXEL: u32 = 2;
+const GRAPHICS_UNIT_POINT: u32 = 3;
+
+const fn bool(value: bool) -> Text<'static> {
+    Text::new_escaped(if value { "True" } else { "False" })
+}
+
+fn version<W: fmt::Write>(writer: &mut Writer<W>, version: &str) -> fmt::Result {
+    writer.tag_with_text_content("Version", NO_ATTRIBUTES, Text::new_escaped(version))
+}
+
+fn boolean<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, value: bool) -> fmt::Result {
+    writer.tag_with_text_content(tag, NO_ATTRIBUTES, bool(value))
+}
+
+fn text<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, value: &str) -> fmt::Result {
+    writer.tag_with_text_content(tag, NO_ATTRIBUTES, value)
+}
+
+fn number<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    value: impl fmt::Display,
+) -> fmt::Result {
+    writer.tag_with_text_content(tag, NO_ATTRIBUTES, DisplayAlreadyEscaped(value))
+}
+
+/// Reverses the conversion of the sizes from the original LiveSplit's pixel
+/// coordinate space. Every size has an original size that converts to it, as
+/// the original pixels are smaller.
+fn original_size(size: u32) -> u32 {
+    let mut original = (size as f32 / PIXEL_SPACE_RATIO) as u32;
+    while translate_size(original) < size {
+        original += 1;
+    }
+    original
+}
+
+fn size<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, size: u32) -> fmt::Result {
+    number(writer, tag, original_size(size))
+}
+
+fn color<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, color: Color) -> fmt::Result {
+    let [r, g, b, _] = color.to_rgba8();
+
+    // The parser adjusts the alpha based on the lightness of the color, so we
+    // look for the alpha that results in the closest color when parsed again.
+    let alpha = (0..=u8::MAX)
+        .min_by(|&x, &y| {
+            let distance =
+                |a| (color_from_argb(u32::from_be_bytes([a, r, g, b])).alpha - color.alpha).abs();
+            distance(x).total_cmp(&distance(y))
+        })
+        .unwrap_or(u8::MAX);
+
+    writer.tag_with_text_content(
+        tag,
+        NO_ATTRIBUTES,
+        DisplayAlreadyEscaped(format_args!("{alpha:02X}{r:02X}{g:02X}{b:02X}")),
+    )
+}
+
+/// Writes a color that only applies if it is overridden, along with whether
+/// it is overridden.
+fn color_override<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    override_tag: &str,
+    value: Option<Color>,
+) -> fmt::Result {
+    color(writer, tag, value.unwrap_or_else(Color::white))?;
+    boolean(writer, override_tag, value.is_some())
+}
+
+fn gradient_tags<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    [tag_color1, tag_color2, tag_kind]: [&str; 3],
+    kind: &str,
+    first: Color,
+    second: Color,
+) -> fmt::Result {
+    color(writer, tag_color1, first)?;
+    color(writer, tag_color2, second)?;
+    text(writer, tag_kind, kind)
+}
+
+const BACKGROUND_TAGS: [&str; 3] = ["BackgroundColor", "BackgroundColor2", "BackgroundGradient"];
+
+fn gradient<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tags: [&str; 3],
+    gradient: &Gradient,
+) -> fmt::Result {
+    let transparent = Color::transparent();
+    let (kind, first, second) = match *gradient {
+        Gradient::Transparent => ("Plain", transparent, transparent),
+        Gradient::Plain(color) => ("Plain", color, transparent),
+        Gradient::Vertical(first, second) => ("Vertical", first, second),
+        Gradient::Horizontal(first, second) => ("Horizontal", first, second),
+    };
+    gradient_tags(writer, tags, kind, first, second)
+}
+
+fn background<W: fmt::Write>(writer: &mut Writer<W>, background: &Gradient) -> fmt::Result {
+    gradient(writer, BACKGROUND_TAGS, background)
+}
+
+fn delta_background<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    background: &DeltaGradient,
+) -> fmt::Result {
+    let transparent = Color::transparent();
+    let kind = match background {
+        DeltaGradient::Gradient(gradient) => return self::background(writer, gradient),
+        DeltaGradient::DeltaPlain => "PlainWithDeltaColor",
+        DeltaGradient::DeltaVertical => "VerticalWithDeltaColor",
+        DeltaGradient::DeltaHorizontal => "HorizontalWithDeltaColor",
+    };
+    gradient_tags(writer, BACKGROUND_TAGS, kind, transparent, transparent)
+}
+
+fn list_background<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    background: &ListGradient,
+) -> fmt::Result {
+    match *background {
+        ListGradient::Same(ref gradient) => self::background(writer, gradient),
+        ListGradient::Alternating(first, second) => {
+            gradient_tags(writer, BACKGROUND_TAGS, "Alternating", first, second)
+        }
+    }
+}
+
+fn comparison_override<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    comparison: Option<&str>,
+) -> fmt::Result {
+    text(writer, tag, comparison.unwrap_or("Current Comparison"))
+}
+
+fn timing_method_override<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    timing_method: Option<TimingMethod>,
+) -> fmt::Result {
+    text(
+        writer,
+        tag,
+        match timing_method {
+            // The original LiveSplit doesn't support Load Removed Time.
+            None | Some(TimingMethod::LoadRemovedTime) => "Current Timing Method",
+            Some(TimingMethod::RealTime) => "Real Time",
+            Some(TimingMethod::GameTime) => "Game Time",
+        },
+    )
+}
+
+fn accuracy<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, accuracy: Accuracy) -> fmt::Result {
+    text(
+        writer,
+        tag,
+        match accuracy {
+            Accuracy::Seconds => "Seconds",
+            Accuracy::Tenths => "Tenths",
+            // The original LiveSplit doesn't support milliseconds.
+            Accuracy::Hundredths | Accuracy::Milliseconds => "Hundredths",
+        },
+    )
+}
+
+fn timer_format<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    digits_format: DigitsFormat,
+    accuracy: Accuracy,
+) -> fmt::Result {
+    let digits_format = match digits_format {
+        DigitsFormat::SingleDigitSeconds | DigitsFormat::DoubleDigitSeconds => "1",
+        DigitsFormat::SingleDigitMinutes | DigitsFormat::DoubleDigitMinutes => "00:01",
+        DigitsFormat::SingleDigitHours => "0:00:01",
+        DigitsFormat::DoubleDigitHours => "00:00:01",
+    };
+    let accuracy = match accuracy {
+        Accuracy::Seconds => "",
+        Accuracy::Tenths => ".2",
+        Accuracy::Hundredths | Accuracy::Milliseconds => ".23",
+    };
+    writer.tag_with_text_content(
+        tag,
+        NO_ATTRIBUTES,
+        DisplayAlreadyEscaped(format_args!("{digits_format}{accuracy}")),
+    )
+}
+
+/// Writes a font as a serialized `System.Drawing.Font`. The original LiveSplit
+/// identifies fonts by their GDI name, which includes the subfamily, so the
+/// weight and stretch are appended to the family name. Bold and italic are
+/// stored as style flags instead.
+fn font<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    font: Option<&Font>,
+    default_family: &str,
+    size: f32,
+    unit: u32,
+) -> fmt::Result {
+    let mut name = String::new();
+    let mut flags = 0u32;
+
+    match font {
+        Some(font) => {
+            name.push_str(&font.family);
+            let stretch = match font.stretch {
+                FontStretch::UltraCondensed => "UltraCondensed",
+                FontStretch::ExtraCondensed => "ExtraCondensed",
+                FontStretch::Condensed => "Condensed",
+                FontStretch::SemiCondensed => "SemiCondensed",
+                FontStretch::Normal => "",
+                FontStretch::SemiExpanded => "SemiExpanded",
+                FontStretch::Expanded => "Expanded",
+                FontStretch::ExtraExpanded => "ExtraExpanded",
+                FontStretch::UltraExpanded => "UltraExpanded",
+            };
+            let weight = match font.weight {
+                FontWeight::Thin => "Thin",
+                FontWeight::ExtraLight => "ExtraLight",
+                FontWeight::Light => "Light",
+                FontWeight::SemiLight => "SemiLight",
+                FontWeight::Normal => "",
+                FontWeight::Medium => "Medium",
+                FontWeight::SemiBold => "SemiBold",
+                FontWeight::Bold => {
+                    flags |= 1;
+                    ""
+                }
+                FontWeight::ExtraBold => "ExtraBold",
+                FontWeight::Black => "Black",
+                FontWeight::ExtraBlack => "ExtraBlack",
+            };
+            for token in [weight, stretch] {
+                if !token.is_empty() {
+                    name.push(' ');
+                    name.push_str(token);
+                }
+            }
+            if font.style != FontStyle::Normal {
+                flags |= 2;
+            }
+        }
+        None => name.push_str(default_family),
+    }
+
+    let mut buf = Vec::with_capacity(LSL_FONT_HEADER.len() + name.len() + 100);
+    buf.extend(LSL_FONT_HEADER);
+    // The length of the name is encoded as a 7-bit variable length integer.
+    let mut len = name.len();
+    while len >= 0x80 {
+        buf.push(len as u8 | 0x80);
+        len >>= 7;
+    }
+    buf.push(len as u8);
+    buf.extend(name.as_bytes());
+    buf.extend(size.to_le_bytes());
+    buf.extend(LSL_FONT_STYLE_HEADER);
+    buf.extend(flags.to_le_bytes());
+    buf.extend(LSL_GRAPHICS_UNIT_HEADER);
+    buf.extend(unit.to_le_bytes());
+    buf.push(0xB);
+
+    let mut base64_buf = Vec::new();
+    base64_buf.resize(
+        base64_simd::STANDARD.encoded_length(buf.len()),
+        MaybeUninit::uninit(),
+    );
+    let encoded = base64_simd::STANDARD
+        .encode_as_str(&buf, base64_simd::Out::from_uninit_slice(&mut base64_buf));
+
+    writer.tag(tag, |tag| {
+        tag.content(|writer| writer.cdata(Text::new_escaped(encoded)))
+    })
+}
+
+fn general_settings<W: fmt::Write>(writer: &mut Writer<W>, layout: &Layout) -> fmt::Result {
+    let settings = layout.general_settings();
+
+    writer.tag_with_content("Settings", NO_ATTRIBUTES, |writer| {
+        color(writer, "TextColor", settings.text_color)?;
+
+        let transparent = Color::transparent();
+        let (background_type, first, second) = match &settings.background {
+            LayoutBackground::Gradient(gradient) => match *gradient {
+                Gradient::Transparent => ("SolidColor", transparent, transparent),
+                Gradient::Plain(color) => ("SolidColor", color, transparent),
+                Gradient::Vertical(first, second) => ("VerticalGradient", first, second),
+                Gradient::Horizontal(first, second) => ("HorizontalGradient", first, second),
+            },
+            LayoutBackground::Image(_) => ("Image", transparent, transparent),
+        };
+        color(writer, "BackgroundColor", first)?;
+        color(writer, "BackgroundColor2", second)?;
+
+        color(
+            writer,
+            "ThinSeparatorsColor",
+            settings.thin_separators_color,
+        )?;
+        color(writer, "SeparatorsColor", settings.separators_color)?;
+        color(writer, "PersonalBestColor", settings.personal_best_color)?;
+        color(
+            writer,
+            "AheadGainingTimeColor",
+            settings.ahead_gaining_time_color,
+        )?;
+        color(
+            writer,
+            "AheadLosingTimeColor",
+            settings.ahead_losing_time_color,
+        )?;
+        color(
+            writer,
+            "BehindGainingTimeColor",
+            settings.behind_gaining_time_color,
+        )?;
+        color(
+            writer,
+            "BehindLosingTimeColor",
+            settings.behind_losing_time_color,
+        )?;
+        color(writer, "BestSegmentColor", settings.best_segment_color)?;
+        boolean(writer, "UseRainbowColor", false)?;
+        color(writer, "NotRunningColor", settings.not_running_color)?;
+        color(writer, "PausedColor", settings.paused_color)?;
+        color(writer, "TextOutlineColor", transparent)?;
+        color(
+            writer,
+            "ShadowsColor",
+            settings
+                .text_shadow
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+        )?;
+
+        font(
+            writer,
+            "TimesFont",
+            settings.times_font.as_ref(),
+            "Segoe UI",
+            12.0,
+            GRAPHICS_UNIT_POINT,
+        )?;
+        font(
+            writer,
+            "TimerFont",
+            settings.timer_font.as_ref(),
+            "Century Gothic",
+            43.75,
+            GRAPHICS_UNIT_PIXEL,
+        )?;
+        font(
+            writer,
+            "TextFont",
+            settings.text_font.as_ref(),
+            "Segoe UI",
+            12.0,
+            GRAPHICS_UNIT_POINT,
+        )?;
+
+        boolean(writer, "AlwaysOnTop", true)?;
+        boolean(writer, "ShowBestSegments", true)?;
+        boolean(writer, "AntiAliasing", true)?;
+        boolean(writer, "DropShadows", settings.text_shadow.is_some())?;
+        text(writer, "BackgroundType", background_type)?;
+
+        match &settings.background {
+            LayoutBackground::Image(image) => {
+                writer.tag("BackgroundImage", |tag| {
+                    let data = image.image.data();
+                    if data.is_empty() {
+                        return Ok(());
+                    }
+                    let mut base64_buf = Vec::new();
+                    let encoded = encode_image(
+                        data,
+                        &mut base64_buf,
+                        &mut Cow::Borrowed(&LSS_IMAGE_HEADER[..]),
+                    );
+                    tag.content(|writer| writer.cdata(Text::new_escaped(encoded)))
+                })?;
+                // The original LiveSplit doesn't support any transparency, so
+                // its opacity is used as the brightness.
+                number(writer, "ImageOpacity", image.brightness)?;
+                number(writer, "ImageBlur", image.blur)?;
+            }
+            LayoutBackground::Gradient(_) => {
+                writer.empty_tag("BackgroundImage", NO_ATTRIBUTES)?;
+                number(writer, "ImageOpacity", 1)?;
+                number(writer, "ImageBlur", 0)?;
+            }
+        }
+
+        number(writer, "Opacity", 1)
+    })
+}
+
+fn component<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let path = match component {
+        Component::ActiveRunner(_) => "LiveSplit.Text.dll",
+        Component::BlankSpace(_) => "LiveSplit.BlankSpace.dll",
+        Component::CurrentComparison(_) => "LiveSplit.CurrentComparison.dll",
+        Component::CurrentPace(_) => "LiveSplit.RunPrediction.dll",
+        Component::Delta(_) => "LiveSplit.Delta.dll",
+        Component::DetailedTimer(_) => "LiveSplit.DetailedTimer.dll",
+        Component::Graph(_) => "LiveSplit.Graph.dll",
+        Component::PbChance(_) => "PBChance.dll",
+        Component::PossibleTimeSave(_) => "LiveSplit.PossibleTimeSave.dll",
+        Component::PreviousSegment(_) => "LiveSplit.PreviousSegment.dll",
+        // The original LiveSplit has no equivalent of the Segment Time
+        // component.
+        Component::SegmentTime(_) => return Ok(()),
+        Component::Separator(_) => "",
+        Component::Splits(_) => "LiveSplit.Splits.dll",
+        Component::SumOfBest(_) => "LiveSplit.SumOfBest.dll",
+        Component::Text(_) => "LiveSplit.Text.dll",
+        Component::Timer(_) => "LiveSplit.Timer.dll",
+        Component::Title(_) => "LiveSplit.Title.dll",
+        Component::TotalPlaytime(_) => "LiveSplit.TotalPlaytime.dll",
+    };
+
+    writer.tag_with_content("Component", NO_ATTRIBUTES, |writer| {
+        text(writer, "Path", path)?;
+        writer.tag_with_content("Settings", NO_ATTRIBUTES, |writer| match component {
+            Component::ActiveRunner(c) => active_runner::settings(writer, c),
+            Component::BlankSpace(c) => blank_space::settings(writer, c),
+            Component::CurrentComparison(c) => current_comparison::settings(writer, c),
+            Component::CurrentPace(c) => current_pace::settings(writer, c),
+            Component::Delta(c) => delta::settings(writer, c),
+            Component::DetailedTimer(c) => detailed_timer::settings(writer, c),
+            Component::Graph(c) => graph::settings(writer, c),
+            Component::PbChance(c) => pb_chance::settings(writer, c),
+            Component::PossibleTimeSave(c) => possible_time_save::settings(writer, c),
+            Component::PreviousSegment(c) => previous_segment::settings(writer, c),
+            Component::SegmentTime(_) | Component::Separator(_) => Ok(()),
+            Component::Splits(c) => splits::settings(writer, c),
+            Component::SumOfBest(c) => sum_of_best::settings(writer, c),
+            Component::Text(c) => text::settings(writer, c),
+            Component::Timer(c) => timer::settings(writer, c),
+            Component::Title(c) => title::settings(writer, c),
+            Component::TotalPlaytime(c) => total_playtime::settings(writer, c),
+        })
+    })
+}
+
+/// Saves a layout as a layout file of the original LiveSplit. Settings that
+/// the original LiveSplit doesn't support are approximated or left out. The
+/// Active Runner component is saved as a Text component showing its name and
+/// the Segment Time component is skipped entirely. Settings of the original
+/// LiveSplit that livesplit-core doesn't support are filled in with their
+/// defaults.
+pub fn save_layout<W: fmt::Write>(layout: &Layout, writer: W) -> fmt::Result {
+    let writer = &mut Writer::new_with_default_header(writer)?;
+
+    writer.tag_with_content(
+        "Layout",
+        [("version", Text::new_escaped("1.6.1"))],
+        |writer| {
+            text(
+                writer,
+                "Mode",
+                match layout.general_settings().direction {
+                    LayoutDirection::Vertical => "Vertical",
+                    LayoutDirection::Horizontal => "Horizontal",
+                },
+            )?;
+            number(writer, "X", 0)?;
+            number(writer, "Y", 0)?;
+            number(writer, "VerticalWidth", -1)?;
+            number(writer, "VerticalHeight", -1)?;
+            number(writer, "HorizontalWidth", -1)?;
+            number(writer, "HorizontalHeight", -1)?;
+
+            general_settings(writer, layout)?;
+
+            writer.tag_with_content("Components", NO_ATTRIBUTES, |writer| {
+                for c in &layout.components {
+                    component(writer, c)?;
+                }
+                Ok(())
+            })
+        },
+    )
+}
@@ -0,0 +1,7 @@
+use super::version;
+use crate::{component::pb_chance::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, _: &Component) -> fmt::Result {
+    version(writer, "0.1")
+}
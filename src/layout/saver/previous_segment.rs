@@ -0,0 +1,29 @@
+use super::{accuracy, background, boolean, color_override, comparison_override, version};
+use crate::{component::previous_segment::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.6")?;
+    color_override(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.label_color,
+    )?;
+    accuracy(writer, "DeltaAccuracy", settings.accuracy)?;
+    boolean(writer, "DropDecimals", settings.drop_decimals)?;
+    comparison_override(
+        writer,
+        "Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    background(writer, &settings.background)?;
+    boolean(writer, "Display2Rows", settings.display_two_rows)?;
+    boolean(
+        writer,
+        "ShowPossibleTimeSave",
+        settings.show_possible_time_save,
+    )
+}
@@ -0,0 +1,73 @@
+use super::{
+    accuracy, boolean, comparison_override, gradient, list_background, number, text,
+    timing_method_override, version,
+};
+use crate::{
+    component::splits::{ColumnKind, ColumnUpdateWith, Component},
+    util::xml::{NO_ATTRIBUTES, Writer},
+};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.6")?;
+    gradient(
+        writer,
+        [
+            "CurrentSplitTopColor",
+            "CurrentSplitBottomColor",
+            "CurrentSplitGradient",
+        ],
+        &settings.current_split_gradient,
+    )?;
+    number(writer, "VisualSplitCount", settings.visual_split_count)?;
+    number(writer, "SplitPreviewCount", settings.split_preview_count)?;
+    boolean(writer, "ShowThinSeparators", settings.show_thin_separators)?;
+    boolean(
+        writer,
+        "AlwaysShowLastSplit",
+        settings.always_show_last_split,
+    )?;
+    accuracy(writer, "SplitTimesAccuracy", settings.split_time_accuracy)?;
+    boolean(writer, "ShowBlankSplits", settings.fill_with_blank_space)?;
+    list_background(writer, &settings.background)?;
+    boolean(writer, "SeparatorLastSplit", settings.separator_last_split)?;
+    accuracy(writer, "DeltasAccuracy", settings.delta_time_accuracy)?;
+    boolean(writer, "DropDecimals", settings.delta_drop_decimals)?;
+    boolean(writer, "Display2Rows", settings.display_two_rows)?;
+    boolean(writer, "ShowColumnLabels", settings.show_column_labels)?;
+
+    writer.tag_with_content("Columns", NO_ATTRIBUTES, |writer| {
+        // The columns are stored from left to right, so they need to be
+        // reversed. The original LiveSplit only supports time columns.
+        for column in settings.columns.iter().rev() {
+            let ColumnKind::Time(time_column) = &column.kind else {
+                continue;
+            };
+            writer.tag_with_content("Settings", NO_ATTRIBUTES, |writer| {
+                version(writer, "1.5")?;
+                text(writer, "Name", &column.name)?;
+                text(
+                    writer,
+                    "Type",
+                    match time_column.update_with {
+                        ColumnUpdateWith::DontUpdate | ColumnUpdateWith::SplitTime => "SplitTime",
+                        ColumnUpdateWith::Delta => "Delta",
+                        ColumnUpdateWith::DeltaWithFallback => "DeltaorSplitTime",
+                        ColumnUpdateWith::SegmentTime => "SegmentTime",
+                        ColumnUpdateWith::SegmentDelta => "SegmentDelta",
+                        ColumnUpdateWith::SegmentDeltaWithFallback => "SegmentDeltaorSegmentTime",
+                    },
+                )?;
+                comparison_override(
+                    writer,
+                    "Comparison",
+                    time_column.comparison_override.as_deref(),
+                )?;
+                timing_method_override(writer, "TimingMethod", time_column.timing_method)
+            })?;
+        }
+        Ok(())
+    })
+}
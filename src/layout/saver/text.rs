@@ -0,0 +1,35 @@
+use super::{background, boolean, color_override, text, version};
+use crate::{
+    component::text::{Component, Text},
+    util::xml::Writer,
+};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.4")?;
+    color_override(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.left_center_color,
+    )?;
+    color_override(
+        writer,
+        "TimeColor",
+        "OverrideTimeColor",
+        settings.right_color,
+    )?;
+    background(writer, &settings.background)?;
+
+    // The original LiveSplit can't show custom variables, so only their name
+    // is shown.
+    let (left_center, right) = match &settings.text {
+        Text::Center(text) | Text::Variable(text, _) => (text.as_str(), ""),
+        Text::Split(left, right) => (left.as_str(), right.as_str()),
+    };
+    text(writer, "Text1", left_center)?;
+    text(writer, "Text2", right)?;
+    boolean(writer, "Display2Rows", settings.display_two_rows)
+}
@@ -0,0 +1,31 @@
+use super::{
+    boolean, color, delta_background, size, timer_format, timing_method_override, version,
+};
+use crate::{component::timer::Component, settings::Color, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.5")?;
+    size(writer, "TimerHeight", settings.height)?;
+    timer_format(
+        writer,
+        "TimerFormat",
+        settings.digits_format,
+        settings.accuracy,
+    )?;
+    boolean(
+        writer,
+        "OverrideSplitColors",
+        settings.color_override.is_some(),
+    )?;
+    boolean(writer, "ShowGradient", settings.show_gradient)?;
+    color(
+        writer,
+        "TimerColor",
+        settings.color_override.unwrap_or_else(Color::white),
+    )?;
+    delta_background(writer, &settings.background)?;
+    timing_method_override(writer, "TimingMethod", settings.timing_method)
+}
@@ -0,0 +1,42 @@
+use super::{background, boolean, color, text, version};
+use crate::{
+    component::title::Component,
+    settings::{Alignment, Color},
+    util::xml::Writer,
+};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.7.3")?;
+    boolean(writer, "ShowGameName", settings.show_game_name)?;
+    boolean(writer, "ShowCategoryName", settings.show_category_name)?;
+    boolean(writer, "ShowAttemptCount", settings.show_attempt_count)?;
+    boolean(
+        writer,
+        "ShowFinishedRunsCount",
+        settings.show_finished_runs_count,
+    )?;
+    boolean(writer, "OverrideTitleColor", settings.text_color.is_some())?;
+    text(
+        writer,
+        "TextAlignment",
+        match settings.text_alignment {
+            Alignment::Auto => "0",
+            Alignment::Left => "1",
+            Alignment::Center => "2",
+        },
+    )?;
+    boolean(writer, "SingleLine", settings.display_as_single_line)?;
+    color(
+        writer,
+        "TitleColor",
+        settings.text_color.unwrap_or_else(Color::white),
+    )?;
+    background(writer, &settings.background)?;
+    boolean(writer, "DisplayGameIcon", settings.display_game_icon)?;
+    boolean(writer, "ShowRegion", settings.show_region)?;
+    boolean(writer, "ShowPlatform", settings.show_platform)?;
+    boolean(writer, "ShowVariables", settings.show_variables)
+}
@@ -85,14 +85,14 @@ pub use crate::{
     platform::DateTime,
     run::{Attempt, Editor as RunEditor, Run, RunMetadata, Segment, SegmentHistory},
     timing::{
-        AtomicDateTime, GameTime, RealTime, Time, TimeSpan, TimeStamp, Timer, TimerPhase,
-        TimingMethod,
+        AtomicDateTime, Clock, GameTime, LoadRemovedTime, RealTime, Time, TimeSpan, TimeStamp,
+        Timer, TimerGroup, TimerPhase, TimingMethod,
     },
 };
 pub use livesplit_hotkey as hotkey;
 
 #[cfg(not(feature = "std"))]
-pub use crate::platform::{register_clock, Clock, Duration};
+pub use crate::platform::{register_clock, Duration};
 
 #[cfg(feature = "std")]
 pub use crate::{hotkey_config::HotkeyConfig, hotkey_system::HotkeySystem, timing::SharedTimer};
@@ -0,0 +1,354 @@
+//! The legacy server protocol is the line based text protocol of the original
+//! LiveSplit Server. Many existing tools, such as OBS scripts, chat bots and
+//! hardware buttons, speak this protocol. Supporting it allows for those tools
+//! to be used without any changes. New tools should use the [server
+//! protocol](super::server_protocol) instead.
+//!
+//! Every command is a single line of text. Some commands have an argument,
+//! which is separated from the command by a single space. For example:
+//! ```text
+//! starttimer
+//! setgametime 1:23:45.67
+//! getdelta Personal Best
+//! ```
+//!
+//! Only the commands that retrieve information have a response, which is a
+//! single line of text as well. Times are formatted like `1:23:45.67` and
+//! deltas like `+1:23.45`. If there is no time or any other value to respond
+//! with, the response is a `-`. Commands that fail or that are not known are
+//! silently ignored.
+//!
+//! The following commands are supported:
+//!
+//! | Command | Response |
+//! |---------|----------|
+//! | `starttimer` | |
+//! | `startorsplit` | |
+//! | `split` | |
+//! | `unsplit` | |
+//! | `skipsplit` | |
+//! | `pause` | |
+//! | `resume` | |
+//! | `reset` | |
+//! | `initgametime` | |
+//! | `setgametime <time>` | |
+//! | `setloadingtimes <time>` | |
+//! | `pausegametime` | |
+//! | `unpausegametime` | |
+//! | `alwayspausegametime` | |
+//! | `setcomparison <comparison>` | |
+//! | `switchto <realtime\|gametime>` | |
+//! | `setcustomvariable ["<name>","<value>"]` | |
+//! | `getdelta [<comparison>]` | The delta of the last split |
+//! | `getlastsplittime` | The split time of the previous segment |
+//! | `getcomparisonsplittime [<comparison>]` | The comparison's time of the current segment |
+//! | `getcurrentrealtime` | The current real time |
+//! | `getcurrentgametime` | The current game time |
+//! | `getcurrenttime` | The current time |
+//! | `getfinaltime [<comparison>]` | The final time of the attempt or comparison |
+//! | `getpredictedtime [<comparison>]` | The predicted final time |
+//! | `getbestpossibletime` | The best possible final time |
+//! | `getsplitindex` | The index of the current segment or `-1` |
+//! | `getcurrentsplitname` | The name of the current segment |
+//! | `getprevioussplitname` | The name of the previous segment |
+//! | `getcurrenttimerphase` | `NotRunning`, `Running`, `Paused` or `Ended` |
+//! | `getattemptcount` | The amount of attempts |
+//! | `getcompletedcount` | The amount of attempts that got finished |
+//! | `ping` | `pong` |
+//!
+//! Some of the commands are also known by the aliases the original LiveSplit
+//! Server accepts: `getfinalsplittime`, `getlastsplitname` and
+//! `gettimerphase`.
+
+use alloc::borrow::Cow;
+
+use crate::{
+    TimeSpan, Timer, TimerPhase, TimingMethod, analysis,
+    comparison::best_segments,
+    event::{CommandSink, TimerQuery},
+    timing::formatter::{Accuracy, Delta, Regular, TimeFormatter, none_wrapper::NoneWrapper},
+};
+
+/// Handles an incoming command and returns the response to be sent, if the
+/// command has a response.
+pub async fn handle_command<S: CommandSink + TimerQuery>(
+    command: &str,
+    command_sink: &S,
+) -> Option<String> {
+    Command::parse(command)?.handle(command_sink).await
+}
+
+/// A command of the legacy server protocol.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Command<'a> {
+    /// `starttimer`: Starts the timer if there is no attempt in progress.
+    StartTimer,
+    /// `startorsplit`: Starts a new attempt or splits.
+    StartOrSplit,
+    /// `split`: Splits if an attempt is in progress.
+    Split,
+    /// `unsplit`: Removes the split time from the last split.
+    Unsplit,
+    /// `skipsplit`: Skips the current split.
+    SkipSplit,
+    /// `pause`: Pauses an active attempt that is not paused.
+    Pause,
+    /// `resume`: Resumes an attempt that is paused.
+    Resume,
+    /// `reset`: Resets the current attempt if there is one in progress.
+    Reset,
+    /// `initgametime`: Initializes game time for the current attempt.
+    InitGameTime,
+    /// `setgametime <time>`: Sets the game time to the time specified.
+    SetGameTime(TimeSpan),
+    /// `setloadingtimes <time>`: Sets the loading times, so that the game
+    /// time is determined by real time minus the loading times.
+    SetLoadingTimes(TimeSpan),
+    /// `pausegametime`: Pauses the game timer.
+    PauseGameTime,
+    /// `unpausegametime`: Resumes the game timer.
+    UnpauseGameTime,
+    /// `alwayspausegametime`: Pauses the game timer, so that it only changes
+    /// when the game time is set.
+    AlwaysPauseGameTime,
+    /// `setcomparison <comparison>`: Sets the current comparison.
+    SetComparison(Cow<'a, str>),
+    /// `switchto <realtime|gametime>`: Sets the current timing method.
+    SwitchTo(TimingMethod),
+    /// `setcustomvariable ["<name>","<value>"]`: Sets the value of a custom
+    /// variable.
+    SetCustomVariable(String, String),
+    /// `getdelta [<comparison>]`: Returns the delta of the last split.
+    GetDelta(Option<Cow<'a, str>>),
+    /// `getlastsplittime`: Returns the split time of the previous segment.
+    GetLastSplitTime,
+    /// `getcomparisonsplittime [<comparison>]`: Returns the comparison's time
+    /// of the current segment.
+    GetComparisonSplitTime(Option<Cow<'a, str>>),
+    /// `getcurrentrealtime`: Returns the current real time.
+    GetCurrentRealTime,
+    /// `getcurrentgametime`: Returns the current game time, or the current
+    /// real time if the game time is not initialized.
+    GetCurrentGameTime,
+    /// `getcurrenttime`: Returns the current time of the current timing
+    /// method, or the current real time if the game time is not initialized.
+    GetCurrentTime,
+    /// `getfinaltime [<comparison>]`: Returns the final time of the attempt
+    /// if it ended, otherwise the final time of the comparison.
+    GetFinalTime(Option<Cow<'a, str>>),
+    /// `getpredictedtime [<comparison>]`: Returns the predicted final time, if
+    /// the pace matches the comparison for the remainder of the run.
+    GetPredictedTime(Option<Cow<'a, str>>),
+    /// `getbestpossibletime`: Returns the best possible final time.
+    GetBestPossibleTime,
+    /// `getsplitindex`: Returns the index of the current segment, or `-1` if
+    /// there is no attempt in progress.
+    GetSplitIndex,
+    /// `getcurrentsplitname`: Returns the name of the current segment.
+    GetCurrentSplitName,
+    /// `getprevioussplitname`: Returns the name of the previous segment.
+    GetPreviousSplitName,
+    /// `getcurrenttimerphase`: Returns the current timer phase.
+    GetCurrentTimerPhase,
+    /// `getattemptcount`: Returns the amount of attempts.
+    GetAttemptCount,
+    /// `getcompletedcount`: Returns the amount of attempts that got finished.
+    GetCompletedCount,
+    /// `ping`: Responds with `pong`.
+    Ping,
+}
+
+impl<'a> Command<'a> {
+    /// Parses a line of the legacy server protocol. Returns `None` if the
+    /// command is not known or its argument is invalid.
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (name, argument) = match line.split_once(' ') {
+            Some((name, argument)) => (name, Some(argument)),
+            None => (line, None),
+        };
+        let comparison = || argument.map(Cow::Borrowed);
+        let time = || argument?.trim().parse::<TimeSpan>().ok();
+
+        Some(match name {
+            "starttimer" => Command::StartTimer,
+            "startorsplit" => Command::StartOrSplit,
+            "split" => Command::Split,
+            "unsplit" => Command::Unsplit,
+            "skipsplit" => Command::SkipSplit,
+            "pause" => Command::Pause,
+            "resume" => Command::Resume,
+            "reset" => Command::Reset,
+            "initgametime" => Command::InitGameTime,
+            "setgametime" => Command::SetGameTime(time()?),
+            "setloadingtimes" => Command::SetLoadingTimes(time()?),
+            "pausegametime" => Command::PauseGameTime,
+            "unpausegametime" => Command::UnpauseGameTime,
+            "alwayspausegametime" => Command::AlwaysPauseGameTime,
+            "setcomparison" => Command::SetComparison(comparison()?),
+            "switchto" => Command::SwitchTo(match argument?.trim() {
+                "realtime" => TimingMethod::RealTime,
+                "gametime" => TimingMethod::GameTime,
+                _ => return None,
+            }),
+            "setcustomvariable" => {
+                let [name, value] = serde_json::from_str::<[String; 2]>(argument?).ok()?;
+                Command::SetCustomVariable(name, value)
+            }
+            "getdelta" => Command::GetDelta(comparison()),
+            "getlastsplittime" => Command::GetLastSplitTime,
+            "getcomparisonsplittime" => Command::GetComparisonSplitTime(comparison()),
+            "getcurrentrealtime" => Command::GetCurrentRealTime,
+            "getcurrentgametime" => Command::GetCurrentGameTime,
+            "getcurrenttime" => Command::GetCurrentTime,
+            "getfinaltime" | "getfinalsplittime" => Command::GetFinalTime(comparison()),
+            "getpredictedtime" => Command::GetPredictedTime(comparison()),
+            "getbestpossibletime" => Command::GetBestPossibleTime,
+            "getsplitindex" => Command::GetSplitIndex,
+            "getcurrentsplitname" => Command::GetCurrentSplitName,
+            "getprevioussplitname" | "getlastsplitname" => Command::GetPreviousSplitName,
+            "getcurrenttimerphase" | "gettimerphase" => Command::GetCurrentTimerPhase,
+            "getattemptcount" => Command::GetAttemptCount,
+            "getcompletedcount" => Command::GetCompletedCount,
+            "ping" => Command::Ping,
+            _ => return None,
+        })
+    }
+
+    async fn handle<S: CommandSink + TimerQuery>(self, command_sink: &S) -> Option<String> {
+        // The original LiveSplit Server doesn't report any errors, so the
+        // results of the commands are intentionally ignored.
+        let _ = match self {
+            Command::StartTimer => command_sink.start().await,
+            Command::StartOrSplit => command_sink.split_or_start().await,
+            Command::Split => command_sink.split().await,
+            Command::Unsplit => command_sink.undo_split().await,
+            Command::SkipSplit => command_sink.skip_split().await,
+            Command::Pause => command_sink.pause().await,
+            Command::Resume => command_sink.resume().await,
+            Command::Reset => command_sink.reset(None).await,
+            Command::InitGameTime => command_sink.initialize_game_time().await,
+            Command::SetGameTime(time) => command_sink.set_game_time(time).await,
+            Command::SetLoadingTimes(time) => command_sink.set_loading_times(time).await,
+            Command::PauseGameTime | Command::AlwaysPauseGameTime => {
+                command_sink.pause_game_time().await
+            }
+            Command::UnpauseGameTime => command_sink.resume_game_time().await,
+            Command::SetComparison(comparison) => {
+                command_sink.set_current_comparison(comparison).await
+            }
+            Command::SwitchTo(method) => command_sink.set_current_timing_method(method).await,
+            Command::SetCustomVariable(name, value) => {
+                command_sink
+                    .set_custom_variable(name.into(), value.into())
+                    .await
+            }
+            query => return Some(query.query(&command_sink.get_timer())),
+        };
+        None
+    }
+
+    fn query(self, timer: &Timer) -> String {
+        let method = timer.current_timing_method();
+        let run = timer.run();
+        let last_segment = run.segments().last().unwrap();
+
+        match self {
+            Command::GetDelta(comparison) => {
+                let comparison = comparison.as_deref().unwrap_or(timer.current_comparison());
+                let delta = match timer.current_phase() {
+                    TimerPhase::Running | TimerPhase::Paused => analysis::last_delta(
+                        run,
+                        timer.current_split_index().unwrap(),
+                        comparison,
+                        method,
+                    ),
+                    TimerPhase::Ended => catch! {
+                        last_segment.split_time()[method]?
+                            - last_segment.comparison(comparison)[method]?
+                    },
+                    TimerPhase::NotRunning => None,
+                };
+                format_delta(delta)
+            }
+            Command::GetLastSplitTime => format_time(catch! {
+                let index = timer.current_split_index()?.checked_sub(1)?;
+                run.segment(index).split_time()[method]?
+            }),
+            Command::GetComparisonSplitTime(comparison) => {
+                let comparison = comparison.as_deref().unwrap_or(timer.current_comparison());
+                format_time(catch! {
+                    timer.current_split()?.comparison(comparison)[method]?
+                })
+            }
+            Command::GetCurrentRealTime => current_time(timer, TimingMethod::RealTime),
+            Command::GetCurrentGameTime => current_time(timer, TimingMethod::GameTime),
+            Command::GetCurrentTime => current_time(timer, method),
+            Command::GetFinalTime(comparison) => format_time(match timer.current_phase() {
+                TimerPhase::Ended => timer.snapshot().current_time()[method],
+                _ => last_segment
+                    .comparison(comparison.as_deref().unwrap_or(timer.current_comparison()))
+                    [method],
+            }),
+            Command::GetPredictedTime(comparison) => {
+                let comparison = comparison.as_deref().unwrap_or(timer.current_comparison());
+                format_time(analysis::current_pace::calculate(&timer.snapshot(), comparison).0)
+            }
+            Command::GetBestPossibleTime => format_time(
+                analysis::current_pace::calculate(&timer.snapshot(), best_segments::NAME).0,
+            ),
+            Command::GetSplitIndex => match timer.current_split_index() {
+                Some(index) => index.to_string(),
+                None => "-1".into(),
+            },
+            Command::GetCurrentSplitName => timer.current_split().map_or("-", |s| s.name()).into(),
+            Command::GetPreviousSplitName => catch! {
+                let index = timer.current_split_index()?.checked_sub(1)?;
+                run.segment(index).name()
+            }
+            .unwrap_or("-")
+            .into(),
+            Command::GetCurrentTimerPhase => match timer.current_phase() {
+                TimerPhase::NotRunning => "NotRunning",
+                TimerPhase::Running => "Running",
+                TimerPhase::Paused => "Paused",
+                TimerPhase::Ended => "Ended",
+            }
+            .into(),
+            Command::GetAttemptCount => run.attempt_count().to_string(),
+            Command::GetCompletedCount => run
+                .attempt_history()
+                .iter()
+                .filter(|a| a.time().real_time.is_some())
+                .count()
+                .to_string(),
+            Command::Ping => "pong".into(),
+            _ => unreachable!("Only queries have a response"),
+        }
+    }
+}
+
+/// Formats the current time of the timing method. Just like the original
+/// LiveSplit Server, real time is used instead if game time is not
+/// initialized.
+fn current_time(timer: &Timer, method: TimingMethod) -> String {
+    let method = if method == TimingMethod::GameTime && !timer.is_game_time_initialized() {
+        TimingMethod::RealTime
+    } else {
+        method
+    };
+    format_time(timer.snapshot().current_time()[method])
+}
+
+fn format_time(time: Option<TimeSpan>) -> String {
+    NoneWrapper::new(Regular::with_accuracy(Accuracy::Hundredths), "-")
+        .format(time)
+        .to_string()
+}
+
+fn format_delta(delta: Option<TimeSpan>) -> String {
+    NoneWrapper::new(Delta::custom(false, Accuracy::Hundredths), "-")
+        .format(delta)
+        .to_string()
+}
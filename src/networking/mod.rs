@@ -3,5 +3,9 @@
 //! the leaderboards of most games. The module is optional and is not compiled
 //! in by default.
 
+#[cfg(feature = "std")]
+pub mod legacy_server_protocol;
+#[cfg(feature = "server")]
+pub mod server;
 #[cfg(feature = "std")]
 pub mod server_protocol;
@@ -0,0 +1,669 @@
+//! Provides a server that makes the [server protocol](super::server_protocol)
+//! available over the network, so the timer can be remotely controlled without
+//! having to implement a server yourself. The server listens on a single port
+//! for both TCP and WebSocket connections. Which of the two a client uses is
+//! determined by the first message it sends. At most 64 clients can be
+//! connected at the same time. Any further connections are closed right away,
+//! just like connections that don't complete the handshake, or don't send
+//! anything at all, within 10 seconds.
+//!
+//! Over TCP, every command and every message sent back is a single line of
+//! JSON that is terminated by a line feed. Over WebSocket, every command and
+//! every message sent back is a single text message instead. Web pages opened
+//! in a browser can only connect if their origin got allowed with
+//! [`Server::allow_origin`].
+//!
+//! The response to a command is only sent to the client that sent the
+//! command. The events, on the other hand, are sent to all the clients that
+//! are connected. The server doesn't know which events happen, so they need to
+//! be passed to it via an [`EventSender`]. The easiest way to do so is to
+//! subscribe to the events of the timer. By default the events and the
+//! responses are sent in the [`Plain`](MessageFormat::Plain) message format.
+//! If the clients need to tell them apart more easily, the server can use the
+//! [`Tagged`](MessageFormat::Tagged) message format instead. Every client has
+//! its own [`Session`], so each client can choose which events it wants to
+//! receive and whether it wants to receive periodic time updates.
+//!
+//! Alternatively the server can speak the [legacy server
+//! protocol](super::legacy_server_protocol) of the original LiveSplit Server,
+//! which allows for replacing it without having to change any of the tools
+//! that use it. In that case no events are sent to the clients.
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::{Run, Segment, Timer, networking::server::Server};
+//!
+//! let mut run = Run::new();
+//! run.push_segment(Segment::new("Shivering Mountain"));
+//! let timer = Timer::new(run).unwrap().into_shared();
+//!
+//! let server = Server::bind("127.0.0.1:0", timer.clone()).unwrap();
+//!
+//! let events = server.event_sender();
+//! timer
+//!     .write()
+//!     .unwrap()
+//!     .subscribe(move |event, _| events.send(event));
+//!
+//! println!("Listening on {}", server.local_addr());
+//! ```
+
+mod websocket;
+
+use super::{
+    legacy_server_protocol,
+    server_protocol::{self, MessageFormat, Session},
+};
+use crate::event::{CommandSink, Event, TimerQuery};
+use std::{
+    future::Future,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    pin::pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{self, AtomicBool, AtomicUsize},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender, TrySendError},
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, JoinHandle, Thread},
+    time::{Duration, Instant},
+};
+
+/// The longest command in bytes that a client is allowed to send. Clients that
+/// send longer commands get disconnected.
+const MAX_COMMAND_LEN: usize = 1 << 20;
+
+/// The most messages that can be queued up to be sent to a client. Clients
+/// that fall so far behind that the queue fills up get disconnected.
+const MAX_QUEUED_MESSAGES: usize = 256;
+
+/// The most clients that can be connected at the same time, including the ones
+/// that are still in the middle of their handshake. Any further connections
+/// are closed right away.
+const MAX_CLIENTS: usize = 64;
+
+/// How long a client has to send its first message, or complete its WebSocket
+/// handshake, before it gets disconnected.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A server that listens for clients that want to control the timer through
+/// the [server protocol](super::server_protocol). The server runs on its own
+/// threads and keeps running until it is dropped, which disconnects all the
+/// clients.
+pub struct Server {
+    local_addr: SocketAddr,
+    shared: Arc<Shared>,
+    listener: Option<JoinHandle<()>>,
+}
+
+/// Sends events to all the clients connected to a [`Server`]. Cloning it is
+/// cheap and it can be sent to other threads, so it can easily be moved into a
+/// subscription to the events of the timer. Once the server is dropped, the
+/// events don't get sent anywhere anymore.
+#[derive(Clone)]
+pub struct EventSender {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    dialect: Dialect,
+    is_running: AtomicBool,
+    connection_count: AtomicUsize,
+    clients: Mutex<Clients>,
+    allowed_origins: Mutex<Vec<String>>,
+}
+
+#[derive(Default)]
+struct Clients {
+    next_id: u64,
+    list: Vec<Client>,
+}
+
+struct Client {
+    id: u64,
+    sender: SyncSender<Message>,
+    stream: TcpStream,
+    session: Option<Arc<Session>>,
+}
+
+/// The session of a client that speaks the server protocol, along with a way
+/// of letting the thread that sends the time updates know when the client
+/// changes how often it wants to receive them.
+struct SessionHandle {
+    session: Arc<Session>,
+    time_updates_changed: Sender<()>,
+}
+
+/// A message that is queued up to be sent to a client.
+enum Message {
+    Text(String),
+    /// A time update, which is kept apart from the other messages, so the
+    /// next one doesn't get queued up before this one got sent.
+    TimeUpdate(String),
+    Pong(Vec<u8>),
+    Close,
+}
+
+/// The protocol the commands and responses are encoded in.
+#[derive(Copy, Clone)]
+enum Dialect {
+    Json(MessageFormat),
+    Legacy,
+}
+
+/// The protocol the messages are transported with.
+#[derive(Copy, Clone)]
+enum Protocol {
+    Tcp,
+    WebSocket,
+}
+
+impl Server {
+    /// Starts a server listening on the address provided. The port can be
+    /// chosen freely. If the port is 0, the operating system chooses an unused
+    /// port, which can be queried with [`local_addr`](Self::local_addr). All
+    /// the commands sent by the clients are passed to the command sink.
+    pub fn bind<A, S>(addr: A, command_sink: S) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+        S: CommandSink + TimerQuery + Send + Sync + 'static,
+    {
+        Self::bind_with_dialect(addr, command_sink, Dialect::Json(MessageFormat::Plain))
+    }
+
+    /// Starts a server listening on the address provided, just like
+    /// [`bind`](Self::bind), but all the responses and events are sent in the
+    /// message format provided.
+    pub fn bind_with_message_format<A, S>(
+        addr: A,
+        command_sink: S,
+        format: MessageFormat,
+    ) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+        S: CommandSink + TimerQuery + Send + Sync + 'static,
+    {
+        Self::bind_with_dialect(addr, command_sink, Dialect::Json(format))
+    }
+
+    /// Starts a server speaking the [legacy server
+    /// protocol](super::legacy_server_protocol) on the address provided. The
+    /// original LiveSplit Server uses port 16834, so that's the port to use
+    /// for replacing it. Events are not sent to the clients, as the protocol
+    /// doesn't have any.
+    pub fn bind_legacy<A, S>(addr: A, command_sink: S) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+        S: CommandSink + TimerQuery + Send + Sync + 'static,
+    {
+        Self::bind_with_dialect(addr, command_sink, Dialect::Legacy)
+    }
+
+    fn bind_with_dialect<A, S>(addr: A, command_sink: S, dialect: Dialect) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+        S: CommandSink + TimerQuery + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+
+        let shared = Arc::new(Shared {
+            dialect,
+            is_running: AtomicBool::new(true),
+            connection_count: AtomicUsize::new(0),
+            clients: Mutex::new(Clients::default()),
+            allowed_origins: Mutex::new(Vec::new()),
+        });
+
+        let listener = thread::Builder::new()
+            .name("Server Listener".into())
+            .spawn({
+                let shared = shared.clone();
+                move || listen(listener, shared, Arc::new(command_sink))
+            })?;
+
+        Ok(Self {
+            local_addr,
+            shared,
+            listener: Some(listener),
+        })
+    }
+
+    /// Returns the address the server is listening on.
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the amount of clients that are currently connected.
+    pub fn client_count(&self) -> usize {
+        self.shared.clients.lock().unwrap().list.len()
+    }
+
+    /// Creates an [`EventSender`] that sends events to all the clients
+    /// connected to the server.
+    pub fn event_sender(&self) -> EventSender {
+        EventSender {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Allows web pages of the origin provided, such as
+    /// `https://one.livesplit.org`, to connect to the server over WebSocket.
+    /// Browsers tell the server which origin the page connecting to it comes
+    /// from, so by default any web page opened in the browser could otherwise
+    /// control the timer. Such connections are rejected unless their origin
+    /// got allowed. Clients that aren't browsers usually don't send an origin
+    /// and are always allowed to connect.
+    pub fn allow_origin(&self, origin: &str) {
+        self.shared
+            .allowed_origins
+            .lock()
+            .unwrap()
+            .push(origin.trim_end_matches('/').to_owned());
+    }
+
+    /// Sends the event provided to all the clients that are connected. If the
+    /// server speaks the legacy server protocol, this does nothing.
+    pub fn send_event(&self, event: Event) {
+        self.shared.broadcast(event);
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.shared
+            .is_running
+            .store(false, atomic::Ordering::SeqCst);
+
+        // The listener is blocked on accepting the next client, so we connect
+        // to it ourselves to let it notice that it needs to stop.
+        let mut addr = self.local_addr;
+        if addr.ip().is_unspecified() {
+            addr.set_ip(if addr.is_ipv4() {
+                Ipv4Addr::LOCALHOST.into()
+            } else {
+                Ipv6Addr::LOCALHOST.into()
+            });
+        }
+        let _ = TcpStream::connect(addr);
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+
+        let clients = std::mem::take(&mut self.shared.clients.lock().unwrap().list);
+        for client in clients {
+            let _ = client.stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+impl EventSender {
+    /// Sends the event provided to all the clients that are connected.
+    pub fn send(&self, event: Event) {
+        self.shared.broadcast(event);
+    }
+}
+
+impl Shared {
+    fn broadcast(&self, event: Event) {
+        let Dialect::Json(format) = self.dialect else {
+            return;
+        };
+        let text = server_protocol::encode_event_with_format(event, format);
+        self.clients.lock().unwrap().list.retain(|client| {
+            if !client
+                .session
+                .as_ref()
+                .is_some_and(|session| session.is_subscribed_to(event))
+            {
+                return true;
+            }
+            // Waiting for a client that doesn't keep up would block the
+            // timer, so it gets disconnected instead.
+            match client.sender.try_send(Message::Text(text.clone())) {
+                Err(TrySendError::Full(_)) => {
+                    let _ = client.stream.shutdown(Shutdown::Both);
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+
+    fn is_allowed_origin(&self, origin: &str) -> bool {
+        let origin = origin.trim_end_matches('/');
+        self.allowed_origins
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+    }
+
+    /// Registers a client, unless the server is already shutting down.
+    fn add_client(
+        &self,
+        sender: SyncSender<Message>,
+        stream: TcpStream,
+        session: Option<Arc<Session>>,
+    ) -> Option<u64> {
+        let mut clients = self.clients.lock().unwrap();
+        if !self.is_running.load(atomic::Ordering::SeqCst) {
+            return None;
+        }
+        let id = clients.next_id;
+        clients.next_id += 1;
+        clients.list.push(Client {
+            id,
+            sender,
+            stream,
+            session,
+        });
+        Some(id)
+    }
+
+    fn remove_client(&self, id: u64) {
+        self.clients.lock().unwrap().list.retain(|c| c.id != id);
+    }
+}
+
+fn listen<S>(listener: TcpListener, shared: Arc<Shared>, command_sink: Arc<S>)
+where
+    S: CommandSink + TimerQuery + Send + Sync + 'static,
+{
+    for stream in listener.incoming() {
+        if !shared.is_running.load(atomic::Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else { continue };
+
+        // Every connection is served by its own threads, so the amount of
+        // connections needs to be limited. Dropping the stream closes it.
+        if shared
+            .connection_count
+            .fetch_add(1, atomic::Ordering::SeqCst)
+            >= MAX_CLIENTS
+        {
+            shared
+                .connection_count
+                .fetch_sub(1, atomic::Ordering::SeqCst);
+            continue;
+        }
+
+        let spawned = thread::Builder::new().name("Server Client".into()).spawn({
+            let shared = shared.clone();
+            let command_sink = command_sink.clone();
+            move || {
+                let _ = serve(stream, &shared, command_sink);
+                shared
+                    .connection_count
+                    .fetch_sub(1, atomic::Ordering::SeqCst);
+            }
+        });
+        if spawned.is_err() {
+            shared
+                .connection_count
+                .fetch_sub(1, atomic::Ordering::SeqCst);
+        }
+    }
+}
+
+/// Serves a single client until it disconnects. The commands are read and
+/// handled on the current thread, while everything that gets sent to the
+/// client is written by a separate thread, so that a slow client never blocks
+/// the timer when events are sent. Clients speaking the server protocol get
+/// another thread that sends them their time updates.
+fn serve<S>(stream: TcpStream, shared: &Shared, command_sink: Arc<S>) -> io::Result<()>
+where
+    S: CommandSink + TimerQuery + Send + Sync + 'static,
+{
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    // A WebSocket connection starts with an HTTP GET request, while a JSON
+    // command can never start with a G.
+    let protocol = match reader.fill_buf()?.first() {
+        None => return Ok(()),
+        Some(b'G') => {
+            websocket::accept(&mut reader, &mut &stream, |origin| {
+                shared.is_allowed_origin(origin)
+            })?;
+            Protocol::WebSocket
+        }
+        Some(_) => Protocol::Tcp,
+    };
+    stream.set_read_timeout(None)?;
+
+    let (sender, receiver) = mpsc::sync_channel(MAX_QUEUED_MESSAGES);
+    let time_update_queued = Arc::new(AtomicBool::new(false));
+
+    let (session, ticker) = match shared.dialect {
+        Dialect::Json(format) => {
+            let session = Arc::new(Session::new(format));
+            let (time_updates_changed, changes) = mpsc::channel();
+            let ticker = thread::Builder::new()
+                .name("Server Client Ticker".into())
+                .spawn({
+                    let session = session.clone();
+                    let sender = sender.clone();
+                    let time_update_queued = time_update_queued.clone();
+                    let command_sink = command_sink.clone();
+                    move || {
+                        send_time_updates(
+                            &session,
+                            &sender,
+                            &time_update_queued,
+                            &changes,
+                            &*command_sink,
+                        )
+                    }
+                })?;
+            let session = SessionHandle {
+                session,
+                time_updates_changed,
+            };
+            (Some(session), Some(ticker))
+        }
+        Dialect::Legacy => (None, None),
+    };
+
+    let Some(id) = shared.add_client(
+        sender.clone(),
+        stream.try_clone()?,
+        session.as_ref().map(|s| s.session.clone()),
+    ) else {
+        return Ok(());
+    };
+    let writer = thread::Builder::new()
+        .name("Server Client Writer".into())
+        .spawn(move || write_messages(stream, protocol, receiver, &time_update_queued))?;
+
+    let result = read_commands(
+        &mut reader,
+        protocol,
+        session.as_ref(),
+        &sender,
+        &*command_sink,
+    );
+
+    shared.remove_client(id);
+    drop(session);
+    if let Some(ticker) = ticker {
+        let _ = ticker.join();
+    }
+    drop(sender);
+    let _ = writer.join();
+
+    result
+}
+
+fn read_commands<S: CommandSink + TimerQuery>(
+    reader: &mut BufReader<TcpStream>,
+    protocol: Protocol,
+    session: Option<&SessionHandle>,
+    sender: &SyncSender<Message>,
+    command_sink: &S,
+) -> io::Result<()> {
+    let mut line = String::new();
+    let mut buffer = Vec::new();
+    loop {
+        let command = match protocol {
+            Protocol::Tcp => {
+                line.clear();
+                let limit = MAX_COMMAND_LEN as u64 + 1;
+                if reader.by_ref().take(limit).read_line(&mut line)? == 0 {
+                    return Ok(());
+                }
+                if line.len() > MAX_COMMAND_LEN {
+                    return Err(io::ErrorKind::InvalidData.into());
+                }
+                let command = line.trim();
+                if command.is_empty() {
+                    continue;
+                }
+                command.into()
+            }
+            Protocol::WebSocket => {
+                match websocket::read_message(reader, &mut buffer, MAX_COMMAND_LEN)? {
+                    websocket::Incoming::Text(text) => text,
+                    websocket::Incoming::Ping(payload) => {
+                        let _ = sender.send(Message::Pong(payload));
+                        continue;
+                    }
+                    websocket::Incoming::Close => {
+                        let _ = sender.send(Message::Close);
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        let response = match session {
+            Some(SessionHandle {
+                session,
+                time_updates_changed,
+            }) => {
+                let interval = session.time_update_interval();
+                let response = block_on(session.handle_command(&command, command_sink));
+                if session.time_update_interval() != interval {
+                    let _ = time_updates_changed.send(());
+                }
+                Some(response)
+            }
+            None => block_on(legacy_server_protocol::handle_command(
+                &command,
+                command_sink,
+            )),
+        };
+        if let Some(response) = response {
+            if sender.send(Message::Text(response)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Sends the time updates at the rate the client subscribed to them with. The
+/// thread waits for changes to the subscription in between the time updates,
+/// so it doesn't need to wake up if there is no subscription. If the previous
+/// time update is still queued up, because the client doesn't keep up, the
+/// time update is skipped, as it would be outdated by the time it gets sent
+/// anyway. It stops once the client disconnects.
+fn send_time_updates<S: TimerQuery>(
+    session: &Session,
+    sender: &SyncSender<Message>,
+    time_update_queued: &AtomicBool,
+    changes: &Receiver<()>,
+    command_sink: &S,
+) {
+    let mut next_update: Option<Instant> = None;
+    loop {
+        let change = match next_update {
+            Some(next_update) => {
+                changes.recv_timeout(next_update.saturating_duration_since(Instant::now()))
+            }
+            None => changes.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match change {
+            Ok(()) => {
+                next_update = session
+                    .time_update_interval()
+                    .map(|interval| Instant::now() + interval);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if !time_update_queued.swap(true, atomic::Ordering::SeqCst) {
+                    let text = session.encode_time_update(&command_sink.get_timer());
+                    if sender.send(Message::TimeUpdate(text)).is_err() {
+                        return;
+                    }
+                }
+                // If the updates fell behind, they continue from now on rather
+                // than trying to catch up.
+                next_update = next_update
+                    .zip(session.time_update_interval())
+                    .map(|(next_update, interval)| (next_update + interval).max(Instant::now()));
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+fn write_messages(
+    mut stream: TcpStream,
+    protocol: Protocol,
+    receiver: Receiver<Message>,
+    time_update_queued: &AtomicBool,
+) {
+    for message in receiver {
+        if let Message::TimeUpdate(_) = message {
+            time_update_queued.store(false, atomic::Ordering::SeqCst);
+        }
+        let result = match (protocol, message) {
+            (Protocol::Tcp, Message::Text(text) | Message::TimeUpdate(text)) => stream
+                .write_all(text.as_bytes())
+                .and_then(|_| stream.write_all(b"\n")),
+            (Protocol::Tcp, Message::Pong(_)) => Ok(()),
+            (Protocol::WebSocket, Message::Text(text) | Message::TimeUpdate(text)) => {
+                websocket::write_frame(&mut stream, websocket::OPCODE_TEXT, text.as_bytes())
+            }
+            (Protocol::WebSocket, Message::Pong(payload)) => {
+                websocket::write_frame(&mut stream, websocket::OPCODE_PONG, &payload)
+            }
+            (Protocol::WebSocket, Message::Close) => {
+                let _ = websocket::write_frame(&mut stream, websocket::OPCODE_CLOSE, &[]);
+                break;
+            }
+            (Protocol::Tcp, Message::Close) => break,
+        };
+        if result.is_err() {
+            break;
+        }
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+/// Drives the future to completion on the current thread. The futures of the
+/// command sinks usually complete right away, so there's no need for a full
+/// async runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! A minimal implementation of the server side of the WebSocket protocol, as
+//! specified in [RFC 6455](https://datatracker.ietf.org/doc/html/rfc6455). It
+//! only supports what is needed for exchanging the text messages of the server
+//! protocol. Extensions and subprotocols are not supported.
+
+use std::io::{self, BufRead, Read, Write};
+
+pub const OPCODE_CONTINUATION: u8 = 0x0;
+pub const OPCODE_TEXT: u8 = 0x1;
+pub const OPCODE_BINARY: u8 = 0x2;
+pub const OPCODE_CLOSE: u8 = 0x8;
+pub const OPCODE_PING: u8 = 0x9;
+pub const OPCODE_PONG: u8 = 0xA;
+
+/// The GUID that gets appended to the key of the client to calculate the key
+/// that is sent back to accept the connection.
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The longest opening handshake in bytes that a client is allowed to send.
+const MAX_REQUEST_LEN: u64 = 16 << 10;
+
+pub enum Incoming {
+    Text(String),
+    Ping(Vec<u8>),
+    Close,
+}
+
+/// Reads the opening handshake of the client and accepts the connection.
+/// Browsers send the origin of the web page that opens the connection, which
+/// needs to be allowed for the connection to be accepted. Handshakes without an
+/// origin don't come from a web page and are always accepted.
+pub fn accept(
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+    is_allowed_origin: impl FnOnce(&str) -> bool,
+) -> io::Result<()> {
+    let mut reader = reader.take(MAX_REQUEST_LEN);
+    let mut key = None;
+    let mut origin = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("Sec-WebSocket-Key") {
+                key = Some(value.trim().to_owned());
+            } else if name.eq_ignore_ascii_case("Origin") {
+                origin = Some(value.trim().to_owned());
+            }
+        }
+    }
+
+    let Some(key) = key else {
+        writer.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")?;
+        return Err(io::ErrorKind::InvalidData.into());
+    };
+
+    if origin.is_some_and(|origin| !is_allowed_origin(&origin)) {
+        writer.write_all(b"HTTP/1.1 403 Forbidden\r\nConnection: close\r\n\r\n")?;
+        return Err(io::ErrorKind::PermissionDenied.into());
+    }
+
+    write!(
+        writer,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key),
+    )
+}
+
+fn accept_key(key: &str) -> String {
+    let hash = sha1(format!("{key}{GUID}").as_bytes());
+    base64_simd::STANDARD.encode_to_string(hash)
+}
+
+/// Reads frames until either a full message or a control frame got read.
+/// Messages can be split into multiple frames and control frames are allowed
+/// to be sent in between them, so the frames of the message read so far are
+/// kept in the buffer until the message is complete. Binary messages are
+/// treated the same as text messages.
+pub fn read_message(
+    reader: &mut impl Read,
+    buffer: &mut Vec<u8>,
+    max_len: usize,
+) -> io::Result<Incoming> {
+    loop {
+        let mut header = [0; 2];
+        reader.read_exact(&mut header)?;
+        let is_final = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0F;
+        let is_masked = header[1] & 0x80 != 0;
+
+        let len = match header[1] & 0x7F {
+            126 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_be_bytes(len).into()
+            }
+            127 => {
+                let mut len = [0; 8];
+                reader.read_exact(&mut len)?;
+                u64::from_be_bytes(len)
+            }
+            len => len.into(),
+        };
+
+        // Clients always need to mask their frames and control frames can't
+        // be split up.
+        let is_control = opcode & 0x8 != 0;
+        if !is_masked || (is_control && (!is_final || len > 125)) {
+            return Err(io::ErrorKind::InvalidData.into());
+        }
+
+        let mut mask = [0; 4];
+        reader.read_exact(&mut mask)?;
+
+        let start = buffer.len();
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|&len| len <= max_len.saturating_sub(start))
+            .ok_or(io::ErrorKind::InvalidData)?;
+        buffer.resize(start + len, 0);
+        let payload = &mut buffer[start..];
+        reader.read_exact(payload)?;
+        for (byte, mask) in payload.iter_mut().zip(mask.iter().cycle()) {
+            *byte ^= mask;
+        }
+
+        match opcode {
+            OPCODE_CONTINUATION | OPCODE_TEXT | OPCODE_BINARY => {
+                if is_final {
+                    return String::from_utf8(core::mem::take(buffer))
+                        .map(Incoming::Text)
+                        .map_err(|_| io::ErrorKind::InvalidData.into());
+                }
+            }
+            OPCODE_CLOSE => return Ok(Incoming::Close),
+            OPCODE_PING => return Ok(Incoming::Ping(buffer.split_off(start))),
+            OPCODE_PONG => buffer.truncate(start),
+            _ => return Err(io::ErrorKind::InvalidData.into()),
+        }
+    }
+}
+
+/// Writes a single unmasked frame that contains the whole payload.
+pub fn write_frame(writer: &mut impl Write, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame)
+}
+
+/// Calculates the SHA-1 hash of the data. SHA-1 is not considered secure
+/// anymore, but the WebSocket handshake requires it and doesn't rely on its
+/// security.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut words = [0; 80];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.into_iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (state, value) in state.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut hash = [0; 20];
+    for (bytes, value) in hash.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    hash
+}
@@ -30,6 +30,19 @@
 //! An optional `message` field may be present to provide additional information
 //! about the error.
 //!
+//! A command may also have an `id` field, which can be any JSON value. The
+//! response to the command then has the same `id`, which allows for telling
+//! which response belongs to which command, even if multiple commands are sent
+//! without waiting for their responses. For example:
+//! ```json
+//! { "id": 42, "command": "GetCurrentTime" }
+//! ```
+//!
+//! The response then looks like this:
+//! ```json
+//! { "id": 42, "success": "00:12:34.567000000" }
+//! ```
+//!
 //! You are also sent events that indicate changes in the timer. The events look
 //! like this:
 //! ```json
@@ -46,16 +59,50 @@
 //!
 //! This does not mean that two splits happened.
 //!
+//! If the events and the responses need to be told apart without looking at
+//! their fields, the [`Tagged`](MessageFormat::Tagged) message format can be
+//! used instead. Every message then has a `type` field that is either
+//! `response` or `event`:
+//! ```json
+//! { "type": "event", "event": "Splitted" }
+//! { "type": "response", "success": null }
+//! ```
+//!
+//! Which events are sent can be chosen with the `SubscribeToEvents` and
+//! `UnsubscribeFromEvents` commands. Initially all events are sent. The timer's
+//! current time can also be sent periodically by subscribing to time updates:
+//! ```json
+//! { "command": "SubscribeToTimeUpdates", "rate": 10 }
+//! ```
+//!
+//! Ten times per second a time update then gets sent, which contains the
+//! current time, the current split index and the delta to the current
+//! comparison:
+//! ```json
+//! { "timeUpdate": { "currentTime": "00:12:34.567000000", "splitIndex": 3, "delta": "-00:00:05.120000000" } }
+//! ```
+//!
+//! In the [`Tagged`](MessageFormat::Tagged) message format, their `type` is
+//! `timeUpdate`. Subscriptions are specific to each client, so they are only
+//! supported when the commands are handled through a [`Session`].
+//!
 //! Keep in mind the experimental nature of the protocol. It will likely change
 //! a lot in the future.
 
 use alloc::borrow::Cow;
-use serde::Serializer;
+use core::time::Duration;
+use serde::{de, ser, Deserialize, Deserializer, Serializer};
+use std::sync::Mutex;
+use time::format_description::well_known::Rfc3339;
 
 use crate::{
+    analysis::{current_pace, delta, pb_chance, possible_time_save, sum_of_segments},
     event::{self, Event},
+    layout::{Layout, LayoutSettings, LayoutState},
+    settings::ImageCache,
     timing::formatter::{self, TimeFormatter, ASCII_MINUS},
-    TimeSpan, Timer, TimerPhase, TimingMethod,
+    util::ordered_map::Map,
+    AtomicDateTime, DateTime, Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod,
 };
 
 /// Handles an incoming command and returns the response to be sent.
@@ -63,19 +110,220 @@ pub async fn handle_command<S: event::CommandSink + event::TimerQuery>(
     command: &str,
     command_sink: &S,
 ) -> String {
-    let response = match serde_json::from_str::<Command<'_>>(command) {
-        Ok(command) => command.handle(command_sink).await.into(),
-        Err(e) => CommandResult::Error(Error::InvalidCommand {
-            message: e.to_string(),
-        }),
+    handle_command_with_format(command, command_sink, MessageFormat::Plain).await
+}
+
+/// Handles an incoming command and returns the response to be sent in the
+/// message format provided.
+pub async fn handle_command_with_format<S: event::CommandSink + event::TimerQuery>(
+    command: &str,
+    command_sink: &S,
+    format: MessageFormat,
+) -> String {
+    respond(command, command_sink, format, None).await
+}
+
+async fn respond<S: event::CommandSink + event::TimerQuery>(
+    command: &str,
+    command_sink: &S,
+    format: MessageFormat,
+    subscriptions: Option<&Mutex<Subscriptions>>,
+) -> String {
+    let (id, result) = match serde_json::from_str::<Request<'_>>(command) {
+        Ok(Request { id, command }) => {
+            (id, command.handle(command_sink, subscriptions).await.into())
+        }
+        Err(e) => (
+            // Even if the command is invalid, the id may still be intact, so
+            // the client can tell which command failed.
+            serde_json::from_str::<RequestId>(command)
+                .ok()
+                .and_then(|r| r.id),
+            CommandResult::Error(Error::InvalidCommand {
+                message: e.to_string(),
+            }),
+        ),
     };
 
-    serde_json::to_string(&response).unwrap()
+    let response = IsResponse { id, result };
+    match format {
+        MessageFormat::Plain => serde_json::to_string(&response),
+        MessageFormat::Tagged => serde_json::to_string(&Message::Response(response)),
+    }
+    .unwrap()
 }
 
 /// Encodes an event that happened to be sent.
 pub fn encode_event(event: Event) -> String {
-    serde_json::to_string(&IsEvent { event }).unwrap()
+    encode_event_with_format(event, MessageFormat::Plain)
+}
+
+/// Encodes an event that happened to be sent in the message format provided.
+pub fn encode_event_with_format(event: Event, format: MessageFormat) -> String {
+    match format {
+        MessageFormat::Plain => serde_json::to_string(&IsEvent { event }),
+        MessageFormat::Tagged => serde_json::to_string(&Message::<()>::Event(IsEvent { event })),
+    }
+    .unwrap()
+}
+
+/// The most time updates that can be requested per second.
+const MAX_TIME_UPDATE_RATE: f64 = 1000.0;
+
+/// A session handles the commands of a single client and keeps track of what
+/// the client subscribed to. Unlike the free functions, it supports the
+/// subscription commands. The methods all take the session by reference, so
+/// it can be shared between the code that handles the commands and the code
+/// that sends the events and time updates to the client.
+pub struct Session {
+    format: MessageFormat,
+    subscriptions: Mutex<Subscriptions>,
+}
+
+struct Subscriptions {
+    /// A bit for each kind of event, indexed by the event's discriminant.
+    /// There are fewer than 64 kinds of events, so they all fit.
+    events: u64,
+    time_update_interval: Option<Duration>,
+}
+
+impl Subscriptions {
+    const fn event_bit(event: Event) -> u64 {
+        1 << event as u32
+    }
+
+    fn event_mask(events: Option<Vec<Event>>) -> u64 {
+        match events {
+            Some(events) => events
+                .into_iter()
+                .fold(0, |mask, event| mask | Self::event_bit(event)),
+            None => u64::MAX,
+        }
+    }
+}
+
+// Every kind of event needs its own bit in the mask.
+const _: () = assert!((Event::Unknown as u32) < u64::BITS);
+
+impl Session {
+    /// Creates a new session that sends its messages in the message format
+    /// provided. Initially it is subscribed to all the events, but not to any
+    /// time updates.
+    pub const fn new(format: MessageFormat) -> Self {
+        Self {
+            format,
+            subscriptions: Mutex::new(Subscriptions {
+                events: u64::MAX,
+                time_update_interval: None,
+            }),
+        }
+    }
+
+    /// Returns the message format the session sends its messages in.
+    pub const fn format(&self) -> MessageFormat {
+        self.format
+    }
+
+    /// Handles an incoming command and returns the response to be sent.
+    pub async fn handle_command<S: event::CommandSink + event::TimerQuery>(
+        &self,
+        command: &str,
+        command_sink: &S,
+    ) -> String {
+        respond(
+            command,
+            command_sink,
+            self.format,
+            Some(&self.subscriptions),
+        )
+        .await
+    }
+
+    /// Returns whether the client is subscribed to the event provided.
+    pub fn is_subscribed_to(&self, event: Event) -> bool {
+        self.subscriptions.lock().unwrap().events & Subscriptions::event_bit(event) != 0
+    }
+
+    /// Encodes an event that happened to be sent, if the client is subscribed
+    /// to it.
+    pub fn encode_event(&self, event: Event) -> Option<String> {
+        self.is_subscribed_to(event)
+            .then(|| encode_event_with_format(event, self.format))
+    }
+
+    /// Returns how often a time update is supposed to be sent, if the client
+    /// is subscribed to them.
+    pub fn time_update_interval(&self) -> Option<Duration> {
+        self.subscriptions.lock().unwrap().time_update_interval
+    }
+
+    /// Encodes a time update for the current state of the timer. It contains
+    /// the current time and the delta to the current comparison, both for the
+    /// current timing method, as well as the current split index.
+    pub fn encode_time_update(&self, timer: &Timer) -> String {
+        let snapshot = timer.snapshot();
+        let time_update = IsTimeUpdate {
+            time_update: TimeUpdate {
+                current_time: snapshot.current_time()[timer.current_timing_method()]
+                    .map(format_time),
+                split_index: snapshot.current_split_index(),
+                delta: delta::calculate(&snapshot, timer.current_comparison())
+                    .0
+                    .map(format_time),
+            },
+        };
+        match self.format {
+            MessageFormat::Plain => serde_json::to_string(&time_update),
+            MessageFormat::Tagged => serde_json::to_string(&Message::<()>::TimeUpdate(time_update)),
+        }
+        .unwrap()
+    }
+}
+
+/// The format of the messages that are sent to the client.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MessageFormat {
+    /// The responses and events are sent as they are. They can be told apart
+    /// by whether they have an `event` field.
+    #[default]
+    Plain,
+    /// Every message has a `type` field that specifies whether it is a
+    /// `response` to a command or an `event`.
+    Tagged,
+}
+
+/// A command along with an optional identifier that is sent back with the
+/// response to the command.
+#[derive(Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Request<'a> {
+    /// The identifier of the request. It can be any JSON value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
+    /// The command to handle.
+    #[serde(flatten, borrow)]
+    pub command: Command<'a>,
+}
+
+#[derive(serde_derive::Deserialize)]
+struct RequestId {
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+}
+
+#[derive(serde_derive::Serialize)]
+struct IsResponse<T, E> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<serde_json::Value>,
+    #[serde(flatten)]
+    result: CommandResult<T, E>,
+}
+
+#[derive(serde_derive::Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Message<R> {
+    Response(R),
+    Event(IsEvent),
+    TimeUpdate(IsTimeUpdate),
 }
 
 #[derive(serde_derive::Serialize)]
@@ -99,6 +347,20 @@ struct IsEvent {
     event: Event,
 }
 
+#[derive(serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IsTimeUpdate {
+    time_update: TimeUpdate,
+}
+
+#[derive(serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeUpdate {
+    current_time: Option<String>,
+    split_index: Option<usize>,
+    delta: Option<String>,
+}
+
 fn serialize_time_span<S: Serializer>(
     time_span: &TimeSpan,
     serializer: S,
@@ -107,6 +369,25 @@ fn serialize_time_span<S: Serializer>(
     serializer.collect_str(&format_args!("{secs}.{:09}", nanos.abs()))
 }
 
+fn serialize_date_time<S: Serializer>(
+    date_time: &AtomicDateTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let text = date_time
+        .time
+        .format(&Rfc3339)
+        .map_err(ser::Error::custom)?;
+    serializer.serialize_str(&text)
+}
+
+fn deserialize_date_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<AtomicDateTime, D::Error> {
+    let text = Cow::<'de, str>::deserialize(deserializer)?;
+    let time = DateTime::parse(&text, &Rfc3339).map_err(de::Error::custom)?;
+    Ok(AtomicDateTime::new(time, false))
+}
+
 const fn is_false(v: &bool) -> bool {
     !*v
 }
@@ -118,6 +399,26 @@ pub enum Command<'a> {
     /// Starts the timer if there is no attempt in progress. If that's not the
     /// case, nothing happens.
     Start,
+    /// Schedules the timer to start at the point in time provided, if there is
+    /// no attempt in progress. Until then the timer counts down. This replaces
+    /// any start that was scheduled before. The point in time is specified as
+    /// an RFC 3339 date time, such as `2025-07-01T18:00:00Z`.
+    ScheduleStart {
+        /// The point in time to start the timer at.
+        #[serde(
+            serialize_with = "serialize_date_time",
+            deserialize_with = "deserialize_date_time"
+        )]
+        time: AtomicDateTime,
+    },
+    /// Cancels the start of the timer that is scheduled, if there is one.
+    CancelScheduledStart,
+    /// Starts the timer if a start is scheduled and its point in time has been
+    /// reached. The attempt is treated as if it started exactly at the
+    /// scheduled point in time. If no start is due, nothing happens. Other
+    /// commands start the attempt as well, but this allows starting it without
+    /// waiting for one.
+    PollScheduledStart,
     /// If an attempt is in progress, stores the current time as the time of the
     /// current split. The attempt ends if the last split time is stored.
     Split,
@@ -151,15 +452,9 @@ pub enum Command<'a> {
     /// Resumes an attempt that is paused.
     Resume,
     /// Removes all the pause times from the current time. If the current
-    /// attempt is paused, it also resumes that attempt. Additionally, if the
-    /// attempt is finished, the final split time is adjusted to not include the
-    /// pause times as well.
-    ///
-    /// # Warning
-    ///
-    /// This behavior is not entirely optimal, as generally only the final split
-    /// time is modified, while all other split times are left unmodified, which
-    /// may not be what actually happened during the run.
+    /// attempt is paused, it also resumes that attempt. Every split time that
+    /// got split after a pause is adjusted to include the time the attempt was
+    /// paused for, as if the attempt had never been paused.
     UndoAllPauses,
     /// Switches the current comparison to the previous comparison in the list.
     SwitchToPreviousComparison,
@@ -206,6 +501,16 @@ pub enum Command<'a> {
         #[serde(serialize_with = "serialize_time_span")]
         time: TimeSpan,
     },
+    /// Initializes the load removed time for the current attempt. The load
+    /// removed time automatically gets uninitialized for each new attempt.
+    InitializeLoadRemovedTime,
+    /// Pauses the load removed time when the game starts loading, such that it
+    /// doesn't automatically increment similar to real time.
+    PauseLoadRemovedTime,
+    /// Resumes the load removed time when the game stops loading, such that it
+    /// automatically increments similar to real time, starting from the load
+    /// removed time it was paused at.
+    ResumeLoadRemovedTime,
     /// Sets the value of a custom variable with the name specified. If the
     /// variable does not exist, a temporary variable gets created that will not
     /// be stored in the splits file.
@@ -217,15 +522,25 @@ pub enum Command<'a> {
         #[serde(borrow)]
         value: Cow<'a, str>,
     },
+    /// Undoes the most recent operation that can be undone. These are
+    /// resetting the attempt, skipping a split, pausing and resuming the
+    /// attempt, changing the game time and changing the comparison.
+    Undo,
+    /// Redoes the most recent operation that got undone.
+    Redo,
 
     /// Returns the timer's current time. The Game Time is [`None`] if the Game
-    /// Time has not been initialized.
+    /// Time has not been initialized. The same applies to the Load Removed
+    /// Time.
     #[serde(rename_all = "camelCase")]
     GetCurrentTime {
         /// The timing method to retrieve the time for.
         #[serde(skip_serializing_if = "Option::is_none")]
         timing_method: Option<TimingMethod>,
     },
+    /// Returns the time that is left until the scheduled start of the timer.
+    /// If no start is scheduled, `null` is returned instead.
+    GetCountdown,
     /// Returns the name of the segment with the specified index. If no index is
     /// specified, the name of the current segment is returned. If the index is
     /// out of bounds, an error is returned. If the index is negative, it is
@@ -287,6 +602,98 @@ pub enum Command<'a> {
     },
     /// Returns the current timer phase and split index.
     GetCurrentState,
+    /// Returns the state of the layout specified for the current state of the
+    /// timer. This is everything that is needed to visualize the layout. The
+    /// layout is specified by its settings in the same JSON format that
+    /// layouts are saved in. If no layout is specified, the default layout is
+    /// used. Images are only referenced by their ids, as the image data is not
+    /// part of the state.
+    GetLayoutState {
+        /// The settings of the layout.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        layout: Option<serde_json::Value>,
+    },
+    /// Returns the whole run, including all of its segments, comparisons,
+    /// metadata and history.
+    GetRun,
+    /// Returns the amount of attempts that have been started.
+    GetAttemptCount,
+    /// Returns the sum of the best segments, which is the fastest time
+    /// possible to complete the run. The current attempt is taken into
+    /// account. The current timing method is used if the timing method is not
+    /// specified.
+    #[serde(rename_all = "camelCase")]
+    GetSumOfBest {
+        /// The timing method to retrieve the time for.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timing_method: Option<TimingMethod>,
+    },
+    /// Returns the chance of the current attempt beating the personal best as
+    /// a number between 0 and 1. If there is no attempt in progress, the
+    /// chance of any attempt beating the personal best is returned instead.
+    GetPbChance,
+    /// Returns the predicted final time of the current attempt, if the pace
+    /// matches the comparison for the remainder of the run. If there is no
+    /// attempt in progress, the final time of the comparison is returned
+    /// instead. The current comparison is used if the comparison name is not
+    /// specified.
+    GetCurrentPace {
+        /// The name of the comparison.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(borrow)]
+        comparison: Option<Cow<'a, str>>,
+    },
+    /// Returns how much time could be saved in the segment with the specified
+    /// index, if it was run as fast as the best segment. If no index is
+    /// specified, the current segment is used. The index is resolved the same
+    /// way as for [`GetSegmentName`](Self::GetSegmentName). If the `total`
+    /// field is set to `true`, the time that could be saved in all the
+    /// segments from there on until the end of the run is returned instead.
+    /// The current comparison is used if the comparison name is not
+    /// specified.
+    GetPossibleTimeSave {
+        /// The index of the segment.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        index: Option<isize>,
+        /// Specifies whether the index is relative to the current segment
+        /// index.
+        #[serde(default, skip_serializing_if = "is_false")]
+        relative: bool,
+        /// The name of the comparison.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(borrow)]
+        comparison: Option<Cow<'a, str>>,
+        /// Specifies whether to sum up the possible time save of all the
+        /// remaining segments.
+        #[serde(default, skip_serializing_if = "is_false")]
+        total: bool,
+    },
+    /// Subscribes to the events specified, so that they get sent from now on.
+    /// If no events are specified, all events are subscribed to. Initially
+    /// all events are subscribed to.
+    SubscribeToEvents {
+        /// The events to subscribe to.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        events: Option<Vec<Event>>,
+    },
+    /// Unsubscribes from the events specified, so that they don't get sent
+    /// anymore. If no events are specified, all events are unsubscribed from.
+    UnsubscribeFromEvents {
+        /// The events to unsubscribe from.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        events: Option<Vec<Event>>,
+    },
+    /// Subscribes to time updates, which get sent periodically and contain
+    /// the current time, the current split index and the delta to the current
+    /// comparison. The rate specifies how many time updates get sent per
+    /// second. It needs to be larger than 0 and can be at most 1000. This
+    /// replaces the rate of any previous subscription.
+    SubscribeToTimeUpdates {
+        /// The amount of time updates per second.
+        rate: f64,
+    },
+    /// Unsubscribes from the time updates.
+    UnsubscribeFromTimeUpdates,
     /// Pings the application to check whether it is still running.
     Ping,
 }
@@ -306,6 +713,10 @@ enum Response {
     None,
     String(String),
     State(State),
+    Number(u32),
+    Chance(f64),
+    LayoutState(Box<LayoutState>),
+    Json(serde_json::Value),
 }
 
 #[derive(serde_derive::Serialize)]
@@ -315,6 +726,11 @@ enum Error {
         message: String,
     },
     InvalidIndex,
+    InvalidLayout {
+        message: String,
+    },
+    InvalidRate,
+    SubscriptionsUnsupported,
     #[serde(untagged)]
     Timer {
         code: event::Error,
@@ -331,12 +747,34 @@ impl Command<'_> {
     async fn handle<E: event::CommandSink + event::TimerQuery>(
         self,
         command_sink: &E,
+        subscriptions: Option<&Mutex<Subscriptions>>,
     ) -> Result<Response, Error> {
         Ok(match self {
             Command::Start => {
                 command_sink.start().await.map_err(Error::timer)?;
                 Response::None
             }
+            Command::ScheduleStart { time } => {
+                command_sink
+                    .schedule_start(time)
+                    .await
+                    .map_err(Error::timer)?;
+                Response::None
+            }
+            Command::CancelScheduledStart => {
+                command_sink
+                    .cancel_scheduled_start()
+                    .await
+                    .map_err(Error::timer)?;
+                Response::None
+            }
+            Command::PollScheduledStart => {
+                command_sink
+                    .poll_scheduled_start()
+                    .await
+                    .map_err(Error::timer)?;
+                Response::None
+            }
             Command::Split => {
                 command_sink.split().await.map_err(Error::timer)?;
                 Response::None
@@ -446,6 +884,27 @@ impl Command<'_> {
                     .map_err(Error::timer)?;
                 Response::None
             }
+            Command::InitializeLoadRemovedTime => {
+                command_sink
+                    .initialize_load_removed_time()
+                    .await
+                    .map_err(Error::timer)?;
+                Response::None
+            }
+            Command::PauseLoadRemovedTime => {
+                command_sink
+                    .pause_load_removed_time()
+                    .await
+                    .map_err(Error::timer)?;
+                Response::None
+            }
+            Command::ResumeLoadRemovedTime => {
+                command_sink
+                    .resume_load_removed_time()
+                    .await
+                    .map_err(Error::timer)?;
+                Response::None
+            }
             Command::SetCustomVariable { key, value } => {
                 command_sink
                     .set_custom_variable(key, value)
@@ -453,6 +912,14 @@ impl Command<'_> {
                     .map_err(Error::timer)?;
                 Response::None
             }
+            Command::Undo => {
+                command_sink.undo().await.map_err(Error::timer)?;
+                Response::None
+            }
+            Command::Redo => {
+                command_sink.redo().await.map_err(Error::timer)?;
+                Response::None
+            }
 
             Command::GetCurrentTime { timing_method } => {
                 let guard = command_sink.get_timer();
@@ -466,6 +933,14 @@ impl Command<'_> {
                     Response::None
                 }
             }
+            Command::GetCountdown => {
+                let guard = command_sink.get_timer();
+                if let Some(countdown) = guard.snapshot().countdown() {
+                    Response::String(format_time(countdown))
+                } else {
+                    Response::None
+                }
+            }
             Command::GetSegmentName { index, relative } => {
                 let guard = command_sink.get_timer();
                 let timer = &*guard;
@@ -520,6 +995,110 @@ impl Command<'_> {
                     TimerPhase::Ended => State::Ended,
                 })
             }
+            Command::GetLayoutState { layout } => {
+                let settings = match layout {
+                    Some(layout) => Some(LayoutSettings::from_json_value(layout).map_err(|e| {
+                        Error::InvalidLayout {
+                            message: e.to_string(),
+                        }
+                    })?),
+                    None => None,
+                };
+                let mut layout =
+                    settings.map_or_else(Layout::default_layout, Layout::from_settings);
+
+                let guard = command_sink.get_timer();
+                let state = layout.state(&mut ImageCache::new(), &guard.snapshot());
+                Response::LayoutState(Box::new(state))
+            }
+            Command::GetRun => {
+                let guard = command_sink.get_timer();
+                Response::Json(serde_json::to_value(RunInfo::new(guard.run())).unwrap())
+            }
+            Command::GetAttemptCount => {
+                Response::Number(command_sink.get_timer().run().attempt_count())
+            }
+            Command::GetSumOfBest { timing_method } => {
+                let guard = command_sink.get_timer();
+                let timer = &*guard;
+                let timing_method = timing_method.unwrap_or_else(|| timer.current_timing_method());
+
+                let time = sum_of_segments::calculate_best(
+                    timer.run().segments(),
+                    false,
+                    true,
+                    timing_method,
+                );
+                if let Some(time) = time {
+                    Response::String(format_time(time))
+                } else {
+                    Response::None
+                }
+            }
+            Command::GetPbChance => {
+                let guard = command_sink.get_timer();
+                let (chance, _) = pb_chance::for_timer(&guard.snapshot());
+                Response::Chance(chance)
+            }
+            Command::GetCurrentPace { comparison } => {
+                let guard = command_sink.get_timer();
+                let timer = &*guard;
+                let comparison = comparison.as_deref().unwrap_or(timer.current_comparison());
+
+                let (time, _) = current_pace::calculate(&timer.snapshot(), comparison);
+                if let Some(time) = time {
+                    Response::String(format_time(time))
+                } else {
+                    Response::None
+                }
+            }
+            Command::GetPossibleTimeSave {
+                index,
+                relative,
+                comparison,
+                total,
+            } => {
+                let guard = command_sink.get_timer();
+                let timer = &*guard;
+                let index = resolve_index(timer, index, relative)?;
+                let comparison = comparison.as_deref().unwrap_or(timer.current_comparison());
+
+                let snapshot = timer.snapshot();
+                let time = if total {
+                    Some(possible_time_save::calculate_total(&snapshot, index, comparison).0)
+                } else {
+                    possible_time_save::calculate(&snapshot, index, comparison, false).0
+                };
+                if let Some(time) = time {
+                    Response::String(format_time(time))
+                } else {
+                    Response::None
+                }
+            }
+            Command::SubscribeToEvents { events } => {
+                let subscriptions = subscriptions.ok_or(Error::SubscriptionsUnsupported)?;
+                subscriptions.lock().unwrap().events |= Subscriptions::event_mask(events);
+                Response::None
+            }
+            Command::UnsubscribeFromEvents { events } => {
+                let subscriptions = subscriptions.ok_or(Error::SubscriptionsUnsupported)?;
+                subscriptions.lock().unwrap().events &= !Subscriptions::event_mask(events);
+                Response::None
+            }
+            Command::SubscribeToTimeUpdates { rate } => {
+                let subscriptions = subscriptions.ok_or(Error::SubscriptionsUnsupported)?;
+                if !(rate > 0.0 && rate <= MAX_TIME_UPDATE_RATE) {
+                    return Err(Error::InvalidRate);
+                }
+                subscriptions.lock().unwrap().time_update_interval =
+                    Some(Duration::from_secs_f64(rate.recip()));
+                Response::None
+            }
+            Command::UnsubscribeFromTimeUpdates => {
+                let subscriptions = subscriptions.ok_or(Error::SubscriptionsUnsupported)?;
+                subscriptions.lock().unwrap().time_update_interval = None;
+                Response::None
+            }
             Command::Ping => Response::None,
         })
     }
@@ -554,3 +1133,159 @@ fn format_time(time: TimeSpan) -> String {
         .format(time)
         .to_string()
 }
+
+/// The JSON representation of a [`Run`] that is sent to the clients.
+#[derive(serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RunInfo<'a> {
+    game_name: &'a str,
+    category_name: &'a str,
+    offset: String,
+    attempt_count: u32,
+    metadata: MetadataInfo<'a>,
+    comparisons: Vec<&'a str>,
+    segments: Vec<SegmentInfo<'a>>,
+    attempt_history: Vec<AttemptInfo>,
+}
+
+#[derive(serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataInfo<'a> {
+    run_id: &'a str,
+    platform_name: &'a str,
+    uses_emulator: bool,
+    region_name: &'a str,
+    speedrun_com_variables: Map<&'a str>,
+    custom_variables: Map<CustomVariableInfo<'a>>,
+}
+
+#[derive(Default, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CustomVariableInfo<'a> {
+    value: &'a str,
+    is_permanent: bool,
+}
+
+#[derive(serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentInfo<'a> {
+    name: &'a str,
+    runner: &'a str,
+    split_time: TimeInfo,
+    best_segment_time: TimeInfo,
+    comparisons: Map<TimeInfo>,
+    segment_history: Vec<HistoryInfo>,
+}
+
+#[derive(serde_derive::Serialize)]
+struct HistoryInfo {
+    index: i32,
+    time: TimeInfo,
+}
+
+#[derive(serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AttemptInfo {
+    index: i32,
+    time: TimeInfo,
+    pause_time: Option<String>,
+    started: Option<String>,
+    ended: Option<String>,
+}
+
+#[derive(Default, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeInfo {
+    real_time: Option<String>,
+    game_time: Option<String>,
+    load_removed_time: Option<String>,
+}
+
+impl<'a> RunInfo<'a> {
+    fn new(run: &'a Run) -> Self {
+        let metadata = run.metadata();
+        let comparisons: Vec<&str> = run.comparisons().collect();
+
+        let mut speedrun_com_variables = Map::default();
+        for (name, value) in metadata.speedrun_com_variables() {
+            speedrun_com_variables.insert(name, value.as_str());
+        }
+        let mut custom_variables = Map::default();
+        for (name, variable) in metadata.custom_variables() {
+            custom_variables.insert(
+                name,
+                CustomVariableInfo {
+                    value: &variable.value,
+                    is_permanent: variable.is_permanent,
+                },
+            );
+        }
+
+        Self {
+            game_name: run.game_name(),
+            category_name: run.category_name(),
+            offset: format_time(run.offset()),
+            attempt_count: run.attempt_count(),
+            metadata: MetadataInfo {
+                run_id: metadata.run_id(),
+                platform_name: metadata.platform_name(),
+                uses_emulator: metadata.uses_emulator(),
+                region_name: metadata.region_name(),
+                speedrun_com_variables,
+                custom_variables,
+            },
+            segments: run
+                .segments()
+                .iter()
+                .map(|segment| {
+                    let mut segment_comparisons = Map::default();
+                    for &comparison in &comparisons {
+                        segment_comparisons
+                            .insert(comparison, TimeInfo::new(segment.comparison(comparison)));
+                    }
+                    SegmentInfo {
+                        name: segment.name(),
+                        runner: segment.runner(),
+                        split_time: TimeInfo::new(segment.split_time()),
+                        best_segment_time: TimeInfo::new(segment.best_segment_time()),
+                        comparisons: segment_comparisons,
+                        segment_history: segment
+                            .segment_history()
+                            .iter()
+                            .map(|&(index, time)| HistoryInfo {
+                                index,
+                                time: TimeInfo::new(time),
+                            })
+                            .collect(),
+                    }
+                })
+                .collect(),
+            comparisons,
+            attempt_history: run
+                .attempt_history()
+                .iter()
+                .map(|attempt| AttemptInfo {
+                    index: attempt.index(),
+                    time: TimeInfo::new(attempt.time()),
+                    pause_time: attempt.pause_time().map(format_time),
+                    started: attempt.started().and_then(format_date_time),
+                    ended: attempt.ended().and_then(format_date_time),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TimeInfo {
+    fn new(time: Time) -> Self {
+        Self {
+            real_time: time.real_time.map(format_time),
+            game_time: time.game_time.map(format_time),
+            load_removed_time: time.load_removed_time.map(format_time),
+        }
+    }
+}
+
+fn format_date_time(date_time: AtomicDateTime) -> Option<String> {
+    date_time.time.format(&Rfc3339).ok()
+}
@@ -1,27 +1,11 @@
-use crate::platform::prelude::*;
+use crate::{platform::prelude::*, timing::Clock};
 use core::{
-    ops::Sub,
+    ops::{Add, Sub},
     sync::atomic::{self, AtomicPtr},
 };
 
 pub use time::{Duration, OffsetDateTime as DateTime};
 
-/// A clock is a global handler that can be registered for providing the high
-/// precision time stamps on a `no_std` target.
-pub trait Clock: 'static {
-    /// Returns the current point in time as a Duration. This is expected to be
-    /// a monotonic high precision time stamp and does not need to represent a
-    /// time based on a calendar.
-    fn now(&self) -> Duration;
-
-    /// Returns the current point in time as a DateTime. This is expected to
-    /// represent the current date and time of day. It does not need to be a
-    /// high precision time stamp and is allowed to suddenly change to due
-    /// synchronization with a time server. If there's no notion of a calendar
-    /// on the system, you may return a dummy value instead.
-    fn date_now(&self) -> DateTime;
-}
-
 static CLOCK: AtomicPtr<Box<dyn Clock>> = AtomicPtr::new(core::ptr::null_mut());
 
 /// Registers a clock as the global handler for providing the high precision
@@ -61,6 +45,14 @@ impl Sub for Instant {
     }
 }
 
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant(self.0 + rhs)
+    }
+}
+
 pub fn utc_now() -> DateTime {
     let clock = CLOCK.load(atomic::Ordering::SeqCst);
     if clock.is_null() {
@@ -100,7 +100,10 @@ cfg_if::cfg_if! {
         target_os = "android",
         target_os = "fuchsia",
     ))] {
-        use core::{mem::MaybeUninit, ops::Sub};
+        use core::{
+            mem::MaybeUninit,
+            ops::{Add, Sub},
+        };
 
         #[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Debug)]
         #[repr(transparent)]
@@ -132,13 +135,22 @@ cfg_if::cfg_if! {
                 self.0 - rhs.0
             }
         }
+
+        impl Add<Duration> for Instant {
+            type Output = Instant;
+
+            #[inline]
+            fn add(self, rhs: Duration) -> Instant {
+                Self(self.0 + rhs)
+            }
+        }
     } else if #[cfg(any(
         target_os = "macos",
         target_os = "ios",
         target_os = "tvos",
         target_os = "watchos",
     ))] {
-        use core::ops::Sub;
+        use core::ops::{Add, Sub};
 
         unsafe extern "C" {
             fn clock_gettime_nsec_np(clock_id: libc::clockid_t) -> u64;
@@ -167,8 +179,17 @@ cfg_if::cfg_if! {
                 Duration::nanoseconds(self.0 as i64 - rhs.0 as i64)
             }
         }
+
+        impl Add<Duration> for Instant {
+            type Output = Instant;
+
+            #[inline]
+            fn add(self, rhs: Duration) -> Instant {
+                Self((self.0 as i64 + rhs.whole_nanoseconds() as i64) as u64)
+            }
+        }
     } else {
-        use core::ops::Sub;
+        use core::ops::{Add, Sub};
 
         #[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Debug)]
         #[repr(transparent)]
@@ -190,6 +211,15 @@ cfg_if::cfg_if! {
                 time::ext::InstantExt::signed_duration_since(&self.0, rhs.0)
             }
         }
+
+        impl Add<Duration> for Instant {
+            type Output = Instant;
+
+            #[inline]
+            fn add(self, rhs: Duration) -> Instant {
+                Self(time::ext::InstantExt::add_signed(self.0, rhs))
+            }
+        }
     }
 }
 
@@ -1,4 +1,7 @@
-use core::{mem::MaybeUninit, ops::Sub};
+use core::{
+    mem::MaybeUninit,
+    ops::{Add, Sub},
+};
 
 pub use time::{Duration, OffsetDateTime as DateTime};
 
@@ -48,6 +51,14 @@ impl Sub for Instant {
     }
 }
 
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant(self.0 + rhs)
+    }
+}
+
 pub fn to_local(date_time: DateTime) -> DateTime {
     date_time
 }
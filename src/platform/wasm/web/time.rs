@@ -1,5 +1,8 @@
 use js_sys::{Date, Reflect};
-use std::{cell::Cell, ops::Sub};
+use std::{
+    cell::Cell,
+    ops::{Add, Sub},
+};
 use time::UtcOffset;
 use wasm_bindgen::{prelude::*, JsCast};
 use web_sys::{Performance, VisibilityState};
@@ -89,6 +92,14 @@ impl Sub for Instant {
     }
 }
 
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant(self.0 + rhs)
+    }
+}
+
 pub fn utc_now() -> DateTime {
     DateTime::from_unix_timestamp_nanos((Date::now() * 1_000_000.0) as i128)
         .expect("Can't query current date")
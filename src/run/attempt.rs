@@ -1,4 +1,4 @@
-use crate::{AtomicDateTime, Time, TimeSpan};
+use crate::{AtomicDateTime, Time, TimeSpan, platform::prelude::*};
 
 /// An `Attempt` describes information about an attempt to run a specific category
 /// by a specific runner in the past. Every time a new attempt is started and
@@ -10,6 +10,26 @@ pub struct Attempt {
     started: Option<AtomicDateTime>,
     ended: Option<AtomicDateTime>,
     pause_time: Option<TimeSpan>,
+    pauses: Vec<Pause>,
+}
+
+/// A `Pause` describes an interval of time during which an attempt was paused.
+/// Both points in time are specified as the real time that passed since the
+/// attempt started, including all the previous pauses, but excluding the timer
+/// offset at the beginning of the attempt.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Pause {
+    /// The point in time the attempt got paused at.
+    pub start: TimeSpan,
+    /// The point in time the attempt got resumed at.
+    pub end: TimeSpan,
+}
+
+impl Pause {
+    /// Returns how long the attempt was paused for.
+    pub fn duration(&self) -> TimeSpan {
+        self.end - self.start
+    }
 }
 
 impl Attempt {
@@ -35,9 +55,17 @@ impl Attempt {
             started,
             ended,
             pause_time,
+            pauses: Vec::new(),
         }
     }
 
+    /// Sets the individual intervals during which the attempt was paused. They
+    /// need to be ordered by the point in time they started at.
+    pub fn with_pauses(mut self, pauses: Vec<Pause>) -> Self {
+        self.pauses = pauses;
+        self
+    }
+
     /// Returns the total duration of the attempt, from the point in time it
     /// started to the point in time it ended. This is different from the real
     /// time of the run, as it includes all the pause times and the timer offset
@@ -65,6 +93,10 @@ impl Attempt {
         self.time
     }
 
+    pub(super) const fn time_mut(&mut self) -> &mut Time {
+        &mut self.time
+    }
+
     /// Accesses the amount of time the attempt has been paused for. If it is
     /// not known, this returns `None`. This means that it may not necessarily
     /// be possible to differentiate whether a Run has not been paused or it
@@ -74,6 +106,15 @@ impl Attempt {
         self.pause_time
     }
 
+    /// Accesses the individual intervals during which the attempt was paused,
+    /// ordered by the point in time they started at. Older attempts may only
+    /// know their total [pause time](Self::pause_time), so this may be empty
+    /// even if the attempt was paused.
+    #[inline]
+    pub fn pauses(&self) -> &[Pause] {
+        &self.pauses
+    }
+
     /// Accesses the point in time the attempt was started at. This returns
     /// `None` if this information is not known.
     #[inline]
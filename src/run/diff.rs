@@ -0,0 +1,586 @@
+//! The diff module provides a structured comparison between two versions of a
+//! [`Run`]. This is useful for reviewing changes to shared splits files, such
+//! as route changes and updated comparisons. The [`RunDiff`] can be inspected
+//! programmatically or printed as a human-readable summary through its
+//! [`Display`](fmt::Display) implementation.
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::{Run, Segment};
+//! use livesplit_core::run::diff;
+//!
+//! let mut old = Run::new();
+//! old.push_segment(Segment::new("Cap Kingdom"));
+//! old.push_segment(Segment::new("Cascade Kingdom"));
+//!
+//! let mut new = old.clone();
+//! new.set_category_name("Any%");
+//! new.segment_mut(1).set_name("Cascade");
+//! new.push_segment(Segment::new("Sand Kingdom"));
+//!
+//! let diff = diff::diff(&old, &new);
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "Category Name: \"\" -> \"Any%\"\n\
+//!      ~ Segment 2 \"Cascade Kingdom\" -> \"Cascade\"\n\
+//!      + Segment 3 \"Sand Kingdom\"\n",
+//! );
+//! ```
+
+use super::{Attempt, LinkedLayout, RunMetadata, Segment};
+use crate::{
+    Run, Time, TimeSpan, TimingMethod,
+    platform::prelude::*,
+    timing::formatter::{Accuracy, Complete, DASH, Regular, TimeFormatter},
+};
+use core::fmt;
+use hashbrown::HashSet;
+
+/// A value that is different in the old and the new [`Run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change<T> {
+    /// The value in the old [`Run`].
+    pub old: T,
+    /// The value in the new [`Run`].
+    pub new: T,
+}
+
+impl<T> Change<T> {
+    fn map<U>(self, mut f: impl FnMut(T) -> U) -> Change<U> {
+        Change {
+            old: f(self.old),
+            new: f(self.new),
+        }
+    }
+}
+
+/// A change to the general information about a [`Run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MetadataChange {
+    /// The name of the game changed.
+    GameName(Change<String>),
+    /// The name of the category changed.
+    CategoryName(Change<String>),
+    /// The offset at which the timer starts changed.
+    Offset(Change<TimeSpan>),
+    /// The amount of attempts changed.
+    AttemptCount(Change<u32>),
+    /// The speedrun.com run ID of the Personal Best changed.
+    RunId(Change<String>),
+    /// The name of the platform changed.
+    PlatformName(Change<String>),
+    /// The name of the region changed.
+    RegionName(Change<String>),
+    /// Whether an emulator is used changed.
+    UsesEmulator(Change<bool>),
+    /// The layout that is linked with the [`Run`] changed.
+    LinkedLayout(Change<Option<LinkedLayout>>),
+}
+
+/// The kind of variable a [`VariableChange`] refers to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VariableKind {
+    /// A speedrun.com variable of the category.
+    SpeedrunCom,
+    /// A permanent custom variable. Temporary custom variables are not stored
+    /// in splits files, so they are not compared.
+    Custom,
+}
+
+/// A variable that got added, removed or whose value changed. A variable that
+/// doesn't exist in one of the [`Run`]s has no value there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariableChange {
+    /// The kind of the variable.
+    pub kind: VariableKind,
+    /// The name of the variable.
+    pub name: String,
+    /// The value of the variable in the old [`Run`].
+    pub old: Option<String>,
+    /// The value of the variable in the new [`Run`].
+    pub new: Option<String>,
+}
+
+/// A custom comparison that got added or removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ComparisonListChange {
+    /// The custom comparison only exists in the new [`Run`].
+    Added(String),
+    /// The custom comparison only exists in the old [`Run`].
+    Removed(String),
+}
+
+/// A comparison time of a segment that changed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonChange {
+    /// The name of the comparison.
+    pub comparison: String,
+    /// The split time of the comparison in the old [`Run`].
+    pub old: Time,
+    /// The split time of the comparison in the new [`Run`].
+    pub new: Time,
+}
+
+/// A change to the segments of a [`Run`]. Segments are matched up by their
+/// names. Segments that couldn't be matched up, but are at the same place in
+/// the route, are considered renamed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SegmentChange {
+    /// The segment only exists in the new [`Run`].
+    Added {
+        /// The index of the segment in the new [`Run`].
+        index: usize,
+        /// The name of the segment.
+        name: String,
+    },
+    /// The segment only exists in the old [`Run`].
+    Removed {
+        /// The index of the segment in the old [`Run`].
+        index: usize,
+        /// The name of the segment.
+        name: String,
+    },
+    /// The segment exists in both [`Run`]s, but either got renamed or its
+    /// times changed.
+    Modified {
+        /// The index of the segment in the old [`Run`].
+        old_index: usize,
+        /// The index of the segment in the new [`Run`].
+        new_index: usize,
+        /// The name of the segment, if it got renamed.
+        name: Option<Change<String>>,
+        /// The comparisons with a different split time. Only the custom
+        /// comparisons that exist in both [`Run`]s are compared.
+        comparisons: Vec<ComparisonChange>,
+        /// The best segment time, if it changed.
+        best_segment_time: Option<Change<Time>>,
+    },
+}
+
+/// The differences between two versions of a [`Run`].
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct RunDiff {
+    /// The changes to the general information about the [`Run`].
+    pub metadata: Vec<MetadataChange>,
+    /// The speedrun.com and custom variables that changed.
+    pub variables: Vec<VariableChange>,
+    /// The custom comparisons that got added or removed.
+    pub comparisons: Vec<ComparisonListChange>,
+    /// The segments that got added, removed, renamed or whose times changed,
+    /// in the order of the route.
+    pub segments: Vec<SegmentChange>,
+    /// The attempts of the new [`Run`] whose index doesn't exist in the
+    /// Attempt History of the old [`Run`].
+    pub new_attempts: Vec<Attempt>,
+}
+
+impl RunDiff {
+    /// Returns whether the [`Run`]s don't differ in any of the compared
+    /// aspects.
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty()
+            && self.variables.is_empty()
+            && self.comparisons.is_empty()
+            && self.segments.is_empty()
+            && self.new_attempts.is_empty()
+    }
+}
+
+/// Calculates the differences between an old and a new version of a [`Run`].
+/// Icons, the auto splitter settings and the Segment Histories are not
+/// compared. The only change to the history that is reported are the new
+/// attempts.
+pub fn diff(old: &Run, new: &Run) -> RunDiff {
+    let mut diff = RunDiff::default();
+    diff_metadata(old, new, &mut diff.metadata);
+    diff_variables(old, new, &mut diff.variables);
+
+    for comparison in old.custom_comparisons() {
+        if !new.custom_comparisons().contains(comparison) {
+            diff.comparisons
+                .push(ComparisonListChange::Removed(comparison.clone()));
+        }
+    }
+    for comparison in new.custom_comparisons() {
+        if !old.custom_comparisons().contains(comparison) {
+            diff.comparisons
+                .push(ComparisonListChange::Added(comparison.clone()));
+        }
+    }
+
+    diff_segments(old, new, &mut diff.segments);
+
+    let old_attempts = old
+        .attempt_history()
+        .iter()
+        .map(Attempt::index)
+        .collect::<HashSet<_>>();
+    diff.new_attempts = new
+        .attempt_history()
+        .iter()
+        .filter(|attempt| !old_attempts.contains(&attempt.index()))
+        .cloned()
+        .collect();
+
+    diff
+}
+
+fn change<T: PartialEq>(old: T, new: T) -> Option<Change<T>> {
+    (old != new).then_some(Change { old, new })
+}
+
+fn diff_metadata(old: &Run, new: &Run, changes: &mut Vec<MetadataChange>) {
+    let (old_metadata, new_metadata) = (old.metadata(), new.metadata());
+
+    changes.extend(
+        [
+            change(old.game_name(), new.game_name())
+                .map(|c| MetadataChange::GameName(c.map(Into::into))),
+            change(old.category_name(), new.category_name())
+                .map(|c| MetadataChange::CategoryName(c.map(Into::into))),
+            change(old.offset(), new.offset()).map(MetadataChange::Offset),
+            change(old.attempt_count(), new.attempt_count()).map(MetadataChange::AttemptCount),
+            change(old_metadata.run_id(), new_metadata.run_id())
+                .map(|c| MetadataChange::RunId(c.map(Into::into))),
+            change(old_metadata.platform_name(), new_metadata.platform_name())
+                .map(|c| MetadataChange::PlatformName(c.map(Into::into))),
+            change(old_metadata.region_name(), new_metadata.region_name())
+                .map(|c| MetadataChange::RegionName(c.map(Into::into))),
+            change(old_metadata.uses_emulator(), new_metadata.uses_emulator())
+                .map(MetadataChange::UsesEmulator),
+            change(old.linked_layout(), new.linked_layout())
+                .map(|c| MetadataChange::LinkedLayout(c.map(|l| l.cloned()))),
+        ]
+        .into_iter()
+        .flatten(),
+    );
+}
+
+fn permanent<'a>(metadata: &'a RunMetadata, name: &str) -> Option<&'a str> {
+    metadata
+        .custom_variable(name)
+        .filter(|variable| variable.is_permanent)
+        .map(|variable| variable.value.as_str())
+}
+
+fn diff_variables(old: &Run, new: &Run, changes: &mut Vec<VariableChange>) {
+    let (old, new) = (old.metadata(), new.metadata());
+
+    let mut push = |kind, name: &str, old: Option<&str>, new: Option<&str>| {
+        if old != new {
+            changes.push(VariableChange {
+                kind,
+                name: name.into(),
+                old: old.map(Into::into),
+                new: new.map(Into::into),
+            });
+        }
+    };
+
+    for (name, value) in old.speedrun_com_variables() {
+        let new_value = new.speedrun_com_variables.get(name);
+        push(
+            VariableKind::SpeedrunCom,
+            name,
+            Some(value),
+            new_value.map(String::as_str),
+        );
+    }
+    for (name, value) in new.speedrun_com_variables() {
+        if old.speedrun_com_variables.get(name).is_none() {
+            push(VariableKind::SpeedrunCom, name, None, Some(value));
+        }
+    }
+
+    for (name, variable) in old.custom_variables() {
+        if variable.is_permanent {
+            push(
+                VariableKind::Custom,
+                name,
+                Some(&variable.value),
+                permanent(new, name),
+            );
+        }
+    }
+    for (name, variable) in new.custom_variables() {
+        if variable.is_permanent && permanent(old, name).is_none() {
+            push(VariableKind::Custom, name, None, Some(&variable.value));
+        }
+    }
+}
+
+/// Matches up the segments of both runs by finding the longest common
+/// subsequence of their names. The matched pairs are returned in order.
+fn matching_segments(old: &[Segment], new: &[Segment]) -> Vec<(usize, usize)> {
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for (i, old_segment) in old.iter().enumerate().rev() {
+        for (j, new_segment) in new.iter().enumerate().rev() {
+            lengths[i * width + j] = if old_segment.name() == new_segment.name() {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i].name() == new[j].name() {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+fn diff_segments(old: &Run, new: &Run, changes: &mut Vec<SegmentChange>) {
+    let comparisons = old
+        .custom_comparisons()
+        .iter()
+        .filter(|c| new.custom_comparisons().contains(c))
+        .collect::<Vec<_>>();
+
+    let (old_segments, new_segments) = (old.segments(), new.segments());
+    let mut pairs = matching_segments(old_segments, new_segments);
+    pairs.push((old_segments.len(), new_segments.len()));
+
+    let (mut i, mut j) = (0, 0);
+    for (matched_i, matched_j) in pairs {
+        // The segments in between two matched segments that are at the same
+        // place in the route are considered renamed.
+        while i < matched_i || j < matched_j {
+            changes.extend(if i < matched_i && j < matched_j {
+                modified(old, new, i, j, &comparisons)
+            } else if i < matched_i {
+                Some(SegmentChange::Removed {
+                    index: i,
+                    name: old_segments[i].name().into(),
+                })
+            } else {
+                Some(SegmentChange::Added {
+                    index: j,
+                    name: new_segments[j].name().into(),
+                })
+            });
+            if i < matched_i {
+                i += 1;
+            }
+            if j < matched_j {
+                j += 1;
+            }
+        }
+
+        if matched_i < old_segments.len() {
+            changes.extend(modified(old, new, i, j, &comparisons));
+            i += 1;
+            j += 1;
+        }
+    }
+}
+
+fn modified(
+    old: &Run,
+    new: &Run,
+    old_index: usize,
+    new_index: usize,
+    comparisons: &[&String],
+) -> Option<SegmentChange> {
+    let (old_segment, new_segment) = (old.segment(old_index), new.segment(new_index));
+
+    let name = change(old_segment.name(), new_segment.name()).map(|c| c.map(Into::into));
+    let comparisons = comparisons
+        .iter()
+        .filter_map(|&comparison| {
+            let (old, new) = (
+                old_segment.comparison(comparison),
+                new_segment.comparison(comparison),
+            );
+            (old != new).then(|| ComparisonChange {
+                comparison: comparison.clone(),
+                old,
+                new,
+            })
+        })
+        .collect::<Vec<_>>();
+    let best_segment_time = change(
+        old_segment.best_segment_time(),
+        new_segment.best_segment_time(),
+    );
+
+    if name.is_none() && comparisons.is_empty() && best_segment_time.is_none() {
+        return None;
+    }
+
+    Some(SegmentChange::Modified {
+        old_index,
+        new_index,
+        name,
+        comparisons,
+        best_segment_time,
+    })
+}
+
+struct Quoted<'a>(Option<&'a str>);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(value) => write!(f, "{value:?}"),
+            None => f.write_str(DASH),
+        }
+    }
+}
+
+fn layout_name(layout: &Option<LinkedLayout>) -> Option<&str> {
+    match layout {
+        Some(LinkedLayout::Default) => Some("Default"),
+        Some(LinkedLayout::Path(path)) => Some(path),
+        None => None,
+    }
+}
+
+fn write_change<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    old: T,
+    new: T,
+) -> fmt::Result {
+    writeln!(f, "{label}: {old} -> {new}")
+}
+
+fn write_time_change(f: &mut fmt::Formatter<'_>, label: &str, old: Time, new: Time) -> fmt::Result {
+    let formatter = Regular::with_accuracy(Accuracy::Hundredths);
+    for method in TimingMethod::all() {
+        if old[method] != new[method] {
+            let name = match method {
+                TimingMethod::RealTime => "Real Time",
+                TimingMethod::GameTime => "Game Time",
+                TimingMethod::LoadRemovedTime => "Load Removed Time",
+            };
+            writeln!(
+                f,
+                "    {label} ({name}): {} -> {}",
+                formatter.format(old[method]),
+                formatter.format(new[method]),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for MetadataChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (label, c) = match self {
+            Self::GameName(c) => ("Game Name", c),
+            Self::CategoryName(c) => ("Category Name", c),
+            Self::RunId(c) => ("Run ID", c),
+            Self::PlatformName(c) => ("Platform", c),
+            Self::RegionName(c) => ("Region", c),
+            Self::Offset(c) => {
+                return write_change(f, "Offset", Complete.format(c.old), Complete.format(c.new));
+            }
+            Self::AttemptCount(c) => return write_change(f, "Attempts", c.old, c.new),
+            Self::UsesEmulator(c) => return write_change(f, "Uses Emulator", c.old, c.new),
+            Self::LinkedLayout(c) => {
+                return write_change(
+                    f,
+                    "Linked Layout",
+                    Quoted(layout_name(&c.old)),
+                    Quoted(layout_name(&c.new)),
+                );
+            }
+        };
+        write_change(f, label, Quoted(Some(&c.old)), Quoted(Some(&c.new)))
+    }
+}
+
+impl fmt::Display for VariableChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            VariableKind::SpeedrunCom => "Speedrun.com Variable",
+            VariableKind::Custom => "Custom Variable",
+        };
+        writeln!(
+            f,
+            "{kind} {:?}: {} -> {}",
+            self.name,
+            Quoted(self.old.as_deref()),
+            Quoted(self.new.as_deref()),
+        )
+    }
+}
+
+impl fmt::Display for SegmentChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { index, name } => writeln!(f, "+ Segment {} {name:?}", index + 1),
+            Self::Removed { index, name } => writeln!(f, "- Segment {} {name:?}", index + 1),
+            Self::Modified {
+                old_index,
+                new_index,
+                name,
+                comparisons,
+                best_segment_time,
+            } => {
+                let position = if old_index == new_index {
+                    format!("{}", new_index + 1)
+                } else {
+                    format!("{} -> {}", old_index + 1, new_index + 1)
+                };
+                match name {
+                    Some(name) => {
+                        writeln!(f, "~ Segment {position} {:?} -> {:?}", name.old, name.new)?
+                    }
+                    None => writeln!(f, "~ Segment {position}")?,
+                }
+                for change in comparisons {
+                    write_time_change(f, &change.comparison, change.old, change.new)?;
+                }
+                if let Some(change) = best_segment_time {
+                    write_time_change(f, "Best Segment", change.old, change.new)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for RunDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.metadata {
+            fmt::Display::fmt(change, f)?;
+        }
+        for change in &self.variables {
+            fmt::Display::fmt(change, f)?;
+        }
+        for change in &self.comparisons {
+            match change {
+                ComparisonListChange::Added(name) => writeln!(f, "+ Comparison {name:?}")?,
+                ComparisonListChange::Removed(name) => writeln!(f, "- Comparison {name:?}")?,
+            }
+        }
+        for change in &self.segments {
+            fmt::Display::fmt(change, f)?;
+        }
+        let formatter = Regular::with_accuracy(Accuracy::Hundredths);
+        for attempt in &self.new_attempts {
+            let time = attempt.time();
+            writeln!(
+                f,
+                "+ Attempt {} (Real Time: {}, Game Time: {})",
+                attempt.index(),
+                formatter.format(time.real_time),
+                formatter.format(time.game_time),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -74,6 +74,7 @@ impl fmt::Display for PotentialCleanUp<'_> {
         let method = match self.method {
             TimingMethod::RealTime => "Real Time",
             TimingMethod::GameTime => "Game Time",
+            TimingMethod::LoadRemovedTime => "Load Removed Time",
         };
 
         write!(
@@ -178,10 +179,16 @@ impl<'r> SumOfBestCleaner<'r> {
                             current_time: current_prediction.map(|p| p.time),
                             skip_count: 0,
                         })
-                    } else if state.method == TimingMethod::RealTime {
-                        State::WithTimingMethod(TimingMethod::GameTime)
                     } else {
-                        State::Done
+                        match state.method {
+                            TimingMethod::RealTime => {
+                                State::WithTimingMethod(TimingMethod::GameTime)
+                            }
+                            TimingMethod::GameTime => {
+                                State::WithTimingMethod(TimingMethod::LoadRemovedTime)
+                            }
+                            TimingMethod::LoadRemovedTime => State::Done,
+                        }
                     };
                 }
                 State::IteratingHistory(state) => {
@@ -12,7 +12,10 @@ use crate::{
     timing::ParseError as ParseTimeSpanError,
     util::{PopulateString, caseless},
 };
-use core::{mem::swap, num::ParseIntError};
+use core::{
+    mem::{self, swap},
+    num::ParseIntError,
+};
 use snafu::{OptionExt, ResultExt};
 
 pub mod cleaning;
@@ -66,6 +69,24 @@ pub enum RenameError {
     },
 }
 
+/// Describes an Error that occurred while retiming the most recent attempt.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum RetimeError {
+    /// There is no attempt in the Attempt History.
+    NoAttempt,
+    /// The attempt never reached the segment.
+    SegmentNotReached,
+    /// The split time is earlier than a split time before it or later than a
+    /// split time after it.
+    NotInOrder,
+    /// Couldn't parse the split time.
+    InvalidTime {
+        /// The underlying error.
+        source: ParseError,
+    },
+}
+
 /// The Run Editor allows modifying Runs while ensuring that all the different
 /// invariants of the Run objects are upheld no matter what kind of operations
 /// are being applied to the Run. It provides the current state of the editor as
@@ -526,8 +547,9 @@ impl Editor {
     }
 
     fn fix_after_deletion(&mut self, index: usize) {
-        self.fix_with_timing_method(index, TimingMethod::RealTime);
-        self.fix_with_timing_method(index, TimingMethod::GameTime);
+        for method in TimingMethod::all() {
+            self.fix_with_timing_method(index, method);
+        }
     }
 
     fn fix_with_timing_method(&mut self, index: usize, method: TimingMethod) {
@@ -656,8 +678,9 @@ impl Editor {
             let first_history = first.segment_history().get(run_index);
             let second_history = second.segment_history().get(run_index);
             if let (Some(first_history), Some(second_history)) = (first_history, second_history) {
-                if first_history.real_time.is_some() != second_history.real_time.is_some()
-                    || first_history.game_time.is_some() != second_history.game_time.is_some()
+                if TimingMethod::all()
+                    .into_iter()
+                    .any(|m| first_history[m].is_some() != second_history[m].is_some())
                 {
                     first.segment_history_mut().remove(run_index);
                     second.segment_history_mut().remove(run_index);
@@ -939,6 +962,119 @@ impl Editor {
         Ok(())
     }
 
+    /// Corrects the split time of a segment in the most recent attempt for the
+    /// selected timing method. This is meant for retiming an attempt after the
+    /// fact, for example to account for loads that got retimed from a
+    /// recording of the attempt. The segment history, the best segments and the
+    /// Personal Best are updated accordingly. If the attempt is faster than the
+    /// Personal Best after the correction, it becomes the new Personal Best.
+    pub fn retime_last_attempt(
+        &mut self,
+        segment_index: usize,
+        split_time: Option<TimeSpan>,
+    ) -> Result<(), RetimeError> {
+        let method = self.selected_method;
+        let index = self.run.attempt_history.last().context(NoAttempt)?.index();
+
+        if self
+            .run
+            .segments()
+            .get(segment_index)
+            .and_then(|s| s.segment_history().get(index))
+            .is_none()
+        {
+            return Err(RetimeError::SegmentNotReached);
+        }
+
+        let mut split_times = attempt_split_times(&self.run, index, method);
+        if let Some(split_time) = split_time {
+            let previous = split_times[..segment_index]
+                .iter()
+                .rev()
+                .find_map(|&t| t)
+                .unwrap_or_default();
+            let next = split_times[segment_index + 1..].iter().find_map(|&t| t);
+            if split_time < previous || next.is_some_and(|next| split_time > next) {
+                return Err(RetimeError::NotInOrder);
+            }
+        }
+
+        let last_segment = self.run.segments().last().unwrap();
+        let pb_time = last_segment.personal_best_split_time()[method];
+        let is_finished = last_segment.segment_history().get(index).is_some();
+        let was_pb = pb_time.is_some()
+            && self
+                .run
+                .segments()
+                .iter()
+                .zip(&split_times)
+                .all(|(s, &t)| s.personal_best_split_time()[method] == t);
+
+        split_times[segment_index] = split_time;
+
+        let mut previous = TimeSpan::zero();
+        for (segment, &split_time) in self.run.segments_mut().iter_mut().zip(&split_times) {
+            let Some(time) = segment.segment_history_mut().get_mut(index) else {
+                continue;
+            };
+            let segment_time = split_time.map(|t| t - previous);
+            let old_segment_time = mem::replace(&mut time[method], segment_time);
+            if let Some(split_time) = split_time {
+                previous = split_time;
+            }
+
+            let best_segment_time = segment.best_segment_time()[method];
+            if best_segment_time.is_some() && best_segment_time == old_segment_time {
+                // The attempt may have been the one that set the best segment,
+                // so it needs to be determined from the history again.
+                segment.best_segment_time_mut()[method] = segment
+                    .segment_history()
+                    .iter()
+                    .filter_map(|&(_, t)| t[method])
+                    .min();
+            } else if segment_time.is_some_and(|t| best_segment_time.is_none_or(|b| t < b)) {
+                segment.best_segment_time_mut()[method] = segment_time;
+            }
+        }
+
+        if is_finished && segment_index + 1 == self.run.len() {
+            self.run.attempt_history.last_mut().unwrap().time_mut()[method] = split_time;
+        }
+
+        let final_time = split_times.last().copied().flatten();
+        if was_pb {
+            self.run
+                .segment_mut(segment_index)
+                .personal_best_split_time_mut()[method] = split_time;
+        } else if is_finished && final_time.is_some_and(|t| pb_time.is_none_or(|pb| t < pb)) {
+            self.run.import_pb_into_segment_history();
+            for method in TimingMethod::all() {
+                let split_times = attempt_split_times(&self.run, index, method);
+                for (segment, split_time) in self.run.segments_mut().iter_mut().zip(split_times) {
+                    segment.personal_best_split_time_mut()[method] = split_time;
+                }
+            }
+        }
+
+        self.times_modified();
+        self.fix();
+
+        Ok(())
+    }
+
+    /// Parses a split time from the string provided and uses it to correct the
+    /// split time of a segment in the most recent attempt for the selected
+    /// timing method. An empty string removes the split time. See
+    /// [`retime_last_attempt`](Self::retime_last_attempt) for more information.
+    pub fn parse_and_retime_last_attempt(
+        &mut self,
+        segment_index: usize,
+        split_time: &str,
+    ) -> Result<(), RetimeError> {
+        let split_time = parse_positive(split_time).context(InvalidTime)?;
+        self.retime_last_attempt(segment_index, split_time)
+    }
+
     /// Clears out the Attempt History and the Segment Histories of all the
     /// segments.
     pub fn clear_history(&mut self) {
@@ -966,6 +1102,21 @@ impl Editor {
     }
 }
 
+/// Determines the split times of an attempt from the segment history. The
+/// split time of a segment is `None` if it got skipped or the attempt never
+/// reached it.
+fn attempt_split_times(run: &Run, index: i32, method: TimingMethod) -> Vec<Option<TimeSpan>> {
+    let mut previous = TimeSpan::zero();
+    run.segments()
+        .iter()
+        .map(|segment| {
+            let segment_time = segment.segment_history().get(index)?[method]?;
+            previous += segment_time;
+            Some(previous)
+        })
+        .collect()
+}
+
 fn parse_positive(time: &str) -> Result<Option<TimeSpan>, ParseError> {
     let time = TimeSpan::parse_opt(time).context(ParseTime)?;
     if time.is_some_and(|t| t < TimeSpan::zero()) {
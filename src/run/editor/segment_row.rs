@@ -23,6 +23,13 @@ impl<T: Borrow<Editor>> SegmentRow<T> {
         editor.run.segment(self.index).name()
     }
 
+    /// Accesses the name of the runner the segment belongs to. It is empty if
+    /// the segment doesn't belong to any runner in particular.
+    pub fn runner(&self) -> &str {
+        let editor: &Editor = self.editor.borrow();
+        editor.run.segment(self.index).runner()
+    }
+
     /// Accesses the split time of the segment for the active timing method.
     pub fn split_time(&self) -> Option<TimeSpan> {
         let editor: &Editor = self.editor.borrow();
@@ -87,6 +94,16 @@ impl<'a> SegmentRow<&'a mut Editor> {
         self.editor.raise_run_edited();
     }
 
+    /// Sets the name of the runner the segment belongs to. An empty name means
+    /// that the segment doesn't belong to any runner in particular.
+    pub fn set_runner<S>(&mut self, runner: S)
+    where
+        S: PopulateString,
+    {
+        self.editor.run.segment_mut(self.index).set_runner(runner);
+        self.editor.raise_run_edited();
+    }
+
     /// Sets the split time of the segment for the active timing method.
     pub fn set_split_time(&mut self, time: Option<TimeSpan>) {
         let method = self.editor.selected_method;
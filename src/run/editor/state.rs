@@ -67,6 +67,9 @@ pub struct Segment {
     pub icon: ImageId,
     /// The name of the segment.
     pub name: String,
+    /// The name of the runner the segment belongs to. It is empty if the
+    /// segment doesn't belong to any runner in particular.
+    pub runner: String,
     /// The segment's split time for the active timing method.
     pub split_time: String,
     /// The segment time for the active timing method.
@@ -143,10 +146,11 @@ impl Editor {
         let mut segments = Vec::with_capacity(self.run.len());
 
         for segment_index in 0..self.run.len() {
-            let (name, split_time, segment_time, best_segment_time, comparison_times);
+            let (name, runner, split_time, segment_time, best_segment_time, comparison_times);
             {
                 let row = SegmentRow::new(segment_index, self);
                 name = row.name().to_string();
+                runner = row.runner().to_string();
                 split_time = formatter.format(row.split_time()).to_string();
                 segment_time = formatter.format(row.segment_time()).to_string();
                 best_segment_time = formatter.format(row.best_segment_time()).to_string();
@@ -170,6 +174,7 @@ impl Editor {
             segments.push(Segment {
                 icon,
                 name,
+                runner,
                 split_time,
                 segment_time,
                 best_segment_time,
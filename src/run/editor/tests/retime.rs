@@ -0,0 +1,155 @@
+use super::super::{Editor, RetimeError};
+use crate::{
+    Run, TimingMethod,
+    util::tests_helper::{create_timer, run_with_splits, run_with_splits_opt, span},
+};
+
+fn editor(attempts: &[&[f64]]) -> Editor {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    for splits in attempts {
+        run_with_splits(&mut timer, splits);
+    }
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    editor
+}
+
+fn segment_times(run: &Run, index: i32) -> Vec<Option<f64>> {
+    run.segments()
+        .iter()
+        .map(|s| {
+            s.segment_history()
+                .get(index)
+                .and_then(|t| t.game_time)
+                .map(|t| t.total_seconds())
+        })
+        .collect()
+}
+
+#[test]
+fn requires_an_attempt() {
+    let mut editor = Editor::new(create_timer(&["A"]).into_run(true)).unwrap();
+    assert!(matches!(
+        editor.retime_last_attempt(0, Some(span(1.0))),
+        Err(RetimeError::NoAttempt),
+    ));
+}
+
+#[test]
+fn only_reached_segments_can_be_retimed() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits_opt(&mut timer, &[Some(1.0)]);
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+
+    assert!(matches!(
+        editor.retime_last_attempt(1, Some(span(2.0))),
+        Err(RetimeError::SegmentNotReached),
+    ));
+    assert!(matches!(
+        editor.retime_last_attempt(3, Some(span(2.0))),
+        Err(RetimeError::SegmentNotReached),
+    ));
+    editor.retime_last_attempt(0, Some(span(0.5))).unwrap();
+}
+
+#[test]
+fn split_times_need_to_stay_in_order() {
+    let mut editor = editor(&[&[1.0, 2.0, 3.0]]);
+    editor.run.mark_as_unmodified();
+
+    assert!(matches!(
+        editor.retime_last_attempt(1, Some(span(0.5))),
+        Err(RetimeError::NotInOrder),
+    ));
+    assert!(matches!(
+        editor.parse_and_retime_last_attempt(1, "3.5"),
+        Err(RetimeError::NotInOrder),
+    ));
+    assert!(matches!(
+        editor.parse_and_retime_last_attempt(1, "-1"),
+        Err(RetimeError::InvalidTime { .. }),
+    ));
+    assert!(!editor.run().has_been_modified());
+}
+
+#[test]
+fn updates_the_segment_history_and_best_segments() {
+    let mut editor = editor(&[&[1.0, 2.0, 3.0], &[1.0, 2.5, 4.0]]);
+
+    editor.parse_and_retime_last_attempt(1, "1.5").unwrap();
+    let run = editor.close();
+
+    assert_eq!(segment_times(&run, 2), [Some(1.0), Some(0.5), Some(2.5)]);
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(0.5))
+    );
+    assert_eq!(
+        run.segment(2).best_segment_time().game_time,
+        Some(span(1.0))
+    );
+
+    // The attempt is still slower than the Personal Best.
+    assert_eq!(
+        run.segment(1).personal_best_split_time().game_time,
+        Some(span(2.0)),
+    );
+    assert!(run.has_been_modified());
+}
+
+#[test]
+fn best_segments_set_by_the_attempt_can_get_slower() {
+    let mut editor = editor(&[&[1.0, 3.0, 4.0], &[1.0, 2.0, 4.5]]);
+    assert_eq!(
+        editor.run().segment(1).best_segment_time().game_time,
+        Some(span(1.0)),
+    );
+
+    editor.retime_last_attempt(1, Some(span(2.5))).unwrap();
+    let run = editor.close();
+
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(1.5))
+    );
+    assert_eq!(
+        run.segment(2).best_segment_time().game_time,
+        Some(span(1.0))
+    );
+}
+
+#[test]
+fn retiming_the_personal_best_updates_it() {
+    let mut editor = editor(&[&[1.0, 2.0, 3.0]]);
+
+    editor.retime_last_attempt(1, Some(span(1.5))).unwrap();
+    let run = editor.close();
+
+    assert_eq!(
+        run.segment(1).personal_best_split_time().game_time,
+        Some(span(1.5)),
+    );
+    assert_eq!(segment_times(&run, 1), [Some(1.0), Some(0.5), Some(1.5)]);
+}
+
+#[test]
+fn faster_attempts_become_the_personal_best() {
+    let mut editor = editor(&[&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.5]]);
+
+    editor.retime_last_attempt(2, Some(span(2.5))).unwrap();
+    let run = editor.close();
+
+    assert_eq!(
+        run.segment(2).personal_best_split_time().game_time,
+        Some(span(2.5)),
+    );
+    assert_eq!(
+        run.attempt_history().last().unwrap().time().game_time,
+        Some(span(2.5)),
+    );
+    assert_eq!(
+        run.segment(2).best_segment_time().game_time,
+        Some(span(0.5))
+    );
+}
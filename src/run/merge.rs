@@ -0,0 +1,240 @@
+use super::{Attempt, Run, SegmentHistory};
+use crate::{DateTime, Time, TimeSpan, TimingMethod, platform::prelude::*};
+use hashbrown::HashMap;
+
+/// Error type for two runs that can't be merged.
+#[derive(PartialEq, Eq, Debug, snafu::Snafu)]
+pub enum MergeError {
+    /// The runs don't have the same amount of segments.
+    #[snafu(display("The runs have a different amount of segments ({len} and {other_len})."))]
+    SegmentCount {
+        /// The amount of segments of the run that gets merged into.
+        len: usize,
+        /// The amount of segments of the run that gets merged in.
+        other_len: usize,
+    },
+    /// A segment has a different name in the other run.
+    #[snafu(display(
+        "Segment {position} is called `{name}`, but it is called `{other_name}` in the other run."
+    ))]
+    SegmentName {
+        /// The position of the segment, starting at 1.
+        position: usize,
+        /// The name of the segment in the run that gets merged into.
+        name: String,
+        /// The name of the segment in the run that gets merged in.
+        other_name: String,
+    },
+}
+
+/// Maps the indices of one of the runs' Segment Histories to the indices of
+/// the merged run.
+struct IndexMapping {
+    attempts: HashMap<i32, i32>,
+    others: HashMap<i32, i32>,
+    keep_non_positive: bool,
+}
+
+impl IndexMapping {
+    fn new(keep_non_positive: bool) -> Self {
+        Self {
+            attempts: HashMap::new(),
+            others: HashMap::new(),
+            keep_non_positive,
+        }
+    }
+
+    fn map(&mut self, index: i32, next_free_index: &mut i32) -> i32 {
+        if let Some(&index) = self.attempts.get(&index) {
+            index
+        } else if index <= 0 && self.keep_non_positive {
+            index
+        } else {
+            // Segment times that are not attached to an attempt, or that are
+            // artifacts of route changes in the other run, are moved below
+            // all the indices that are already in use.
+            *self.others.entry(index).or_insert_with(|| {
+                *next_free_index -= 1;
+                *next_free_index
+            })
+        }
+    }
+}
+
+/// Returns whether the attempt of the other run needs to be placed before the
+/// attempt of this run. Attempts without a date are from old versions of
+/// LiveSplit, so they are placed first.
+fn other_is_earlier(attempt: &Attempt, other: &Attempt) -> bool {
+    match (attempt.started(), other.started()) {
+        (Some(started), Some(other_started)) => other_started.time < started.time,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// Identifies an attempt regardless of its index, so that the same attempt can
+/// be found in both runs.
+type AttemptKey = (DateTime, Option<DateTime>, [Option<TimeSpan>; 3]);
+
+/// Returns the key of the attempt. Attempts without a start date, such as all
+/// the resets stored by old versions of LiveSplit, can't be told apart from
+/// each other, so they don't have a key.
+fn attempt_key(attempt: &Attempt) -> Option<AttemptKey> {
+    Some((
+        attempt.started()?.time,
+        attempt.ended().map(|ended| ended.time),
+        TimingMethod::all().map(|method| attempt.time()[method]),
+    ))
+}
+
+fn final_time(run: &Run, method: TimingMethod) -> Option<TimeSpan> {
+    run.segments.last()?.personal_best_split_time()[method]
+}
+
+impl Run {
+    /// Merges the history of another Run of the same category into this Run.
+    /// This is useful if the same category is run on multiple machines, each
+    /// with their own splits file. The segments of both Runs need to have the
+    /// same names in the same order, otherwise an error is returned and this
+    /// Run stays unchanged.
+    ///
+    /// The Attempt Histories are combined in chronological order and all the
+    /// attempts get renumbered, so the indices don't collide. Attempts that
+    /// are in both Runs, such as when merging two copies of the same splits
+    /// file, are only kept once. They are recognized by their start, end and
+    /// final time, so attempts without a start date are always kept. The
+    /// Segment Histories are combined accordingly and the Best
+    /// Segments are recalculated. The Personal Best with the better final time
+    /// for the timing method provided is kept. The attempt counts get added
+    /// up, without counting the attempts that are in both Runs twice. All
+    /// other information, like the custom comparisons, is kept from this Run.
+    pub fn merge(&mut self, other: &Run, method: TimingMethod) -> Result<(), MergeError> {
+        if self.len() != other.len() {
+            return Err(MergeError::SegmentCount {
+                len: self.len(),
+                other_len: other.len(),
+            });
+        }
+
+        if let Some((position, (segment, other_segment))) = self
+            .segments
+            .iter()
+            .zip(&other.segments)
+            .enumerate()
+            .find(|(_, (segment, other_segment))| segment.name() != other_segment.name())
+        {
+            return Err(MergeError::SegmentName {
+                position: position + 1,
+                name: segment.name().into(),
+                other_name: other_segment.name().into(),
+            });
+        }
+
+        let mut mapping = IndexMapping::new(true);
+        let mut other_mapping = IndexMapping::new(false);
+
+        let mut attempts = self.attempt_history.iter().peekable();
+        let mut other_attempts = other.attempt_history.iter().peekable();
+        let mut attempt_history = Vec::with_capacity(attempts.len() + other_attempts.len());
+
+        let known_attempts = self
+            .attempt_history
+            .iter()
+            .filter_map(|attempt| Some((attempt_key(attempt)?, attempt.index())))
+            .collect::<HashMap<_, _>>();
+        let mut duplicates = Vec::new();
+
+        loop {
+            let (attempt, is_other) = match (attempts.peek(), other_attempts.peek()) {
+                (Some(attempt), Some(other)) if other_is_earlier(attempt, other) => {
+                    (other_attempts.next().unwrap(), true)
+                }
+                (Some(_), _) => (attempts.next().unwrap(), false),
+                (None, Some(_)) => (other_attempts.next().unwrap(), true),
+                (None, None) => break,
+            };
+            if is_other {
+                let known = attempt_key(attempt).and_then(|key| known_attempts.get(&key));
+                if let Some(&index) = known {
+                    duplicates.push((attempt.index(), index));
+                    continue;
+                }
+            }
+            let index = attempt_history.len() as i32 + 1;
+            let target = if is_other {
+                &mut other_mapping
+            } else {
+                &mut mapping
+            };
+            target.attempts.insert(attempt.index(), index);
+            attempt_history.push(
+                Attempt::new(
+                    index,
+                    attempt.time(),
+                    attempt.started(),
+                    attempt.ended(),
+                    attempt.pause_time(),
+                )
+                .with_pauses(attempt.pauses().to_vec()),
+            );
+        }
+
+        // The segment times of an attempt that is in both runs belong to the
+        // attempt that is kept.
+        for &(other_index, index) in &duplicates {
+            other_mapping
+                .attempts
+                .insert(other_index, mapping.attempts[&index]);
+        }
+
+        // The relocated indices need to stay below both the indices in use by
+        // this run and the renumbered attempts, which start at 1.
+        let mut next_free_index = self.min_segment_history_index().map_or(1, |min| min.min(1));
+
+        let take_other_pb = match (final_time(self, method), final_time(other, method)) {
+            (Some(time), Some(other_time)) => other_time < time,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+
+        for (segment, other_segment) in self.segments.iter_mut().zip(&other.segments) {
+            let mut history = SegmentHistory::default();
+            for &(index, time) in segment.segment_history() {
+                history.insert(mapping.map(index, &mut next_free_index), time);
+            }
+            for &(index, time) in other_segment.segment_history() {
+                history.insert(other_mapping.map(index, &mut next_free_index), time);
+            }
+
+            let mut best_segment_time = Time::new();
+            for method in TimingMethod::all() {
+                best_segment_time[method] = [
+                    segment.best_segment_time(),
+                    other_segment.best_segment_time(),
+                ]
+                .into_iter()
+                .chain(history.iter().map(|&(_, time)| time))
+                .filter_map(|time| time[method])
+                .min();
+            }
+
+            *segment.segment_history_mut() = history;
+            segment.set_best_segment_time(best_segment_time);
+
+            if take_other_pb {
+                segment.set_personal_best_split_time(other_segment.personal_best_split_time());
+            }
+        }
+
+        if take_other_pb {
+            self.metadata.set_run_id(other.metadata.run_id());
+        }
+
+        self.attempt_history = attempt_history;
+        self.attempt_count += other.attempt_count.saturating_sub(duplicates.len() as u32);
+        self.regenerate_comparisons();
+        self.mark_as_modified();
+
+        Ok(())
+    }
+}
@@ -16,24 +16,31 @@
 
 mod attempt;
 mod comparisons;
+pub mod diff;
 pub mod editor;
 mod linked_layout;
+mod merge;
 pub mod parser;
 mod run_metadata;
 pub mod saver;
 mod segment;
 mod segment_history;
+mod unknown_elements;
 
 #[cfg(test)]
 mod tests;
 
-pub use attempt::Attempt;
+pub use attempt::{Attempt, Pause};
 pub use comparisons::Comparisons;
-pub use editor::{Editor, RenameError};
+pub use diff::RunDiff;
+pub use editor::{Editor, RenameError, RetimeError};
 pub use linked_layout::LinkedLayout;
+pub use merge::MergeError;
 pub use run_metadata::{CustomVariable, RunMetadata};
 pub use segment::Segment;
+pub(crate) use segment_history::HistoryChanges;
 pub use segment_history::SegmentHistory;
+pub use unknown_elements::UnknownElements;
 
 use crate::{
     AtomicDateTime, Time, TimeSpan, TimingMethod,
@@ -76,6 +83,7 @@ pub struct Run {
     comparison_generators: ComparisonGenerators,
     auto_splitter_settings: String,
     linked_layout: Option<LinkedLayout>,
+    unknown_elements: UnknownElements,
 }
 
 #[derive(Clone, Debug)]
@@ -129,6 +137,7 @@ impl Run {
             comparison_generators: ComparisonGenerators(default_generators()),
             auto_splitter_settings: String::new(),
             linked_layout: None,
+            unknown_elements: UnknownElements::new(),
         }
     }
 
@@ -270,6 +279,13 @@ impl Run {
         &self.attempt_history
     }
 
+    /// Grants mutable access to the history of all the runs that have been
+    /// attempted.
+    #[inline]
+    pub(crate) const fn attempt_history_mut(&mut self) -> &mut Vec<Attempt> {
+        &mut self.attempt_history
+    }
+
     /// Accesses the custom comparisons that are stored in this Run. This
     /// includes `Personal Best` but excludes all the other Comparison
     /// Generators.
@@ -333,6 +349,21 @@ impl Run {
         &mut self.auto_splitter_settings
     }
 
+    /// Accesses the elements of the splits file that livesplit-core doesn't
+    /// know about. They are preserved, so they can be written back out when
+    /// saving the splits file.
+    #[inline]
+    pub const fn unknown_elements(&self) -> &UnknownElements {
+        &self.unknown_elements
+    }
+
+    /// Grants mutable access to the elements of the splits file that
+    /// livesplit-core doesn't know about.
+    #[inline]
+    pub const fn unknown_elements_mut(&mut self) -> &mut UnknownElements {
+        &mut self.unknown_elements
+    }
+
     /// Accesses the [`LinkedLayout`] of this `Run`. If a
     /// [`Layout`](crate::Layout) is linked, it is supposed to be loaded to
     /// visualize the `Run`.
@@ -419,14 +450,20 @@ impl Run {
         ended: Option<AtomicDateTime>,
         pause_time: Option<TimeSpan>,
     ) {
+        let index = self.next_attempt_index();
+        self.add_attempt_with_index(time, index, started, ended, pause_time);
+    }
+
+    /// Returns the index that the next Attempt added to the Run's Attempt
+    /// History with [`add_attempt`](Self::add_attempt) receives.
+    pub fn next_attempt_index(&self) -> i32 {
         let index = self
             .attempt_history
             .iter()
             .map(Attempt::index)
             .max()
             .unwrap_or(0);
-        let index = max(0, index + 1);
-        self.add_attempt_with_index(time, index, started, ended, pause_time);
+        max(0, index + 1)
     }
 
     /// Adds a new Attempt to the Run's Attempt History with a predetermined
@@ -445,6 +482,16 @@ impl Run {
         pause_time: Option<TimeSpan>,
     ) {
         let attempt = Attempt::new(index, time, started, ended, pause_time);
+        self.push_attempt(attempt);
+    }
+
+    /// Adds an Attempt to the Run's Attempt History.
+    ///
+    /// # Warning
+    ///
+    /// The Attempt's index may not overlap with an index that is already in
+    /// the Attempt History.
+    pub fn push_attempt(&mut self, attempt: Attempt) {
         self.attempt_history.push(attempt);
     }
 
@@ -549,12 +596,18 @@ impl Run {
     /// comparison times and history, removing duplicates in the segment
     /// histories and removing empty times.
     pub fn fix_splits(&mut self) {
+        self.fix_splits_recording(&mut HistoryChanges::default());
+    }
+
+    /// Fixes the splits just like [`fix_splits`](Self::fix_splits), while
+    /// recording the changes to the Segment Histories.
+    pub(crate) fn fix_splits_recording(&mut self, changes: &mut HistoryChanges) {
         for method in TimingMethod::all() {
-            self.fix_comparison_times_and_history(method);
+            self.fix_comparison_times_and_history(method, changes);
         }
-        self.remove_duplicates();
-        self.remove_none_values();
-        self.reattach_unattached_segment_history_elements();
+        self.remove_duplicates(changes);
+        self.remove_none_values(changes);
+        self.reattach_unattached_segment_history_elements(changes);
     }
 
     /// Clears out the Attempt History and the Segment Histories of all the segments.
@@ -580,7 +633,11 @@ impl Run {
         self.clear_run_id();
     }
 
-    fn fix_comparison_times_and_history(&mut self, method: TimingMethod) {
+    fn fix_comparison_times_and_history(
+        &mut self,
+        method: TimingMethod,
+        changes: &mut HistoryChanges,
+    ) {
         // Remove negative Best Segment Times
         for segment in &mut self.segments {
             if segment.best_segment_time_mut()[method].is_some_and(|t| t < TimeSpan::zero()) {
@@ -588,8 +645,8 @@ impl Run {
             }
         }
 
-        for segment in &mut self.segments {
-            fix_history_from_none_best_segments(segment, method);
+        for (index, segment) in self.segments.iter_mut().enumerate() {
+            fix_history_from_none_best_segments(segment, index, method, changes);
         }
 
         for comparison in &self.custom_comparisons {
@@ -615,50 +672,52 @@ impl Run {
             }
         }
 
-        for segment in &mut self.segments {
-            fix_history_from_best_segment_times(segment, method);
+        for (index, segment) in self.segments.iter_mut().enumerate() {
+            fix_history_from_best_segment_times(segment, index, method, changes);
         }
     }
 
-    fn remove_none_values(&mut self) {
+    fn remove_none_values(&mut self, changes: &mut HistoryChanges) {
         let mut cache = Vec::new();
         if let Some(min_index) = self.min_segment_history_index() {
             let max_index = self.max_attempt_history_index().unwrap_or(0) + 1;
             for run_index in min_index..max_index {
                 for index in 0..self.len() {
                     if let Some(element) = self.segments[index].segment_history().get(run_index) {
-                        if element.real_time.is_none() && element.game_time.is_none() {
+                        if TimingMethod::all()
+                            .into_iter()
+                            .all(|m| element[m].is_none())
+                        {
                             cache.push(run_index);
                         } else {
                             cache.clear();
                         }
                     } else {
                         // Remove None times in history that aren't followed by a non-None time
-                        self.remove_items_from_cache(index, &mut cache);
+                        self.remove_items_from_cache(index, &mut cache, changes);
                     }
                 }
                 let len = self.len();
-                self.remove_items_from_cache(len, &mut cache);
+                self.remove_items_from_cache(len, &mut cache, changes);
             }
         }
     }
 
-    fn remove_duplicates(&mut self) {
-        let mut rta_set = HashSet::new();
-        let mut igt_set = HashSet::new();
+    fn remove_duplicates(&mut self, changes: &mut HistoryChanges) {
+        let mut sets = TimingMethod::all().map(|_| HashSet::new());
 
-        for segment in self.segments_mut() {
+        for (segment_index, segment) in self.segments_mut().iter_mut().enumerate() {
             let history = segment.segment_history_mut();
 
-            rta_set.clear();
-            igt_set.clear();
+            for set in &mut sets {
+                set.clear();
+            }
 
             for &(_, time) in history.iter_actual_runs() {
-                if let Some(time) = time.real_time {
-                    rta_set.insert(time);
-                }
-                if let Some(time) = time.game_time {
-                    igt_set.insert(time);
+                for (method, set) in TimingMethod::all().into_iter().zip(&mut sets) {
+                    if let Some(time) = time[method] {
+                        set.insert(time);
+                    }
                 }
             }
 
@@ -668,25 +727,35 @@ impl Run {
                 }
 
                 let (mut is_none, mut is_unique) = (true, false);
-                if let Some(time) = time.real_time {
-                    is_unique |= rta_set.insert(time);
-                    is_none = false;
+                for (method, set) in TimingMethod::all().into_iter().zip(&mut sets) {
+                    if let Some(time) = time[method] {
+                        is_unique |= set.insert(time);
+                        is_none = false;
+                    }
                 }
 
-                if let Some(time) = time.game_time {
-                    is_unique |= igt_set.insert(time);
-                    is_none = false;
+                if !is_none && !is_unique {
+                    changes.record(segment_index, index, Some(time));
                 }
-
                 is_none || is_unique
             });
         }
     }
 
-    fn remove_items_from_cache(&mut self, index: usize, cache: &mut Vec<i32>) {
+    fn remove_items_from_cache(
+        &mut self,
+        index: usize,
+        cache: &mut Vec<i32>,
+        changes: &mut HistoryChanges,
+    ) {
         let ind = index - cache.len();
-        for (index, segment) in cache.drain(..).zip(self.segments_mut()[ind..].iter_mut()) {
-            segment.segment_history_mut().remove(index);
+        for (segment_index, index) in (ind..).zip(cache.drain(..)) {
+            if let Some(time) = self.segments[segment_index]
+                .segment_history_mut()
+                .remove(index)
+            {
+                changes.record(segment_index, index, Some(time));
+            }
         }
     }
 
@@ -702,17 +771,29 @@ impl Run {
     /// Fixes the Segment History by calculating the segment times from the
     /// Personal Best times and adding those to the Segment History.
     pub fn import_pb_into_segment_history(&mut self) {
+        self.import_pb_into_segment_history_recording(&mut HistoryChanges::default());
+    }
+
+    /// Imports the Personal Best into the Segment History just like
+    /// [`import_pb_into_segment_history`](Self::import_pb_into_segment_history),
+    /// while recording the changes to the Segment Histories.
+    pub(crate) fn import_pb_into_segment_history_recording(
+        &mut self,
+        changes: &mut HistoryChanges,
+    ) {
         if let Some(mut index) = self.min_segment_history_index() {
             for timing_method in TimingMethod::all() {
                 index -= 1;
                 let mut prev_time = TimeSpan::zero();
 
-                for segment in self.segments_mut() {
+                for (segment_index, segment) in self.segments.iter_mut().enumerate() {
                     // Import the PB splits into the history
                     let pb_time = segment.personal_best_split_time()[timing_method];
                     let time = Time::new()
                         .with_timing_method(timing_method, pb_time.map(|p| p - prev_time));
-                    segment.segment_history_mut().insert(index, time);
+                    let history = segment.segment_history_mut();
+                    changes.record(segment_index, index, history.get(index));
+                    history.insert(index, time);
 
                     if let Some(time) = pb_time {
                         prev_time = time;
@@ -730,7 +811,10 @@ impl Run {
     /// This panics if the segment index provided is out of bounds.
     pub fn import_best_segment(&mut self, segment_index: usize) {
         let best_segment_time = self.segments[segment_index].best_segment_time();
-        if best_segment_time.real_time.is_some() || best_segment_time.game_time.is_some() {
+        if TimingMethod::all()
+            .into_iter()
+            .any(|m| best_segment_time[m].is_some())
+        {
             // We can unwrap here because due to the fact that we can access the
             // best_segment_time of some segment, at least one exists.
             let index = self.min_segment_history_index().unwrap() - 1;
@@ -747,6 +831,17 @@ impl Run {
     ///
     /// This panics if there is no attempt in the Attempt History.
     pub fn update_segment_history(&mut self, segments_count: usize) {
+        self.update_segment_history_recording(segments_count, &mut HistoryChanges::default());
+    }
+
+    /// Updates the Segment History just like
+    /// [`update_segment_history`](Self::update_segment_history), while
+    /// recording the changes to the Segment Histories.
+    pub(crate) fn update_segment_history_recording(
+        &mut self,
+        segments_count: usize,
+        changes: &mut HistoryChanges,
+    ) {
         let mut previous_split_time = Time::zero();
 
         let segments = &mut self.segments[..segments_count];
@@ -756,15 +851,16 @@ impl Run {
             .expect("There is no attempt in the Attempt History.")
             .index();
 
-        for segment in segments {
+        for (segment_index, segment) in segments.iter_mut().enumerate() {
             let split_time = segment.split_time();
             let segment_time = split_time - previous_split_time;
-            segment.segment_history_mut().insert(index, segment_time);
-            if let Some(time) = split_time.real_time {
-                previous_split_time.real_time = Some(time);
-            }
-            if let Some(time) = split_time.game_time {
-                previous_split_time.game_time = Some(time);
+            let history = segment.segment_history_mut();
+            changes.record(segment_index, index, history.get(index));
+            history.insert(index, segment_time);
+            for method in TimingMethod::all() {
+                if let Some(time) = split_time[method] {
+                    previous_split_time[method] = Some(time);
+                }
             }
         }
     }
@@ -781,7 +877,7 @@ impl Run {
         }
     }
 
-    fn reattach_unattached_segment_history_elements(&mut self) {
+    fn reattach_unattached_segment_history_elements(&mut self, changes: &mut HistoryChanges) {
         let max_id = self.max_attempt_history_index().unwrap_or_default();
         let mut min_id = self.min_segment_history_index().unwrap_or_default();
 
@@ -794,9 +890,11 @@ impl Run {
         {
             let reassign_id = min_id - 1;
 
-            for segment in self.segments_mut() {
+            for (segment_index, segment) in self.segments.iter_mut().enumerate() {
                 let history = segment.segment_history_mut();
                 if let Some(time) = history.remove(unattached_id) {
+                    changes.record(segment_index, unattached_id, Some(time));
+                    changes.record(segment_index, reassign_id, None);
                     history.insert(reassign_id, time);
                 }
             }
@@ -812,24 +910,37 @@ impl Default for Run {
     }
 }
 
-fn fix_history_from_none_best_segments(segment: &mut Segment, method: TimingMethod) {
+fn fix_history_from_none_best_segments(
+    segment: &mut Segment,
+    segment_index: usize,
+    method: TimingMethod,
+    changes: &mut HistoryChanges,
+) {
     // Only do anything if the Best Segment Time is gone for the Segment in question
     if segment.best_segment_time()[method].is_none() {
         // Keep only the skipped segments
-        segment
-            .segment_history_mut()
-            .retain(|&(_, time)| time[method].is_none());
+        segment.segment_history_mut().retain(|&(index, time)| {
+            let is_skipped = time[method].is_none();
+            if !is_skipped {
+                changes.record(segment_index, index, Some(time));
+            }
+            is_skipped
+        });
     }
 }
 
-fn fix_history_from_best_segment_times(segment: &mut Segment, method: TimingMethod) {
+fn fix_history_from_best_segment_times(
+    segment: &mut Segment,
+    segment_index: usize,
+    method: TimingMethod,
+    changes: &mut HistoryChanges,
+) {
     if let Some(best_segment) = segment.best_segment_time()[method] {
-        for (_, time) in segment.segment_history_mut().iter_mut() {
+        for (index, time) in segment.segment_history_mut().iter_mut() {
             // Make sure no times in the history are lower than the Best Segment
-            if let Some(time) = &mut time[method] {
-                if *time < best_segment {
-                    *time = best_segment;
-                }
+            if time[method].is_some_and(|time| time < best_segment) {
+                changes.record(segment_index, *index, Some(*time));
+                time[method] = Some(best_segment);
             }
         }
     }
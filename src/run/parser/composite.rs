@@ -28,11 +28,14 @@
 //! ```
 
 use super::{
-    TimerKind, face_split, flitter, livesplit, llanfair, llanfair_gered, portal2_live_timer,
-    shit_split, source_live_timer, speedrun_igt, splitterino, splitterz, splitty,
-    time_split_tracker, urn, wsplit,
+    Diagnostics, Rejection, TimerKind, face_split, flitter, livesplit, llanfair, llanfair_gered,
+    portal2_live_timer, shit_split, source_live_timer, speedrun_igt, splits_io, splitterino,
+    splitterz, splitty, time_split_tracker, urn, wsplit,
+};
+use crate::{
+    Run,
+    platform::{path::Path, prelude::*},
 };
-use crate::{Run, platform::path::Path};
 use core::{result::Result as StdResult, str};
 
 /// The Error type for splits files that couldn't be parsed by the Composite
@@ -41,7 +44,11 @@ use core::{result::Result as StdResult, str};
 #[snafu(context(suffix(false)))]
 pub enum Error {
     /// No parser was able to parse the splits file.
-    NoParserParsedIt,
+    NoParserParsedIt {
+        /// The errors each of the parsers that got tried rejected the splits
+        /// file with.
+        rejections: Vec<Rejection>,
+    },
 }
 
 /// The Result type for the Composite Parser.
@@ -54,6 +61,9 @@ pub struct ParsedRun<'a> {
     pub run: Run,
     /// The parser that parsed it.
     pub kind: TimerKind<'a>,
+    /// The warnings of the parser that parsed it and the errors of all the
+    /// parsers that were tried before it.
+    pub diagnostics: Diagnostics,
 }
 
 impl ParsedRun<'_> {
@@ -62,15 +72,11 @@ impl ParsedRun<'_> {
         ParsedRun {
             run: self.run,
             kind: self.kind.into_owned(),
+            diagnostics: self.diagnostics,
         }
     }
 }
 
-#[inline(always)]
-const fn parsed(run: Run, kind: TimerKind<'_>) -> ParsedRun<'_> {
-    ParsedRun { run, kind }
-}
-
 /// Attempts to parse and fix a splits file by invoking the corresponding parser
 /// for the file format detected. Additionally you can provide the path of the
 /// splits file so additional files, like external images, can be loaded. If you
@@ -96,73 +102,81 @@ pub fn parse<'source>(
     source: &'source [u8],
     load_files_path: Option<&Path>,
 ) -> Result<ParsedRun<'source>> {
-    if let Ok(source) = simdutf8::basic::from_utf8(source) {
-        if let Ok(run) = livesplit::parse(source) {
-            return Ok(parsed(run, TimerKind::LiveSplit));
-        }
-
-        if let Ok(run) = wsplit::parse(source, load_files_path.is_some()) {
-            return Ok(parsed(run, TimerKind::WSplit));
-        }
-
-        if let Ok(run) = splitterz::parse(source, load_files_path.is_some()) {
-            return Ok(parsed(run, TimerKind::SplitterZ));
-        }
-
-        if let Ok(run) = shit_split::parse(source) {
-            return Ok(parsed(run, TimerKind::ShitSplit));
-        }
-
-        if let Ok(run) = splitty::parse(source) {
-            return Ok(parsed(run, TimerKind::Splitty));
-        }
-
-        if let Ok(run) = time_split_tracker::parse(source, load_files_path) {
-            return Ok(parsed(run, TimerKind::TimeSplitTracker));
-        }
+    let mut diagnostics = Diagnostics::new();
+
+    macro_rules! try_parser {
+        ($kind:expr, $result:expr) => {
+            match $result {
+                Ok(run) => {
+                    return Ok(ParsedRun {
+                        run,
+                        kind: $kind,
+                        diagnostics,
+                    });
+                }
+                Err(error) => diagnostics.reject($kind, &error),
+            }
+        };
+    }
 
-        if let Ok(run) = portal2_live_timer::parse(source) {
-            return Ok(parsed(run, TimerKind::Portal2LiveTimer));
+    if let Ok(source) = simdutf8::basic::from_utf8(source) {
+        // Warnings of the LiveSplit parser are only of interest if it actually
+        // parsed the splits file.
+        let mut livesplit_diagnostics = Diagnostics::new();
+        match livesplit::parse_with_diagnostics(source, &mut livesplit_diagnostics) {
+            Ok(run) => {
+                return Ok(ParsedRun {
+                    run,
+                    kind: TimerKind::LiveSplit,
+                    diagnostics: livesplit_diagnostics,
+                });
+            }
+            Err(error) => diagnostics.reject(TimerKind::LiveSplit, &error),
         }
 
-        if let Ok(run) = face_split::parse(source, load_files_path.is_some()) {
-            return Ok(parsed(run, TimerKind::FaceSplit));
-        }
+        try_parser!(
+            TimerKind::WSplit,
+            wsplit::parse(source, load_files_path.is_some())
+        );
+        try_parser!(
+            TimerKind::SplitterZ,
+            splitterz::parse(source, load_files_path.is_some())
+        );
+        try_parser!(TimerKind::ShitSplit, shit_split::parse(source));
+        try_parser!(TimerKind::Splitty, splitty::parse(source));
+        try_parser!(
+            TimerKind::TimeSplitTracker,
+            time_split_tracker::parse(source, load_files_path)
+        );
+        try_parser!(
+            TimerKind::Portal2LiveTimer,
+            portal2_live_timer::parse(source)
+        );
+        try_parser!(
+            TimerKind::FaceSplit,
+            face_split::parse(source, load_files_path.is_some())
+        );
 
         // Should be parsed after LiveSplit's parser, as it also parses all
         // LiveSplit files with the current implementation.
-        if let Ok(run) = llanfair_gered::parse(source) {
-            return Ok(parsed(run, TimerKind::LlanfairGered));
-        }
-
-        // Splitterino, SourceLiveTimer, Flitter, and SpeedRunIGT need to be
-        // before Urn because of a false positive due to the nature of parsing
-        // JSON files.
-        if let Ok(run) = splitterino::parse(source) {
-            return Ok(parsed(run, TimerKind::Splitterino));
-        }
+        try_parser!(TimerKind::LlanfairGered, llanfair_gered::parse(source));
 
-        if let Ok(run) = flitter::parse(source) {
-            return Ok(parsed(run, TimerKind::Flitter));
-        }
-
-        if let Ok(run) = source_live_timer::parse(source) {
-            return Ok(parsed(run, TimerKind::SourceLiveTimer));
-        }
-
-        if let Ok(run) = speedrun_igt::parse(source) {
-            return Ok(parsed(run, TimerKind::SpeedRunIGT));
-        }
+        // The splits.io Exchange Format, Splitterino, SourceLiveTimer, Flitter,
+        // and SpeedRunIGT need to be before Urn because of a false positive due
+        // to the nature of parsing JSON files.
+        try_parser!(TimerKind::SplitsIO, splits_io::parse(source));
+        try_parser!(TimerKind::Splitterino, splitterino::parse(source));
+        try_parser!(TimerKind::Flitter, flitter::parse(source));
+        try_parser!(TimerKind::SourceLiveTimer, source_live_timer::parse(source));
+        try_parser!(TimerKind::SpeedRunIGT, speedrun_igt::parse(source));
 
         // Urn accepts entirely empty JSON files.
-        if let Ok(run) = urn::parse(source) {
-            return Ok(parsed(run, TimerKind::Urn));
-        }
+        try_parser!(TimerKind::Urn, urn::parse(source));
     }
 
-    if let Ok(run) = llanfair::parse(source) {
-        return Ok(parsed(run, TimerKind::Llanfair));
-    }
+    try_parser!(TimerKind::Llanfair, llanfair::parse(source));
 
-    Err(Error::NoParserParsedIt)
+    Err(Error::NoParserParsedIt {
+        rejections: diagnostics.rejections,
+    })
 }
@@ -0,0 +1,80 @@
+use super::TimerKind;
+use crate::platform::prelude::*;
+use core::fmt;
+
+/// Diagnostics collected while parsing a splits file. They explain why a
+/// splits file may have loaded differently than expected. Parsers report the
+/// problems they were able to recover from as warnings. The Composite Parser
+/// additionally reports why each of the parsers it tried before the
+/// successful one rejected the splits file.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// The problems in the splits file that the parser recovered from.
+    pub warnings: Vec<Warning>,
+    /// The parsers that rejected the splits file, along with their errors.
+    pub rejections: Vec<Rejection>,
+}
+
+/// A problem in a splits file that the parser recovered from, usually by
+/// ignoring the information that couldn't be understood.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// Where in the splits file the problem is. For XML based splits files
+    /// this is the path of the element, such as
+    /// `/Run/AttemptHistory/Attempt[@id="123"]`.
+    pub location: String,
+    /// A description of the problem and how it was handled.
+    pub message: String,
+}
+
+/// The error a parser rejected the splits file with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection {
+    /// The parser that rejected the splits file.
+    pub kind: TimerKind<'static>,
+    /// The error the parser produced.
+    pub error: String,
+}
+
+impl Diagnostics {
+    /// Creates new empty diagnostics.
+    pub const fn new() -> Self {
+        Self {
+            warnings: Vec::new(),
+            rejections: Vec::new(),
+        }
+    }
+
+    /// Returns whether there are neither warnings nor rejections.
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty() && self.rejections.is_empty()
+    }
+
+    /// Reports a problem in the splits file that the parser recovered from.
+    pub fn warn(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.warnings.push(Warning {
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Reports that a parser rejected the splits file with the error provided.
+    pub fn reject(&mut self, kind: TimerKind<'_>, error: &dyn fmt::Display) {
+        self.rejections.push(Rejection {
+            kind: kind.into_owned(),
+            error: error.to_string(),
+        });
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.error)
+    }
+}
@@ -2,17 +2,18 @@
 
 use crate::{
     platform::prelude::*,
-    run::{AddComparisonError, LinkedLayout},
+    run::{parser::Diagnostics, AddComparisonError, Attempt, LinkedLayout, Pause, UnknownElements},
     settings::Image,
     util::{
         ascii_char::AsciiChar,
         xml::{
             helper::{
                 attribute, attribute_escaped_err, end_tag, image, optional_attribute_escaped_err,
-                parse_attributes, parse_base, parse_children, reencode_children, text,
-                text_as_escaped_string_err, text_parsed, Error as XmlError,
+                parse_attributes, parse_base, parse_children, reencode_attribute,
+                reencode_children, reencode_element, text, text_as_escaped_string_err, text_parsed,
+                Error as XmlError,
             },
-            Reader,
+            Attributes, Reader, TagName,
         },
     },
     AtomicDateTime, DateTime, Run, RunMetadata, Segment, Time, TimeSpan,
@@ -100,6 +101,9 @@ const fn type_hint<T>(v: Result<T>) -> Result<T> {
 #[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq)]
 struct Version(u32, u32, u32, u32);
 
+/// The version of the splits files that the LiveSplit Saver saves.
+const LATEST_VERSION: Version = Version(1, 8, 0, 0);
+
 fn parse_version(version: &str) -> Result<Version> {
     let splits = version.split('.');
     let mut v = [1, 0, 0, 0];
@@ -203,6 +207,8 @@ where
             time_span_opt(reader, |t| time.real_time = t)
         } else if tag.name() == "GameTime" {
             time_span_opt(reader, |t| time.game_time = t)
+        } else if tag.name() == "LoadRemovedTime" {
+            time_span_opt(reader, |t| time.load_removed_time = t)
         } else {
             end_tag(reader)
         }
@@ -228,11 +234,57 @@ fn parse_bool(value: &str) -> Result<bool> {
     }
 }
 
+fn unknown_element(
+    reader: &mut Reader<'_>,
+    tag: TagName<'_>,
+    attributes: Attributes<'_>,
+    unknown_elements: &mut UnknownElements,
+    parent: &str,
+    diagnostics: &mut Diagnostics,
+) -> Result<()> {
+    diagnostics.warn(
+        format!("{parent}/{}", tag.name()),
+        "the element is not known, it is preserved without being interpreted",
+    );
+    reencode_element(reader, tag, attributes, unknown_elements.xml_mut()).map_err(Into::into)
+}
+
+fn unknown_attributes(
+    attributes: Attributes<'_>,
+    known: &[&str],
+    unknown_elements: &mut UnknownElements,
+    element: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    for (key, value) in attributes.iter() {
+        if !known.contains(&key) {
+            diagnostics.warn(
+                format!("{element}/@{key}"),
+                "the attribute is not known, it is preserved without being interpreted",
+            );
+            unknown_elements
+                .attributes_mut()
+                .push((key.into(), reencode_attribute(value)));
+        }
+    }
+}
+
 fn parse_metadata(
     version: Version,
     reader: &mut Reader<'_>,
+    attributes: Attributes<'_>,
     metadata: &mut RunMetadata,
+    unknown_elements: &mut UnknownElements,
+    diagnostics: &mut Diagnostics,
 ) -> Result<()> {
+    unknown_attributes(
+        attributes,
+        &[],
+        unknown_elements,
+        "/Run/Metadata",
+        diagnostics,
+    );
+
     if version >= Version(1, 6, 0, 0) {
         parse_children(reader, |reader, tag, attributes| match tag.name() {
             "Run" => {
@@ -264,7 +316,14 @@ fn parse_metadata(
                 type_hint(text(reader, |t| var.set_value(t)))?;
                 Ok(())
             }),
-            _ => end_tag(reader),
+            _ => unknown_element(
+                reader,
+                tag,
+                attributes,
+                unknown_elements,
+                "/Run/Metadata",
+                diagnostics,
+            ),
         })
     } else {
         end_tag(reader)
@@ -274,16 +333,26 @@ fn parse_metadata(
 fn parse_segment(
     version: Version,
     reader: &mut Reader<'_>,
+    attributes: Attributes<'_>,
     image_buf: &mut Vec<MaybeUninit<u8>>,
     run: &mut Run,
+    diagnostics: &mut Diagnostics,
 ) -> Result<Segment> {
     let mut segment = Segment::new("");
-
-    parse_children(reader, |reader, tag, _| match tag.name() {
+    unknown_attributes(
+        attributes,
+        &[],
+        segment.unknown_elements_mut(),
+        &format!("/Run/Segments/Segment[{}]", run.len() + 1),
+        diagnostics,
+    );
+
+    parse_children(reader, |reader, tag, attributes| match tag.name() {
         "Name" => text(reader, |t| segment.set_name(t)),
         "Icon" => image(reader, image_buf, |i| {
             segment.set_icon(Image::new(i.into(), Image::ICON))
         }),
+        "Runner" => text(reader, |t| segment.set_runner(t)),
         "SplitTimes" => {
             if version >= Version(1, 3, 0, 0) {
                 parse_children(reader, |reader, tag, attributes| {
@@ -335,7 +404,14 @@ fn parse_segment(
                 time_old(reader, |t| segment.segment_history_mut().insert(index, t))
             }
         }),
-        _ => end_tag(reader),
+        _ => unknown_element(
+            reader,
+            tag,
+            attributes,
+            segment.unknown_elements_mut(),
+            &format!("/Run/Segments/Segment[{}]", run.len() + 1),
+            diagnostics,
+        ),
     })?;
 
     Ok(segment)
@@ -369,35 +445,71 @@ fn parse_run_history(version: Version, reader: &mut Reader<'_>, run: &mut Run) -
     }
 }
 
-fn parse_attempt_history(version: Version, reader: &mut Reader<'_>, run: &mut Run) -> Result<()> {
+fn parse_attempt_history(
+    version: Version,
+    reader: &mut Reader<'_>,
+    run: &mut Run,
+    diagnostics: &mut Diagnostics,
+) -> Result<()> {
     if version >= Version(1, 5, 0, 0) {
         parse_children(reader, |reader, _, attributes| {
             let mut time = Time::new();
             let mut pause_time = None;
             let mut index = None;
-            let (mut started, mut started_synced) = (None, false);
-            let (mut ended, mut ended_synced) = (None, false);
+            let (mut started, mut started_synced) = (Ok(None), false);
+            let (mut ended, mut ended_synced) = (Ok(None), false);
 
             type_hint(parse_attributes(attributes, |k, v| {
                 match k {
                     "id" => index = Some(v.escaped().parse()?),
-                    "started" => started = Some(parse_date_time(v.escaped())?),
+                    "started" => started = parse_date_time(v.escaped()).map(Some),
                     "isStartedSynced" => started_synced = parse_bool(v.escaped())?,
-                    "ended" => ended = Some(parse_date_time(v.escaped())?),
+                    "ended" => ended = parse_date_time(v.escaped()).map(Some),
                     "isEndedSynced" => ended_synced = parse_bool(v.escaped())?,
                     _ => {}
                 }
                 Ok(true)
             }))?;
 
-            let index = index.ok_or(Error::Xml {
+            let index: i32 = index.ok_or(Error::Xml {
                 source: XmlError::AttributeNotFound,
             })?;
 
+            let location = || format!("/Run/AttemptHistory/Attempt[@id=\"{index}\"]");
+            let started = started.unwrap_or_else(|_| {
+                diagnostics.warn(location(), "the started date is invalid, it got ignored");
+                None
+            });
+            let ended = ended.unwrap_or_else(|_| {
+                diagnostics.warn(location(), "the ended date is invalid, it got ignored");
+                None
+            });
+
+            let mut pauses = Vec::new();
+
             parse_children(reader, |reader, tag, _| match tag.name() {
                 "RealTime" => time_span_opt(reader, |t| time.real_time = t),
                 "GameTime" => time_span_opt(reader, |t| time.game_time = t),
+                "LoadRemovedTime" => time_span_opt(reader, |t| time.load_removed_time = t),
                 "PauseTime" => time_span_opt(reader, |t| pause_time = t),
+                "Pauses" => parse_children(reader, |reader, _, attributes| {
+                    let (mut start, mut end) = (None, None);
+                    type_hint(parse_attributes(attributes, |k, v| {
+                        match k {
+                            "start" => start = parse_time_span(v.escaped()).ok(),
+                            "end" => end = parse_time_span(v.escaped()).ok(),
+                            _ => {}
+                        }
+                        Ok(true)
+                    }))?;
+                    match (start, end) {
+                        (Some(start), Some(end)) if start <= end => {
+                            pauses.push(Pause { start, end })
+                        }
+                        _ => diagnostics.warn(location(), "a pause is invalid, it got ignored"),
+                    }
+                    end_tag(reader)
+                }),
                 _ => end_tag(reader),
             })?;
 
@@ -405,12 +517,18 @@ fn parse_attempt_history(version: Version, reader: &mut Reader<'_>, run: &mut Ru
             let ended = if version <= Version(1, 7, 0, 0)
                 && catch! { ended? < started?.time }.unwrap_or(false)
             {
+                diagnostics.warn(
+                    location(),
+                    "the attempt ended before it started, the ended date got ignored",
+                );
                 None
             } else {
                 ended.map(|t| AtomicDateTime::new(t, ended_synced))
             };
 
-            run.add_attempt_with_index(time, index, started, ended, pause_time);
+            run.push_attempt(
+                Attempt::new(index, time, started, ended, pause_time).with_pauses(pauses),
+            );
 
             Ok(())
         })
@@ -421,11 +539,19 @@ fn parse_attempt_history(version: Version, reader: &mut Reader<'_>, run: &mut Ru
 
 /// Attempts to parse a LiveSplit splits file.
 pub fn parse(source: &str) -> Result<Run> {
+    parse_with_diagnostics(source, &mut Diagnostics::new())
+}
+
+/// Attempts to parse a LiveSplit splits file. The problems in the splits file
+/// that the parser is able to recover from are reported as warnings in the
+/// diagnostics provided.
+pub fn parse_with_diagnostics(source: &str, diagnostics: &mut Diagnostics) -> Result<Run> {
     let mut reader = Reader::new(source);
 
     let mut image_buf = Vec::new();
 
     let mut run = Run::new();
+    let mut unknown_metadata_elements = UnknownElements::new();
 
     let mut required_flags = 0u8;
 
@@ -436,7 +562,23 @@ pub fn parse(source: &str) -> Result<Run> {
             Ok(())
         }))?;
 
-        parse_children(reader, |reader, tag, _| match tag.name() {
+        unknown_attributes(
+            attributes,
+            &["version"],
+            run.unknown_elements_mut(),
+            "/Run",
+            diagnostics,
+        );
+
+        if version > LATEST_VERSION {
+            diagnostics.warn(
+                "/Run",
+                "the splits file was saved by a newer version of LiveSplit, \
+                 some of its information may not be understood",
+            );
+        }
+
+        parse_children(reader, |reader, tag, attributes| match tag.name() {
             "GameIcon" => {
                 required_flags |= 1;
                 image(reader, &mut image_buf, |i| {
@@ -459,14 +601,28 @@ pub fn parse(source: &str) -> Result<Run> {
                 required_flags |= 1 << 4;
                 text_parsed(reader, |t| run.set_attempt_count(t))
             }
-            "AttemptHistory" => parse_attempt_history(version, reader, &mut run),
+            "AttemptHistory" => parse_attempt_history(version, reader, &mut run, diagnostics),
             "RunHistory" => parse_run_history(version, reader, &mut run),
-            "Metadata" => parse_metadata(version, reader, run.metadata_mut()),
+            "Metadata" => parse_metadata(
+                version,
+                reader,
+                attributes,
+                run.metadata_mut(),
+                &mut unknown_metadata_elements,
+                diagnostics,
+            ),
             "Segments" => {
                 required_flags |= 1 << 5;
-                parse_children(reader, |reader, tag, _| {
+                parse_children(reader, |reader, tag, attributes| {
                     if tag.name() == "Segment" {
-                        let segment = parse_segment(version, reader, &mut image_buf, &mut run)?;
+                        let segment = parse_segment(
+                            version,
+                            reader,
+                            attributes,
+                            &mut image_buf,
+                            &mut run,
+                            diagnostics,
+                        )?;
                         run.push_segment(segment);
                         Ok(())
                     } else {
@@ -487,7 +643,14 @@ pub fn parse(source: &str) -> Result<Run> {
                     Some(LinkedLayout::Path(t.into_owned()))
                 });
             }),
-            _ => end_tag(reader),
+            _ => unknown_element(
+                reader,
+                tag,
+                attributes,
+                run.unknown_elements_mut(),
+                "/Run",
+                diagnostics,
+            ),
         })
     })?;
 
@@ -497,6 +660,8 @@ pub fn parse(source: &str) -> Result<Run> {
         });
     }
 
+    *run.metadata_mut().unknown_elements_mut() = unknown_metadata_elements;
+
     Ok(run)
 }
 
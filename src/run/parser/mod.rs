@@ -38,6 +38,7 @@ pub mod portal2_live_timer;
 pub mod shit_split;
 pub mod source_live_timer;
 pub mod speedrun_igt;
+pub mod splits_io;
 pub mod splitterino;
 pub mod splitterz;
 pub mod splitty;
@@ -45,8 +46,12 @@ pub mod time_split_tracker;
 pub mod urn;
 pub mod wsplit;
 
+mod diagnostics;
 mod timer_kind;
 
-pub use self::timer_kind::TimerKind;
+pub use self::{
+    diagnostics::{Diagnostics, Rejection, Warning},
+    timer_kind::TimerKind,
+};
 
 pub use composite::{parse, parse_and_fix};
@@ -80,6 +80,7 @@ fn time(rta: i64, igt: i64) -> Time {
     Time {
         real_time: Some(Duration::milliseconds(rta).into()),
         game_time: Some(Duration::milliseconds(igt).into()),
+        load_removed_time: None,
     }
 }
 
@@ -0,0 +1,250 @@
+//! Provides the parser for the splits.io Exchange Format. This is a generic
+//! JSON based splits file format that many tools use for exchanging splits.
+//!
+//! The format is documented here:
+//! https://github.com/glacials/splits-io/tree/master/public/schema
+
+use crate::{AtomicDateTime, DateTime, Run, Segment, Time, TimeSpan, platform::prelude::*};
+use alloc::borrow::Cow;
+use core::result::Result as StdResult;
+use serde_derive::Deserialize;
+use serde_json::Error as JsonError;
+use snafu::ensure;
+use time::format_description::well_known::Rfc3339;
+
+/// The name of the permanent custom variable that the runners of the run are
+/// stored in. Multiple runners are separated by a comma.
+pub const RUNNER_VARIABLE: &str = "Runner";
+
+/// The Error type for splits files that couldn't be parsed by the splits.io
+/// Exchange Format Parser.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum Error {
+    /// Failed to parse JSON.
+    Json {
+        /// The underlying error.
+        #[cfg_attr(not(feature = "std"), snafu(source(false)))]
+        source: JsonError,
+    },
+    /// The version of the schema is not supported.
+    UnsupportedSchemaVersion,
+}
+
+/// The Result type for the splits.io Exchange Format Parser.
+pub type Result<T> = StdResult<T, Error>;
+
+#[derive(Deserialize)]
+struct Splits<'a> {
+    #[serde(rename = "_schemaVersion", borrow)]
+    schema_version: Cow<'a, str>,
+    #[serde(borrow)]
+    game: Option<Named<'a>>,
+    #[serde(borrow)]
+    category: Option<Named<'a>>,
+    #[serde(borrow, default)]
+    runners: Vec<Named<'a>>,
+    #[serde(borrow)]
+    attempts: Option<Attempts<'a>>,
+    #[serde(borrow, default)]
+    segments: Vec<SplitsIoSegment<'a>>,
+}
+
+#[derive(Deserialize)]
+struct Named<'a> {
+    #[serde(borrow)]
+    longname: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    shortname: Option<Cow<'a, str>>,
+}
+
+impl<'a> Named<'a> {
+    fn into_name(self) -> Option<Cow<'a, str>> {
+        self.longname.or(self.shortname)
+    }
+}
+
+#[derive(Deserialize)]
+struct Attempts<'a> {
+    total: Option<u32>,
+    #[serde(borrow, default)]
+    histories: Vec<AttemptHistory<'a>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AttemptHistory<'a> {
+    attempt_number: i32,
+    #[serde(rename = "realtimeMS")]
+    realtime_ms: Option<f64>,
+    #[serde(rename = "gametimeMS")]
+    gametime_ms: Option<f64>,
+    #[serde(rename = "loadRemovedTimeMS")]
+    load_removed_time_ms: Option<f64>,
+    #[serde(borrow)]
+    started_at: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    ended_at: Option<Cow<'a, str>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitsIoSegment<'a> {
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    ended_at: Option<Duration>,
+    best_duration: Option<Duration>,
+    #[serde(default)]
+    is_skipped: bool,
+    #[serde(default)]
+    histories: Vec<SegmentHistory>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentHistory {
+    attempt_number: i32,
+    #[serde(rename = "realtimeMS")]
+    realtime_ms: Option<f64>,
+    #[serde(rename = "gametimeMS")]
+    gametime_ms: Option<f64>,
+    #[serde(rename = "loadRemovedTimeMS")]
+    load_removed_time_ms: Option<f64>,
+    #[serde(default)]
+    is_skipped: bool,
+    #[serde(default)]
+    is_reset: bool,
+}
+
+#[derive(Deserialize)]
+struct Duration {
+    #[serde(rename = "realtimeMS")]
+    realtime_ms: Option<f64>,
+    #[serde(rename = "gametimeMS")]
+    gametime_ms: Option<f64>,
+    #[serde(rename = "loadRemovedTimeMS")]
+    load_removed_time_ms: Option<f64>,
+}
+
+impl Duration {
+    fn to_time(&self) -> Time {
+        time(
+            self.realtime_ms,
+            self.gametime_ms,
+            self.load_removed_time_ms,
+        )
+    }
+}
+
+/// The Load Removed Time is not part of the format, but livesplit-core stores
+/// it in an additional field.
+fn time(
+    realtime_ms: Option<f64>,
+    gametime_ms: Option<f64>,
+    load_removed_time_ms: Option<f64>,
+) -> Time {
+    Time {
+        real_time: realtime_ms.map(TimeSpan::from_milliseconds),
+        game_time: gametime_ms.map(TimeSpan::from_milliseconds),
+        load_removed_time: load_removed_time_ms.map(TimeSpan::from_milliseconds),
+    }
+}
+
+/// Parses a date time in the RFC 3339 format, as it's used by the splits.io
+/// Exchange Format. Date times with an offset are converted to UTC.
+fn parse_date_time(text: &str) -> Option<DateTime> {
+    let date_time = DateTime::parse(text, &Rfc3339).ok()?;
+    Some(date_time.to_offset(time::UtcOffset::UTC))
+}
+
+fn date_time(text: Option<Cow<'_, str>>) -> Option<AtomicDateTime> {
+    Some(AtomicDateTime::new(parse_date_time(&text?)?, false))
+}
+
+/// Attempts to parse a splits file in the splits.io Exchange Format.
+pub fn parse(source: &str) -> Result<Run> {
+    let splits: Splits<'_> =
+        serde_json::from_str(source).map_err(|source| Error::Json { source })?;
+
+    ensure!(
+        splits.schema_version.starts_with("v1."),
+        UnsupportedSchemaVersion
+    );
+
+    let mut run = Run::new();
+
+    if let Some(name) = splits.game.and_then(Named::into_name) {
+        run.set_game_name(name);
+    }
+    if let Some(name) = splits.category.and_then(Named::into_name) {
+        run.set_category_name(name);
+    }
+
+    let mut runners = splits.runners.into_iter().filter_map(Named::into_name);
+    if let Some(first) = runners.next() {
+        let mut value = first.into_owned();
+        for runner in runners {
+            value.push_str(", ");
+            value.push_str(&runner);
+        }
+        run.metadata_mut()
+            .custom_variable_mut(RUNNER_VARIABLE)
+            .permanent()
+            .set_value(value);
+    }
+
+    if let Some(attempts) = splits.attempts {
+        if let Some(total) = attempts.total {
+            run.set_attempt_count(total);
+        }
+        for attempt in attempts.histories {
+            run.add_attempt_with_index(
+                time(
+                    attempt.realtime_ms,
+                    attempt.gametime_ms,
+                    attempt.load_removed_time_ms,
+                ),
+                attempt.attempt_number,
+                date_time(attempt.started_at),
+                date_time(attempt.ended_at),
+                None,
+            );
+        }
+    }
+
+    for split in splits.segments {
+        let mut segment = Segment::new(split.name);
+
+        if !split.is_skipped {
+            if let Some(ended_at) = split.ended_at {
+                segment.set_personal_best_split_time(ended_at.to_time());
+            }
+        }
+
+        if let Some(best_duration) = split.best_duration {
+            segment.set_best_segment_time(best_duration.to_time());
+        }
+
+        let history = segment.segment_history_mut();
+        for element in split.histories {
+            // A reset attempt never reached the segment.
+            if element.is_reset {
+                continue;
+            }
+            let segment_time = if element.is_skipped {
+                Time::default()
+            } else {
+                time(
+                    element.realtime_ms,
+                    element.gametime_ms,
+                    element.load_removed_time_ms,
+                )
+            };
+            history.insert(element.attempt_number, segment_time);
+        }
+
+        run.push_segment(segment);
+    }
+
+    Ok(run)
+}
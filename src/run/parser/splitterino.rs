@@ -155,6 +155,7 @@ fn parse_split_time(
     Time {
         real_time,
         game_time,
+        load_removed_time: None,
     }
 }
 
@@ -35,6 +35,8 @@ pub enum TimerKind<'a> {
     Splitterino,
     /// SpeedRunIGT
     SpeedRunIGT,
+    /// The splits.io Exchange Format
+    SplitsIO,
     /// A Generic Timer. The name of the timer is associated with the variant.
     /// "Generic Timer" is used if there is no known name.
     Generic(Cow<'a, str>),
@@ -59,6 +61,7 @@ impl TimerKind<'_> {
             TimerKind::SourceLiveTimer => TimerKind::SourceLiveTimer,
             TimerKind::Splitterino => TimerKind::Splitterino,
             TimerKind::SpeedRunIGT => TimerKind::SpeedRunIGT,
+            TimerKind::SplitsIO => TimerKind::SplitsIO,
             TimerKind::Generic(v) => TimerKind::Generic(v.into_owned().into()),
         }
     }
@@ -82,6 +85,7 @@ impl fmt::Display for TimerKind<'_> {
             TimerKind::SourceLiveTimer => "SourceLiveTimer",
             TimerKind::Splitterino => "Splitterino",
             TimerKind::SpeedRunIGT => "SpeedRunIGT",
+            TimerKind::SplitsIO => "splits.io Exchange Format",
             TimerKind::Generic(name) => name,
         })
     }
@@ -1,3 +1,4 @@
+use super::UnknownElements;
 use crate::{
     platform::prelude::*,
     util::{
@@ -77,6 +78,8 @@ pub struct RunMetadata {
     /// the runner. Additionally auto splitters or other sources may provide
     /// temporary custom variables that are not stored in the splits files.
     pub custom_variables: Map<CustomVariable>,
+    #[serde(skip)]
+    unknown_elements: UnknownElements,
 }
 
 impl RunMetadata {
@@ -214,6 +217,21 @@ impl RunMetadata {
         self.custom_variables.iter()
     }
 
+    /// Accesses the elements of the metadata of the splits file that
+    /// livesplit-core doesn't know about. They are preserved, so they can be
+    /// written back out when saving the splits file.
+    #[inline]
+    pub const fn unknown_elements(&self) -> &UnknownElements {
+        &self.unknown_elements
+    }
+
+    /// Grants mutable access to the elements of the metadata of the splits
+    /// file that livesplit-core doesn't know about.
+    #[inline]
+    pub const fn unknown_elements_mut(&mut self) -> &mut UnknownElements {
+        &mut self.unknown_elements
+    }
+
     /// Resets all the Metadata Information.
     pub fn clear(&mut self) {
         self.run_id.clear();
@@ -222,5 +240,6 @@ impl RunMetadata {
         self.uses_emulator = false;
         self.speedrun_com_variables.clear();
         self.custom_variables.clear();
+        self.unknown_elements.clear();
     }
 }
@@ -0,0 +1,147 @@
+//! The CSV Saver exports the history of a Run as comma separated values that
+//! can be imported into spreadsheets. There are two tables: the Attempt History
+//! and the Segment Histories. Both of them have a row for each attempt index.
+//!
+//! # Examples
+//!
+//! Exporting both tables of a Run.
+//!
+//! ```
+//! use livesplit_core::run::saver::csv;
+//! use livesplit_core::{Run, Segment};
+//!
+//! let mut run = Run::new();
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! let mut attempts = String::new();
+//! csv::save_attempt_history(&run, &mut attempts).expect("Couldn't save the attempts");
+//!
+//! let mut segments = String::new();
+//! csv::save_segment_history(&run, &mut segments).expect("Couldn't save the segments");
+//!
+//! assert_eq!(
+//!     attempts,
+//!     "Attempt,Started,Ended,Pause Time,Real Time,Game Time,Load Removed Time\r\n",
+//! );
+//! assert_eq!(
+//!     segments,
+//!     "Attempt,Cap Kingdom (Real Time),Cap Kingdom (Game Time),Cap Kingdom (Load Removed Time)\r\n",
+//! );
+//! ```
+
+use crate::{
+    AtomicDateTime, Run, TimeSpan,
+    platform::prelude::*,
+    timing::formatter::{Complete, TimeFormatter},
+};
+use core::fmt;
+use time::UtcOffset;
+
+fn field<W: fmt::Write>(writer: &mut W, value: &str) -> fmt::Result {
+    if value.contains([',', '"', '\r', '\n']) {
+        writer.write_char('"')?;
+        for (i, part) in value.split('"').enumerate() {
+            if i != 0 {
+                writer.write_str("\"\"")?;
+            }
+            writer.write_str(part)?;
+        }
+        writer.write_char('"')
+    } else {
+        writer.write_str(value)
+    }
+}
+
+fn time<W: fmt::Write>(writer: &mut W, time: Option<TimeSpan>) -> fmt::Result {
+    if let Some(time) = time {
+        write!(writer, "{}", Complete.format(time))?;
+    }
+    Ok(())
+}
+
+fn date<W: fmt::Write>(writer: &mut W, date: Option<AtomicDateTime>) -> fmt::Result {
+    if let Some(date) = date {
+        let date = date.time.to_offset(UtcOffset::UTC);
+        let (year, month, day) = date.to_calendar_date();
+        let month = month as u8;
+        let (hour, minute, second) = date.to_hms();
+        write!(
+            writer,
+            "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}"
+        )?;
+    }
+    Ok(())
+}
+
+/// Saves the Attempt History of a Run as a CSV table. There is a row for each
+/// attempt with its index, the UTC date times it started and ended at, the
+/// amount of time it was paused for and its final Real Time, Game Time and
+/// Load Removed Time. Unknown values are left empty.
+pub fn save_attempt_history<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    writer
+        .write_str("Attempt,Started,Ended,Pause Time,Real Time,Game Time,Load Removed Time\r\n")?;
+
+    for attempt in run.attempt_history() {
+        write!(writer, "{},", attempt.index())?;
+        date(&mut writer, attempt.started())?;
+        writer.write_char(',')?;
+        date(&mut writer, attempt.ended())?;
+        writer.write_char(',')?;
+        time(&mut writer, attempt.pause_time())?;
+        writer.write_char(',')?;
+        time(&mut writer, attempt.time().real_time)?;
+        writer.write_char(',')?;
+        time(&mut writer, attempt.time().game_time)?;
+        writer.write_char(',')?;
+        time(&mut writer, attempt.time().load_removed_time)?;
+        writer.write_str("\r\n")?;
+    }
+
+    Ok(())
+}
+
+/// Saves the Segment Histories of a Run as a CSV table. There is a row for each
+/// attempt index found in any of the Segment Histories and three columns for
+/// each segment, storing the Real Time, the Game Time and the Load Removed Time
+/// of the segment in that attempt. Indices below 1 don't refer to actual attempts, but are artifacts
+/// of route changes and similar algorithmic changes. A segment that was never
+/// reached in an attempt is left empty.
+pub fn save_segment_history<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    writer.write_str("Attempt")?;
+    for segment in run.segments() {
+        writer.write_char(',')?;
+        field(&mut writer, &format!("{} (Real Time)", segment.name()))?;
+        writer.write_char(',')?;
+        field(&mut writer, &format!("{} (Game Time)", segment.name()))?;
+        writer.write_char(',')?;
+        field(
+            &mut writer,
+            &format!("{} (Load Removed Time)", segment.name()),
+        )?;
+    }
+    writer.write_str("\r\n")?;
+
+    let mut indices = run
+        .segments()
+        .iter()
+        .flat_map(|s| s.segment_history().iter().map(|&(index, _)| index))
+        .collect::<Vec<_>>();
+    indices.sort_unstable();
+    indices.dedup();
+
+    for index in indices {
+        write!(writer, "{index}")?;
+        for segment in run.segments() {
+            let segment_time = segment.segment_history().get(index).unwrap_or_default();
+            writer.write_char(',')?;
+            time(&mut writer, segment_time.real_time)?;
+            writer.write_char(',')?;
+            time(&mut writer, segment_time.game_time)?;
+            writer.write_char(',')?;
+            time(&mut writer, segment_time.load_removed_time)?;
+        }
+        writer.write_str("\r\n")?;
+    }
+
+    Ok(())
+}
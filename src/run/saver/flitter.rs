@@ -0,0 +1,91 @@
+//! The Flitter Saver saves Runs as Flitter splits files.
+//!
+//! Flitter only supports Real Time, so all the Game Times are lost. Only the
+//! Personal Best and the Best Segments are stored. Keep in mind that Flitter
+//! requires the last split of the Personal Best to not be empty.
+
+use super::format_time;
+use crate::{Run, TimeSpan, platform::prelude::*};
+use core::fmt;
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+struct Splits<'a> {
+    title: &'a str,
+    category: &'a str,
+    attempts: u32,
+    completed: u32,
+    split_names: Vec<&'a str>,
+    golds: Vec<Option<Gold>>,
+    personal_best: PersonalBest,
+}
+
+#[derive(Serialize)]
+struct Gold {
+    duration: String,
+}
+
+#[derive(Serialize)]
+struct PersonalBest {
+    attempt: u32,
+    splits: Vec<Option<Split>>,
+}
+
+#[derive(Serialize)]
+struct Split {
+    time: String,
+}
+
+fn time(time: TimeSpan) -> String {
+    format_time(time, 3)
+}
+
+/// Saves a Run as a Flitter splits file.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    let finished_attempts = || {
+        run.attempt_history()
+            .iter()
+            .filter(|a| a.time().real_time.is_some())
+    };
+
+    let pb_time = run
+        .segments()
+        .last()
+        .and_then(|s| s.personal_best_split_time().real_time);
+
+    let pb_attempt = pb_time
+        .and_then(|pb_time| finished_attempts().find(|a| a.time().real_time == Some(pb_time)))
+        .map_or(0, |a| a.index().max(0) as u32);
+
+    let splits = Splits {
+        title: run.game_name(),
+        category: run.category_name(),
+        attempts: run.attempt_count(),
+        completed: finished_attempts().count() as u32,
+        split_names: run.segments().iter().map(|s| s.name()).collect(),
+        golds: run
+            .segments()
+            .iter()
+            .map(|s| {
+                Some(Gold {
+                    duration: time(s.best_segment_time().real_time?),
+                })
+            })
+            .collect(),
+        personal_best: PersonalBest {
+            attempt: pb_attempt,
+            splits: run
+                .segments()
+                .iter()
+                .map(|s| {
+                    Some(Split {
+                        time: time(s.personal_best_split_time().real_time?),
+                    })
+                })
+                .collect(),
+        },
+    };
+
+    let json = serde_json::to_string_pretty(&splits).map_err(|_| fmt::Error)?;
+    writer.write_str(&json)
+}
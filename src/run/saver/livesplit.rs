@@ -26,17 +26,21 @@
 
 use crate::{
     platform::prelude::*,
-    run::LinkedLayout,
-    settings::Image,
+    run::{Attempt, LinkedLayout, UnknownElements},
+    settings::{Image, ImageId},
     timing::formatter::{Complete, TimeFormatter},
     util::xml::{AttributeWriter, DisplayAlreadyEscaped, Text, Writer, NO_ATTRIBUTES},
     DateTime, Run, Time, Timer, TimerPhase,
 };
 use alloc::borrow::Cow;
-use core::{fmt, mem::MaybeUninit};
+use core::{
+    fmt,
+    mem::{self, MaybeUninit},
+};
+use hashbrown::HashMap;
 use time::UtcOffset;
 
-const LSS_IMAGE_HEADER: &[u8; 156] = include_bytes!("lss_image_header.bin");
+pub(crate) const LSS_IMAGE_HEADER: &[u8; 156] = include_bytes!("lss_image_header.bin");
 
 const fn bool(value: bool) -> Text<'static> {
     Text::new_escaped(if value { "True" } else { "False" })
@@ -62,38 +66,178 @@ where
     })
 }
 
-fn image<W: fmt::Write>(
+/// Writes a tag with the attributes provided, followed by the unknown
+/// attributes that were preserved when the splits file got parsed.
+fn tag_with_unknown_attributes<'a, W, const N: usize, F>(
     writer: &mut Writer<W>,
     tag: &str,
-    image: &Image,
-    base64_buf: &mut Vec<MaybeUninit<u8>>,
+    attributes: [(&str, Text<'a>); N],
+    unknown: &UnknownElements,
+    content: F,
+) -> fmt::Result
+where
+    W: fmt::Write,
+    F: FnOnce(&mut Writer<W>) -> fmt::Result,
+{
+    writer.tag(tag, |mut tag| {
+        for (key, value) in attributes {
+            tag.attribute(key, value)?;
+        }
+        for (key, value) in unknown.attributes() {
+            tag.attribute(key, Text::new_escaped(value))?;
+        }
+        tag.content(content)
+    })
+}
+
+pub(crate) fn encode_image<'b>(
+    image_data: &[u8],
+    base64_buf: &'b mut Vec<MaybeUninit<u8>>,
     image_buf: &mut Cow<'_, [u8]>,
-) -> fmt::Result {
-    writer.tag(tag, |tag| {
-        let image_data = image.data();
-        if image_data.is_empty() {
-            return Ok(());
+) -> &'b str {
+    let len = image_data.len();
+    let image_buf = image_buf.to_mut();
+    image_buf.truncate(LSS_IMAGE_HEADER.len());
+    image_buf.reserve(len + 6);
+    image_buf.extend((len as u32).to_le_bytes());
+    image_buf.push(0x2);
+    image_buf.extend(image_data);
+    image_buf.push(0xB);
+
+    base64_buf.resize(
+        base64_simd::STANDARD.encoded_length(image_buf.len()),
+        MaybeUninit::uninit(),
+    );
+
+    base64_simd::STANDARD.encode_as_str(image_buf, base64_simd::Out::from_uninit_slice(base64_buf))
+}
+
+struct ImageEncoder<'a> {
+    base64_buf: Vec<MaybeUninit<u8>>,
+    image_buf: Cow<'static, [u8]>,
+    cache: Option<&'a mut IconCache>,
+}
+
+impl<'a> ImageEncoder<'a> {
+    fn new(cache: Option<&'a mut IconCache>) -> Self {
+        Self {
+            base64_buf: Vec::new(),
+            image_buf: Cow::Borrowed(&LSS_IMAGE_HEADER[..]),
+            cache,
         }
+    }
+
+    fn image<W: fmt::Write>(
+        &mut self,
+        writer: &mut Writer<W>,
+        tag: &str,
+        image: &Image,
+    ) -> fmt::Result {
+        writer.tag(tag, |tag| {
+            let image_data = image.data();
+            if image_data.is_empty() {
+                return Ok(());
+            }
+
+            let encoded = match &mut self.cache {
+                Some(cache) => {
+                    cache.get_or_encode(image, &mut self.base64_buf, &mut self.image_buf)
+                }
+                None => encode_image(image_data, &mut self.base64_buf, &mut self.image_buf),
+            };
 
-        let len = image_data.len();
-        let image_buf = image_buf.to_mut();
-        image_buf.truncate(LSS_IMAGE_HEADER.len());
-        image_buf.reserve(len + 6);
-        image_buf.extend((len as u32).to_le_bytes());
-        image_buf.push(0x2);
-        image_buf.extend(image_data);
-        image_buf.push(0xB);
+            tag.content(|writer| writer.cdata(Text::new_escaped(encoded)))
+        })
+    }
+}
 
-        base64_buf.resize(
-            base64_simd::STANDARD.encoded_length(image_buf.len()),
-            MaybeUninit::uninit(),
-        );
+/// The base64 encoded icons of the last save, keyed by their image ID. Icons
+/// that are not used anymore get dropped on the next save.
+#[derive(Default)]
+struct IconCache {
+    current: HashMap<ImageId, String>,
+    previous: HashMap<ImageId, String>,
+}
 
-        let encoded = base64_simd::STANDARD
-            .encode_as_str(image_buf, base64_simd::Out::from_uninit_slice(base64_buf));
+impl IconCache {
+    fn start_save(&mut self) {
+        mem::swap(&mut self.current, &mut self.previous);
+        self.current.clear();
+    }
 
-        tag.content(|writer| writer.cdata(Text::new_escaped(encoded)))
-    })
+    fn get_or_encode(
+        &mut self,
+        image: &Image,
+        base64_buf: &mut Vec<MaybeUninit<u8>>,
+        image_buf: &mut Cow<'_, [u8]>,
+    ) -> &str {
+        let id = *image.id();
+        self.current.entry(id).or_insert_with(|| {
+            self.previous
+                .remove(&id)
+                .unwrap_or_else(|| encode_image(image.data(), base64_buf, image_buf).into())
+        })
+    }
+}
+
+/// The serialized elements of a list that mostly just gets new elements
+/// appended to it. On each save, only the elements after the longest common
+/// prefix with the list of the previous save get serialized again.
+struct HistoryCache<T> {
+    items: Vec<T>,
+    ends: Vec<usize>,
+    xml: String,
+}
+
+impl<T> Default for HistoryCache<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            ends: Vec::new(),
+            xml: String::new(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> HistoryCache<T> {
+    fn update<F>(&mut self, items: &[T], mut serialize: F) -> fmt::Result
+    where
+        F: FnMut(&mut Writer<String>, &T) -> fmt::Result,
+    {
+        let common = self
+            .items
+            .iter()
+            .zip(items)
+            .take_while(|(cached, item)| cached == item)
+            .count();
+
+        self.items.truncate(common);
+        self.ends.truncate(common);
+        self.xml
+            .truncate(self.ends.last().copied().unwrap_or_default());
+
+        let mut writer = Writer::new_skip_header(mem::take(&mut self.xml));
+        for item in &items[common..] {
+            if let Err(e) = serialize(&mut writer, item) {
+                *self = Self::default();
+                return Err(e);
+            }
+            self.items.push(item.clone());
+            self.ends.push(writer.get_ref().len());
+        }
+        self.xml = writer.into_inner();
+
+        Ok(())
+    }
+
+    fn write<W: fmt::Write>(&self, writer: &mut Writer<W>, tag: &str) -> fmt::Result {
+        writer.tag(tag, |tag| {
+            if !self.xml.is_empty() {
+                tag.content(|writer| writer.text(Text::new_escaped(&self.xml)))?;
+            }
+            Ok(())
+        })
+    }
 }
 
 fn date<W: fmt::Write>(
@@ -131,11 +275,19 @@ fn time_inner<W: fmt::Write>(writer: &mut Writer<W>, time: Time) -> fmt::Result
         )?;
     }
 
+    if let Some(time) = time.load_removed_time {
+        writer.tag_with_text_content(
+            "LoadRemovedTime",
+            NO_ATTRIBUTES,
+            DisplayAlreadyEscaped(Complete.format(time)),
+        )?;
+    }
+
     Ok(())
 }
 
 fn time<W: fmt::Write>(writer: AttributeWriter<'_, W>, time: Time) -> fmt::Result {
-    if time.real_time.is_some() || time.game_time.is_some() {
+    if time.real_time.is_some() || time.game_time.is_some() || time.load_removed_time.is_some() {
         writer.content(|writer| time_inner(writer, time))
     } else {
         Ok(())
@@ -154,6 +306,67 @@ impl<W: std::io::Write> fmt::Write for IoWrite<W> {
     }
 }
 
+fn attempt<W: fmt::Write>(writer: &mut Writer<W>, attempt: &Attempt) -> fmt::Result {
+    writer.tag("Attempt", |mut tag| {
+        tag.attribute("id", DisplayAlreadyEscaped(attempt.index()))?;
+
+        if let Some(started) = attempt.started() {
+            date(&mut tag, "started", started.time)?;
+            tag.attribute("isStartedSynced", bool(started.synced_with_atomic_clock))?;
+        }
+        if let Some(ended) = attempt.ended() {
+            date(&mut tag, "ended", ended.time)?;
+            tag.attribute("isEndedSynced", bool(ended.synced_with_atomic_clock))?;
+        }
+
+        let is_empty = attempt.time().real_time.is_none()
+            && attempt.time().game_time.is_none()
+            && attempt.time().load_removed_time.is_none()
+            && attempt.pause_time().is_none()
+            && attempt.pauses().is_empty();
+
+        if !is_empty {
+            tag.content(|writer| {
+                time_inner(writer, attempt.time())?;
+
+                if let Some(pause_time) = attempt.pause_time() {
+                    writer.tag_with_text_content(
+                        "PauseTime",
+                        NO_ATTRIBUTES,
+                        DisplayAlreadyEscaped(Complete.format(pause_time)),
+                    )?;
+                }
+
+                if !attempt.pauses().is_empty() {
+                    scoped_iter(writer, "Pauses", attempt.pauses(), |writer, pause| {
+                        writer.empty_tag(
+                            "Pause",
+                            [
+                                ("start", DisplayAlreadyEscaped(Complete.format(pause.start))),
+                                ("end", DisplayAlreadyEscaped(Complete.format(pause.end))),
+                            ],
+                        )
+                    })?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(())
+    })
+}
+
+fn segment_history_element<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    &(index, history_time): &(i32, Time),
+) -> fmt::Result {
+    writer.tag("Time", |mut tag| {
+        tag.attribute("id", DisplayAlreadyEscaped(index))?;
+        time(tag, history_time)
+    })
+}
+
 /// Saves the Run in use by the Timer provided as a LiveSplit splits file
 /// (*.lss).
 pub fn save_timer<W: fmt::Write>(timer: &Timer, writer: W) -> fmt::Result {
@@ -169,18 +382,110 @@ pub fn save_timer<W: fmt::Write>(timer: &Timer, writer: W) -> fmt::Result {
 /// function if the Run is in use by a timer in order to properly save the
 /// current attempt as well.
 pub fn save_run<W: fmt::Write>(run: &Run, writer: W) -> fmt::Result {
+    save(run, writer, None)
+}
+
+/// An incremental LiveSplit Saver that keeps the serialized Attempt History,
+/// Segment Histories and icons of the previous save around. Saving the same Run
+/// again only serializes the parts that changed since then, which is usually
+/// just the most recent attempt. This is meant for saving the same Run
+/// repeatedly, such as for autosaving after every attempt. The output is
+/// exactly the same as the one of `save_run`.
+///
+/// # Examples
+///
+/// ```
+/// use livesplit_core::run::saver::livesplit::{self, IncrementalSaver};
+/// use livesplit_core::{Run, Segment, Time};
+///
+/// let mut run = Run::new();
+/// run.push_segment(Segment::new("Cap Kingdom"));
+///
+/// let mut saver = IncrementalSaver::new();
+/// let mut first = String::new();
+/// saver.save_run(&run, &mut first).expect("Couldn't save the splits file");
+///
+/// run.add_attempt_with_index(Time::new(), 1, None, None, None);
+///
+/// // Only the new attempt gets serialized.
+/// let mut second = String::new();
+/// saver.save_run(&run, &mut second).expect("Couldn't save the splits file");
+///
+/// let mut full = String::new();
+/// livesplit::save_run(&run, &mut full).expect("Couldn't save the splits file");
+/// assert_eq!(second, full);
+/// ```
+#[derive(Default)]
+pub struct IncrementalSaver {
+    icons: IconCache,
+    attempt_history: HistoryCache<Attempt>,
+    segment_histories: Vec<HistoryCache<(i32, Time)>>,
+}
+
+impl IncrementalSaver {
+    /// Creates a new incremental saver with nothing cached yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saves the Run in use by the Timer provided as a LiveSplit splits file
+    /// (*.lss), reusing everything that didn't change since the last save.
+    pub fn save_timer<W: fmt::Write>(&mut self, timer: &Timer, writer: W) -> fmt::Result {
+        let run = if timer.current_phase() == TimerPhase::NotRunning {
+            timer.run()
+        } else {
+            &timer.clone().into_run(true)
+        };
+        self.save_run(run, writer)
+    }
+
+    /// Saves a Run as a LiveSplit splits file (*.lss), reusing everything that
+    /// didn't change since the last save. Use the `save_timer` method if the
+    /// Run is in use by a timer in order to properly save the current attempt
+    /// as well.
+    pub fn save_run<W: fmt::Write>(&mut self, run: &Run, writer: W) -> fmt::Result {
+        save(run, writer, Some(self))
+    }
+
+    /// Drops everything that is cached, so that the next save serializes the
+    /// whole Run again.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn save<W: fmt::Write>(
+    run: &Run,
+    writer: W,
+    mut cache: Option<&mut IncrementalSaver>,
+) -> fmt::Result {
     let writer = &mut Writer::new_with_default_header(writer)?;
 
-    let base64_buf = &mut Vec::new();
-    let image_buf = &mut Cow::Borrowed(&LSS_IMAGE_HEADER[..]);
+    let (icons, mut histories) = match &mut cache {
+        Some(cache) => {
+            cache.icons.start_save();
+            cache
+                .segment_histories
+                .resize_with(run.len(), Default::default);
+            (
+                Some(&mut cache.icons),
+                Some((&mut cache.attempt_history, &mut cache.segment_histories)),
+            )
+        }
+        None => (None, None),
+    };
+    let images = &mut ImageEncoder::new(icons);
 
-    writer.tag_with_content("Run", [("version", Text::new_escaped("1.8.0"))], |writer| {
-        image(writer, "GameIcon", run.game_icon(), base64_buf, image_buf)?;
+    let version = [("version", Text::new_escaped("1.8.0"))];
+    let unknown = run.unknown_elements();
+    tag_with_unknown_attributes(writer, "Run", version, unknown, |writer| {
+        images.image(writer, "GameIcon", run.game_icon())?;
         writer.tag_with_text_content("GameName", NO_ATTRIBUTES, run.game_name())?;
         writer.tag_with_text_content("CategoryName", NO_ATTRIBUTES, run.category_name())?;
 
-        writer.tag_with_content("Metadata", NO_ATTRIBUTES, |writer| {
-            let metadata = run.metadata();
+        let metadata = run.metadata();
+        let unknown = metadata.unknown_elements();
+        tag_with_unknown_attributes(writer, "Metadata", [], unknown, |writer| {
             writer.empty_tag("Run", [("id", metadata.run_id())])?;
             writer.tag_with_text_content(
                 "Platform",
@@ -205,7 +510,8 @@ pub fn save_run<W: fmt::Write>(run: &Run, writer: W) -> fmt::Result {
                 |writer, (name, var)| {
                     writer.tag_with_text_content("Variable", [("name", name)], var.value.as_str())
                 },
-            )
+            )?;
+            writer.text(Text::new_escaped(unknown.xml()))
         })?;
 
         writer.tag_with_text_content(
@@ -229,87 +535,62 @@ pub fn save_run<W: fmt::Write>(run: &Run, writer: W) -> fmt::Result {
             DisplayAlreadyEscaped(run.attempt_count()),
         )?;
 
+        if let Some((cache, _)) = &mut histories {
+            cache.update(run.attempt_history(), attempt)?;
+            cache.write(writer, "AttemptHistory")?;
+        } else {
+            scoped_iter(writer, "AttemptHistory", run.attempt_history(), attempt)?;
+        }
+
         scoped_iter(
             writer,
-            "AttemptHistory",
-            run.attempt_history(),
-            |writer, attempt| {
-                writer.tag("Attempt", |mut tag| {
-                    tag.attribute("id", DisplayAlreadyEscaped(attempt.index()))?;
-
-                    if let Some(started) = attempt.started() {
-                        date(&mut tag, "started", started.time)?;
-                        tag.attribute("isStartedSynced", bool(started.synced_with_atomic_clock))?;
-                    }
-                    if let Some(ended) = attempt.ended() {
-                        date(&mut tag, "ended", ended.time)?;
-                        tag.attribute("isEndedSynced", bool(ended.synced_with_atomic_clock))?;
+            "Segments",
+            run.segments().iter().enumerate(),
+            |writer, (segment_index, segment)| {
+                let unknown = segment.unknown_elements();
+                tag_with_unknown_attributes(writer, "Segment", [], unknown, |writer| {
+                    writer.tag_with_text_content("Name", NO_ATTRIBUTES, segment.name())?;
+                    images.image(writer, "Icon", segment.icon())?;
+                    if !segment.runner().is_empty() {
+                        writer.tag_with_text_content("Runner", NO_ATTRIBUTES, segment.runner())?;
                     }
 
-                    let is_empty = attempt.time().real_time.is_none()
-                        && attempt.time().game_time.is_none()
-                        && attempt.pause_time().is_none();
-
-                    if !is_empty {
-                        tag.content(|writer| {
-                            time_inner(writer, attempt.time())?;
-
-                            if let Some(pause_time) = attempt.pause_time() {
-                                writer.tag_with_text_content(
-                                    "PauseTime",
-                                    NO_ATTRIBUTES,
-                                    DisplayAlreadyEscaped(Complete.format(pause_time)),
-                                )?;
-                            }
-
-                            Ok(())
-                        })?;
+                    scoped_iter(
+                        writer,
+                        "SplitTimes",
+                        run.custom_comparisons(),
+                        |writer, comparison| {
+                            writer.tag("SplitTime", |mut tag| {
+                                tag.attribute("name", comparison.as_str())?;
+                                time(tag, segment.comparison(comparison))
+                            })
+                        },
+                    )?;
+
+                    writer.tag("BestSegmentTime", |tag| {
+                        time(tag, segment.best_segment_time())
+                    })?;
+
+                    let history = segment.segment_history().iter();
+                    if let Some((_, caches)) = &mut histories {
+                        let cache = &mut caches[segment_index];
+                        cache.update(history.as_slice(), segment_history_element)?;
+                        cache.write(writer, "SegmentHistory")?;
+                    } else {
+                        scoped_iter(writer, "SegmentHistory", history, segment_history_element)?;
                     }
 
-                    Ok(())
+                    writer.text(Text::new_escaped(segment.unknown_elements().xml()))
                 })
             },
         )?;
 
-        scoped_iter(writer, "Segments", run.segments(), |writer, segment| {
-            writer.tag_with_content("Segment", NO_ATTRIBUTES, |writer| {
-                writer.tag_with_text_content("Name", NO_ATTRIBUTES, segment.name())?;
-                image(writer, "Icon", segment.icon(), base64_buf, image_buf)?;
-
-                scoped_iter(
-                    writer,
-                    "SplitTimes",
-                    run.custom_comparisons(),
-                    |writer, comparison| {
-                        writer.tag("SplitTime", |mut tag| {
-                            tag.attribute("name", comparison.as_str())?;
-                            time(tag, segment.comparison(comparison))
-                        })
-                    },
-                )?;
-
-                writer.tag("BestSegmentTime", |tag| {
-                    time(tag, segment.best_segment_time())
-                })?;
-
-                scoped_iter(
-                    writer,
-                    "SegmentHistory",
-                    segment.segment_history(),
-                    |writer, &(index, history_time)| {
-                        writer.tag("Time", |mut tag| {
-                            tag.attribute("id", DisplayAlreadyEscaped(index))?;
-                            time(tag, history_time)
-                        })
-                    },
-                )
-            })
-        })?;
-
         writer.tag_with_text_content(
             "AutoSplitterSettings",
             NO_ATTRIBUTES,
             Text::new_escaped(run.auto_splitter_settings()),
-        )
+        )?;
+
+        writer.text(Text::new_escaped(run.unknown_elements().xml()))
     })
 }
@@ -1,5 +1,7 @@
 //! The saver module provides all the different ways to save Runs as splits
-//! files.
+//! files. The LiveSplit Saver is the only one that stores all the information a
+//! Run holds. All the other savers only store what the respective splits file
+//! format is able to represent.
 //!
 //! # Examples
 //!
@@ -25,4 +27,49 @@
 //! livesplit::save_run(&run, IoWrite(writer)).expect("Couldn't save the splits file");
 //! ```
 
+pub mod csv;
+pub mod flitter;
 pub mod livesplit;
+pub mod splits_io;
+pub mod splitterino;
+pub mod urn;
+pub mod wsplit;
+
+use crate::{TimeSpan, platform::prelude::*};
+use core::fmt::Write;
+
+/// Formats a time the way most timers other than LiveSplit store them. There
+/// is no days component, so the hours keep on growing beyond 24. Leading zero
+/// hours and minutes are omitted. The fractional part is truncated to the
+/// amount of digits provided.
+fn format_time(time: TimeSpan, fraction_digits: u32) -> String {
+    let (total_seconds, nanoseconds) = time.to_seconds_and_subsec_nanoseconds();
+    let mut buf = String::new();
+    let (total_seconds, nanoseconds) = if (total_seconds | nanoseconds as i64) < 0 {
+        buf.push('-');
+        (total_seconds.unsigned_abs(), nanoseconds.unsigned_abs())
+    } else {
+        (total_seconds as u64, nanoseconds as u32)
+    };
+
+    let (hours, minutes, seconds) = (
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+    );
+
+    let _ = if hours > 0 {
+        write!(buf, "{hours}:{minutes:02}:{seconds:02}")
+    } else if minutes > 0 {
+        write!(buf, "{minutes}:{seconds:02}")
+    } else {
+        write!(buf, "{seconds}")
+    };
+
+    if fraction_digits > 0 {
+        let fraction = nanoseconds / 10_u32.pow(9 - fraction_digits);
+        let _ = write!(buf, ".{fraction:0width$}", width = fraction_digits as usize);
+    }
+
+    buf
+}
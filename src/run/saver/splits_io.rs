@@ -0,0 +1,179 @@
+//! The splits.io Exchange Format Saver saves Runs in the splits.io Exchange
+//! Format. This is a generic JSON based splits file format that many tools use
+//! for exchanging splits.
+//!
+//! The Attempt History, the Segment Histories, the Personal Best and the Best
+//! Segments are stored for all the timing methods. The format only specifies
+//! fields for the Real Time and the Game Time, so the Load Removed Time is
+//! stored in an additional `loadRemovedTimeMS` field that other tools ignore.
+//! The pause times of the attempts and all the comparisons other than the
+//! Personal Best are lost.
+
+use crate::{
+    AtomicDateTime, Run, Time, TimingMethod, platform::prelude::*,
+    run::parser::splits_io::RUNNER_VARIABLE,
+};
+use core::fmt;
+use serde_derive::Serialize;
+use time::UtcOffset;
+
+#[derive(Serialize)]
+struct Splits<'a> {
+    #[serde(rename = "_schemaVersion")]
+    schema_version: &'static str,
+    timer: Timer,
+    game: Named<'a>,
+    category: Named<'a>,
+    runners: Vec<Named<'a>>,
+    attempts: Attempts,
+    segments: Vec<SplitsIoSegment<'a>>,
+}
+
+#[derive(Serialize)]
+struct Timer {
+    shortname: &'static str,
+    longname: &'static str,
+    version: &'static str,
+    website: &'static str,
+}
+
+#[derive(Serialize)]
+struct Named<'a> {
+    longname: &'a str,
+}
+
+#[derive(Serialize)]
+struct Attempts {
+    total: u32,
+    histories: Vec<AttemptHistory>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AttemptHistory {
+    attempt_number: i32,
+    #[serde(flatten)]
+    duration: Duration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ended_at: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitsIoSegment<'a> {
+    name: &'a str,
+    ended_at: Duration,
+    best_duration: Duration,
+    is_skipped: bool,
+    histories: Vec<SegmentHistory>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentHistory {
+    attempt_number: i32,
+    #[serde(flatten)]
+    duration: Duration,
+    is_skipped: bool,
+}
+
+#[derive(Serialize)]
+struct Duration {
+    #[serde(rename = "realtimeMS", skip_serializing_if = "Option::is_none")]
+    realtime_ms: Option<f64>,
+    #[serde(rename = "gametimeMS", skip_serializing_if = "Option::is_none")]
+    gametime_ms: Option<f64>,
+    #[serde(rename = "loadRemovedTimeMS", skip_serializing_if = "Option::is_none")]
+    load_removed_time_ms: Option<f64>,
+}
+
+fn duration(time: Time) -> Duration {
+    Duration {
+        realtime_ms: time.real_time.map(|t| t.total_milliseconds()),
+        gametime_ms: time.game_time.map(|t| t.total_milliseconds()),
+        load_removed_time_ms: time.load_removed_time.map(|t| t.total_milliseconds()),
+    }
+}
+
+fn is_skipped(time: Time) -> bool {
+    TimingMethod::all()
+        .into_iter()
+        .all(|method| time[method].is_none())
+}
+
+fn date_time(date_time: Option<AtomicDateTime>) -> Option<String> {
+    let date_time = date_time?.time.to_offset(UtcOffset::UTC);
+    let (year, month, day) = date_time.to_calendar_date();
+    let month = month as u8;
+    let (hour, minute, second, millisecond) = date_time.to_hms_milli();
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{millisecond:03}Z"
+    ))
+}
+
+/// Saves a Run in the splits.io Exchange Format.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    let splits = Splits {
+        schema_version: "v1.0.1",
+        timer: Timer {
+            shortname: "livesplit-core",
+            longname: "livesplit-core",
+            version: env!("CARGO_PKG_VERSION"),
+            website: "https://github.com/LiveSplit/livesplit-core",
+        },
+        game: Named {
+            longname: run.game_name(),
+        },
+        category: Named {
+            longname: run.category_name(),
+        },
+        runners: run
+            .metadata()
+            .custom_variable_value(RUNNER_VARIABLE)
+            .into_iter()
+            .flat_map(|runners| runners.split(", "))
+            .filter(|runner| !runner.is_empty())
+            .map(|longname| Named { longname })
+            .collect(),
+        attempts: Attempts {
+            total: run.attempt_count(),
+            histories: run
+                .attempt_history()
+                .iter()
+                .map(|attempt| AttemptHistory {
+                    attempt_number: attempt.index(),
+                    duration: duration(attempt.time()),
+                    started_at: date_time(attempt.started()),
+                    ended_at: date_time(attempt.ended()),
+                })
+                .collect(),
+        },
+        segments: run
+            .segments()
+            .iter()
+            .map(|segment| {
+                let split_time = segment.personal_best_split_time();
+                SplitsIoSegment {
+                    name: segment.name(),
+                    ended_at: duration(split_time),
+                    best_duration: duration(segment.best_segment_time()),
+                    is_skipped: is_skipped(split_time),
+                    histories: segment
+                        .segment_history()
+                        .iter()
+                        .map(|&(attempt_number, time)| SegmentHistory {
+                            attempt_number,
+                            duration: duration(time),
+                            is_skipped: is_skipped(time),
+                        })
+                        .collect(),
+                }
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&splits).map_err(|_| fmt::Error)?;
+    writer.write_str(&json)
+}
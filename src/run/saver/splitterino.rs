@@ -0,0 +1,131 @@
+//! The Splitterino Saver saves Runs as Splitterino splits files.
+//!
+//! Splitterino stores times with millisecond precision. The Attempt History,
+//! the Segment Histories and all the comparisons other than the Personal Best
+//! can't be stored.
+
+use crate::{Run, Time, TimeSpan, platform::prelude::*};
+use core::fmt;
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+struct SplitsFormat<'a> {
+    version: &'static str,
+    splits: Splits<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Splits<'a> {
+    game: GameInfo<'a>,
+    start_delay: i64,
+    segments: Vec<SplitterinoSegment<'a>>,
+    timing: &'static str,
+}
+
+#[derive(Serialize)]
+struct GameInfo<'a> {
+    name: &'a str,
+    category: &'a str,
+    platform: &'a str,
+    region: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitterinoSegment<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    personal_best: Option<SegmentTime>,
+    overall_best: SegmentTime,
+    skipped: bool,
+}
+
+#[derive(Serialize)]
+struct SegmentTime {
+    igt: DetailedTime,
+    rta: DetailedTime,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DetailedTime {
+    raw_time: u64,
+    pause_time: u64,
+}
+
+const fn milliseconds(time: TimeSpan) -> i64 {
+    let (seconds, nanoseconds) = time.to_seconds_and_subsec_nanoseconds();
+    // We round to the closest millisecond, as the times may have been parsed
+    // from milliseconds stored as floating point numbers.
+    1000 * seconds + (nanoseconds as i64 + 500_000).div_euclid(1_000_000)
+}
+
+fn detailed_time(time: Option<TimeSpan>) -> DetailedTime {
+    DetailedTime {
+        // Empty Time is stored as zero
+        raw_time: time.map_or(0, |t| milliseconds(t).max(0) as u64),
+        pause_time: 0,
+    }
+}
+
+fn segment_time(time: Time) -> SegmentTime {
+    SegmentTime {
+        igt: detailed_time(time.game_time),
+        rta: detailed_time(time.real_time),
+    }
+}
+
+/// Saves a Run as a Splitterino splits file.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    let metadata = run.metadata();
+
+    let mut previous_split_time = Time::zero();
+
+    let splits = SplitsFormat {
+        version: "0.1",
+        splits: Splits {
+            game: GameInfo {
+                name: run.game_name(),
+                category: run.category_name(),
+                platform: metadata.platform_name(),
+                region: metadata.region_name(),
+            },
+            start_delay: milliseconds(-run.offset()),
+            segments: run
+                .segments()
+                .iter()
+                .map(|segment| {
+                    let split_time = segment.personal_best_split_time();
+                    let skipped = split_time.real_time.is_none() && split_time.game_time.is_none();
+
+                    let personal_best = if skipped {
+                        None
+                    } else {
+                        // Splitterino stores segment times rather than split
+                        // times.
+                        let time = split_time - previous_split_time;
+                        if split_time.real_time.is_some() {
+                            previous_split_time.real_time = split_time.real_time;
+                        }
+                        if split_time.game_time.is_some() {
+                            previous_split_time.game_time = split_time.game_time;
+                        }
+                        Some(segment_time(time))
+                    };
+
+                    SplitterinoSegment {
+                        name: segment.name(),
+                        personal_best,
+                        overall_best: segment_time(segment.best_segment_time()),
+                        skipped,
+                    }
+                })
+                .collect(),
+            timing: "rta",
+        },
+    };
+
+    let json = serde_json::to_string_pretty(&splits).map_err(|_| fmt::Error)?;
+    writer.write_str(&json)
+}
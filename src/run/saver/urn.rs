@@ -0,0 +1,64 @@
+//! The Urn Saver saves Runs as Urn splits files.
+//!
+//! Urn only supports Real Time, so all the Game Times are lost. The Attempt
+//! History and the Segment Histories can't be stored either. The only history
+//! information that is kept are the Best Split Times. Urn only stores a title,
+//! which is used for the category name, so the game name is lost as well.
+
+use super::format_time;
+use crate::{
+    Run, TimeSpan,
+    comparison::{ComparisonGenerator, best_split_times},
+    platform::prelude::*,
+};
+use core::fmt;
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+struct Splits<'a> {
+    title: &'a str,
+    attempt_count: u32,
+    start_delay: String,
+    splits: Vec<Split<'a>>,
+}
+
+#[derive(Serialize)]
+struct Split<'a> {
+    title: &'a str,
+    time: String,
+    best_time: String,
+    best_segment: String,
+}
+
+fn time(time: Option<TimeSpan>) -> String {
+    // Empty Time is stored as zero
+    format_time(time.unwrap_or_default(), 6)
+}
+
+/// Saves a Run as an Urn splits file.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    // The Best Split Times are not necessarily up to date, so we generate them
+    // on a copy of the segments.
+    let mut segments = run.segments().to_vec();
+    best_split_times::BestSplitTimes.generate(&mut segments, run.attempt_history());
+
+    let splits = Splits {
+        title: run.category_name(),
+        attempt_count: run.attempt_count(),
+        start_delay: time(Some(-run.offset())),
+        splits: run
+            .segments()
+            .iter()
+            .zip(&segments)
+            .map(|(segment, generated)| Split {
+                title: segment.name(),
+                time: time(segment.personal_best_split_time().real_time),
+                best_time: time(generated.comparison(best_split_times::NAME).real_time),
+                best_segment: time(segment.best_segment_time().real_time),
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&splits).map_err(|_| fmt::Error)?;
+    writer.write_str(&json)
+}
@@ -0,0 +1,41 @@
+//! The WSplit Saver saves Runs as WSplit splits files.
+//!
+//! WSplit only supports Real Time, so all the Game Times are lost. Besides the
+//! Personal Best and the Best Segments, only the `Old Run` comparison is
+//! stored. Segment icons are not stored, as WSplit refers to them by their
+//! path on the file system. WSplit only stores a title, which is used for the
+//! category name, so the game name is lost as well. The segments are stored as
+//! comma separated values, so any commas in their names are replaced by a
+//! similar looking `‚` character.
+
+use crate::{Run, TimeSpan};
+use core::fmt;
+
+fn seconds(time: Option<TimeSpan>) -> f64 {
+    // Empty Time is stored as zero
+    time.map_or(0.0, |t| t.total_seconds())
+}
+
+/// Saves a Run as a WSplit splits file.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    writeln!(writer, "Title={}", run.category_name())?;
+    writeln!(writer, "Attempts={}", run.attempt_count())?;
+    writeln!(writer, "Offset={}", (-run.offset()).total_milliseconds())?;
+
+    if let Some(goal) = run.metadata().custom_variable_value("Goal") {
+        writeln!(writer, "Goal={goal}")?;
+    }
+
+    for segment in run.segments() {
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            segment.name().replace(',', "‚"),
+            seconds(segment.comparison("Old Run").real_time),
+            seconds(segment.personal_best_split_time().real_time),
+            seconds(segment.best_segment_time().real_time),
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use hashbrown::HashMap;
 
-use super::Comparisons;
+use super::{Comparisons, UnknownElements};
 use crate::{
     SegmentHistory, Time, TimeSpan, TimingMethod, comparison::personal_best, platform::prelude::*,
     settings::Image, util::PopulateString,
@@ -24,11 +24,13 @@ use crate::{
 pub struct Segment {
     name: String,
     icon: Image,
+    runner: String,
     best_segment_time: Time,
     split_time: Time,
     segment_history: SegmentHistory,
     comparisons: Comparisons,
     variables: HashMap<String, String>,
+    unknown_elements: UnknownElements,
 }
 
 impl Segment {
@@ -71,6 +73,28 @@ impl Segment {
         self.icon = image;
     }
 
+    /// Accesses the name of the runner the segment belongs to. This is used
+    /// for relay and team runs, where the segments are run by different
+    /// runners. It is empty if the segment doesn't belong to any runner in
+    /// particular.
+    #[inline]
+    #[allow(clippy::missing_const_for_fn)] // FIXME: Can't reason about Deref
+    pub fn runner(&self) -> &str {
+        &self.runner
+    }
+
+    /// Sets the name of the runner the segment belongs to. This is used for
+    /// relay and team runs, where the segments are run by different runners.
+    /// An empty name means that the segment doesn't belong to any runner in
+    /// particular.
+    #[inline]
+    pub fn set_runner<S>(&mut self, runner: S)
+    where
+        S: PopulateString,
+    {
+        runner.populate(&mut self.runner);
+    }
+
     /// Grants mutable access to the comparison times stored in the Segment.
     /// This includes both the custom comparisons and the generated ones.
     #[inline]
@@ -197,6 +221,21 @@ impl Segment {
         self.variables.clear();
     }
 
+    /// Accesses the elements of the segment in the splits file that
+    /// livesplit-core doesn't know about. They are preserved, so they can be
+    /// written back out when saving the splits file.
+    #[inline]
+    pub const fn unknown_elements(&self) -> &UnknownElements {
+        &self.unknown_elements
+    }
+
+    /// Grants mutable access to the elements of the segment in the splits
+    /// file that livesplit-core doesn't know about.
+    #[inline]
+    pub const fn unknown_elements_mut(&mut self) -> &mut UnknownElements {
+        &mut self.unknown_elements
+    }
+
     /// Clears all the information the segment stores when it has been splitted,
     /// such as the split's time and variables.
     pub fn clear_split_info(&mut self) {
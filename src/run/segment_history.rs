@@ -1,4 +1,4 @@
-use crate::{platform::prelude::*, Time};
+use crate::{platform::prelude::*, Segment, Time};
 use core::{
     cmp::min,
     slice::{Iter, IterMut},
@@ -119,6 +119,41 @@ impl SegmentHistory {
     }
 }
 
+/// Keeps track of the changes to the Segment Histories of a
+/// [`Run`](super::Run), so they can be reverted without having to clone the
+/// Segment Histories beforehand. Each change consists of the index of the
+/// segment, the index of the segment time and the segment time it replaced, if
+/// there was one.
+#[derive(Clone, Default, Debug)]
+pub(crate) struct HistoryChanges(Vec<(usize, i32, Option<Time>)>);
+
+impl HistoryChanges {
+    /// Records that the segment time with the index provided is about to be
+    /// changed in the Segment History of the segment provided.
+    pub(crate) fn record(&mut self, segment: usize, index: i32, previous: Option<Time>) {
+        self.0.push((segment, index, previous));
+    }
+
+    /// Reverts the changes in the reverse order they happened in and returns
+    /// the changes that redo them.
+    pub(crate) fn revert(self, segments: &mut [Segment]) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .rev()
+                .map(|(segment, index, time)| {
+                    let history = segments[segment].segment_history_mut();
+                    let previous = history.remove(index);
+                    if let Some(time) = time {
+                        history.insert(index, time);
+                    }
+                    (segment, index, previous)
+                })
+                .collect(),
+        )
+    }
+}
+
 impl<'a> IntoIterator for &'a SegmentHistory {
     type Item = &'a (i32, Time);
     type IntoIter = Iter<'a, (i32, Time)>;
@@ -0,0 +1,196 @@
+use crate::{
+    Run, Segment, Time,
+    run::{
+        LinkedLayout,
+        diff::{
+            Change, ComparisonChange, ComparisonListChange, MetadataChange, SegmentChange,
+            VariableChange, VariableKind, diff,
+        },
+    },
+    util::tests_helper::{create_timer, run_with_splits, span},
+};
+
+fn run(names: &[&str]) -> Run {
+    let mut run = Run::new();
+    for &name in names {
+        run.push_segment(Segment::new(name));
+    }
+    run
+}
+
+#[test]
+fn identical_runs_have_no_differences() {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[1.0, 2.0]);
+    let run = timer.into_run(true);
+
+    let diff = diff(&run, &run);
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn aligns_added_removed_and_renamed_segments() {
+    let old = run(&["A", "B", "C", "D", "E"]);
+    let new = run(&["A", "X", "C", "E", "F"]);
+
+    assert_eq!(
+        diff(&old, &new).segments,
+        [
+            SegmentChange::Modified {
+                old_index: 1,
+                new_index: 1,
+                name: Some(Change {
+                    old: "B".into(),
+                    new: "X".into(),
+                }),
+                comparisons: Vec::new(),
+                best_segment_time: None,
+            },
+            SegmentChange::Removed {
+                index: 3,
+                name: "D".into(),
+            },
+            SegmentChange::Added {
+                index: 4,
+                name: "F".into(),
+            },
+        ],
+    );
+}
+
+#[test]
+fn reports_moved_segments_as_removed_and_added() {
+    let old = run(&["A", "B", "C"]);
+    let new = run(&["B", "C", "A"]);
+
+    let diff = diff(&old, &new);
+    assert_eq!(
+        diff.to_string(),
+        "- Segment 1 \"A\"\n\
+         + Segment 3 \"A\"\n",
+    );
+}
+
+#[test]
+fn reports_changed_times() {
+    let mut old = run(&["A", "B"]);
+    for (segment, time) in old.segments_mut().iter_mut().zip([3.0, 6.0]) {
+        let time = Time::new().with_game_time(Some(span(time)));
+        segment.set_personal_best_split_time(time);
+        segment.set_best_segment_time(Time::new().with_game_time(Some(span(3.0))));
+    }
+    old.add_attempt_with_index(Time::new(), 1, None, None, None);
+    old.set_attempt_count(1);
+
+    let mut new = old.clone();
+    new.segment_mut(1)
+        .set_personal_best_split_time(Time::new().with_game_time(Some(span(5.5))));
+    new.segment_mut(1)
+        .set_best_segment_time(Time::new().with_game_time(Some(span(2.5))));
+    new.add_attempt_with_index(
+        Time::new().with_game_time(Some(span(5.5))),
+        2,
+        None,
+        None,
+        None,
+    );
+    new.set_attempt_count(2);
+
+    let diff = diff(&old, &new);
+    assert_eq!(
+        diff.metadata,
+        [MetadataChange::AttemptCount(Change { old: 1, new: 2 })],
+    );
+    assert_eq!(diff.new_attempts, &new.attempt_history()[1..]);
+    assert_eq!(
+        diff.segments,
+        [SegmentChange::Modified {
+            old_index: 1,
+            new_index: 1,
+            name: None,
+            comparisons: vec![ComparisonChange {
+                comparison: "Personal Best".into(),
+                old: Time::new().with_game_time(Some(span(6.0))),
+                new: Time::new().with_game_time(Some(span(5.5))),
+            }],
+            best_segment_time: Some(Change {
+                old: Time::new().with_game_time(Some(span(3.0))),
+                new: Time::new().with_game_time(Some(span(2.5))),
+            }),
+        }],
+    );
+
+    assert_eq!(
+        diff.to_string(),
+        "Attempts: 1 -> 2\n\
+         ~ Segment 2\n    \
+             Personal Best (Game Time): 0:06.00 -> 0:05.50\n    \
+             Best Segment (Game Time): 0:03.00 -> 0:02.50\n\
+         + Attempt 2 (Real Time: —, Game Time: 0:05.50)\n",
+    );
+}
+
+#[test]
+fn reports_metadata_variables_and_comparisons() {
+    let mut old = run(&["A"]);
+    old.add_custom_comparison("Old").unwrap();
+    old.metadata_mut().set_speedrun_com_variable("Amiibo", "No");
+    old.metadata_mut()
+        .custom_variable_mut("Route")
+        .permanent()
+        .set_value("A");
+    old.metadata_mut()
+        .custom_variable_mut("Temporary")
+        .set_value("A");
+
+    let mut new = old.clone();
+    new.custom_comparisons_mut().retain(|c| c != "Old");
+    new.add_custom_comparison("New").unwrap();
+    new.set_game_name("Game");
+    new.set_linked_layout(Some(LinkedLayout::Path("layout.ls1l".into())));
+    new.metadata_mut().set_emulator_usage(true);
+    new.metadata_mut().remove_speedrun_com_variable("Amiibo");
+    new.metadata_mut()
+        .custom_variable_mut("Route")
+        .set_value("B");
+    new.metadata_mut()
+        .custom_variable_mut("Temporary")
+        .set_value("B");
+
+    let diff = diff(&old, &new);
+    assert_eq!(
+        diff.variables,
+        [
+            VariableChange {
+                kind: VariableKind::SpeedrunCom,
+                name: "Amiibo".into(),
+                old: Some("No".into()),
+                new: None,
+            },
+            VariableChange {
+                kind: VariableKind::Custom,
+                name: "Route".into(),
+                old: Some("A".into()),
+                new: Some("B".into()),
+            },
+        ],
+    );
+    assert_eq!(
+        diff.comparisons,
+        [
+            ComparisonListChange::Removed("Old".into()),
+            ComparisonListChange::Added("New".into()),
+        ],
+    );
+    assert_eq!(
+        diff.to_string(),
+        "Game Name: \"\" -> \"Game\"\n\
+         Uses Emulator: false -> true\n\
+         Linked Layout: — -> \"layout.ls1l\"\n\
+         Speedrun.com Variable \"Amiibo\": \"No\" -> —\n\
+         Custom Variable \"Route\": \"A\" -> \"B\"\n\
+         - Comparison \"Old\"\n\
+         + Comparison \"New\"\n",
+    );
+}
@@ -0,0 +1,246 @@
+use crate::{
+    AtomicDateTime, DateTime, Run, Segment, Time, TimingMethod,
+    run::{Attempt, MergeError},
+    util::tests_helper::{create_timer, run_with_splits, span},
+};
+
+fn run_from(splits: &[&[f64]]) -> Run {
+    let mut timer = create_timer(&["A", "B"]);
+    for splits in splits {
+        run_with_splits(&mut timer, splits);
+    }
+    timer.into_run(true)
+}
+
+fn set_started(run: &mut Run, seconds: &[i64]) {
+    let attempts = run.attempt_history().to_vec();
+    run.attempt_history.clear();
+    for (attempt, &seconds) in attempts.iter().zip(seconds) {
+        let started = AtomicDateTime::new(DateTime::from_unix_timestamp(seconds).unwrap(), true);
+        run.add_attempt_with_index(
+            attempt.time(),
+            attempt.index(),
+            Some(started),
+            attempt.ended(),
+            attempt.pause_time(),
+        );
+    }
+}
+
+#[test]
+fn unions_the_histories_in_chronological_order() {
+    let mut run = run_from(&[&[3.0, 6.0], &[2.5, 5.5]]);
+    set_started(&mut run, &[100, 300]);
+    let mut other = run_from(&[&[2.0, 7.0]]);
+    set_started(&mut other, &[200]);
+
+    run.merge(&other, TimingMethod::GameTime).unwrap();
+
+    let indices = run
+        .attempt_history()
+        .iter()
+        .map(|a| a.index())
+        .collect::<Vec<_>>();
+    assert_eq!(indices, [1, 2, 3]);
+    assert_eq!(run.attempt_history()[1].time().game_time, Some(span(7.0)));
+    assert_eq!(run.attempt_count(), 3);
+
+    let history = run.segment(1).segment_history();
+    assert_eq!(history.get(1).unwrap().game_time, Some(span(3.0)));
+    assert_eq!(history.get(2).unwrap().game_time, Some(span(5.0)));
+    assert_eq!(history.get(3).unwrap().game_time, Some(span(3.0)));
+
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(2.0))
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(3.0))
+    );
+}
+
+#[test]
+fn keeps_the_better_personal_best() {
+    let mut run = run_from(&[&[3.0, 6.0]]);
+    let other = run_from(&[&[2.0, 5.0]]);
+
+    run.merge(&other, TimingMethod::GameTime).unwrap();
+    assert_eq!(
+        run.segment(1).personal_best_split_time().game_time,
+        Some(span(5.0))
+    );
+
+    let worse = run_from(&[&[1.0, 8.0]]);
+    run.merge(&worse, TimingMethod::GameTime).unwrap();
+    assert_eq!(
+        run.segment(0).personal_best_split_time().game_time,
+        Some(span(2.0))
+    );
+    assert_eq!(run.attempt_history().len(), 3);
+}
+
+#[test]
+fn moves_unattached_history_below_all_indices() {
+    let mut run = run_from(&[&[3.0, 6.0]]);
+    let mut other = run_from(&[&[2.0]]);
+    other
+        .segment_mut(0)
+        .segment_history_mut()
+        .insert(0, Default::default());
+    other
+        .segment_mut(1)
+        .segment_history_mut()
+        .insert(5, Default::default());
+
+    run.merge(&other, TimingMethod::GameTime).unwrap();
+
+    let history = run.segment(0).segment_history();
+    assert_eq!(history.get(2).unwrap().game_time, Some(span(2.0)));
+    assert!(history.get(0).is_some());
+    assert_eq!(history.try_get_min_index(), Some(0));
+    assert_eq!(
+        run.segment(1).segment_history().try_get_min_index(),
+        Some(-1)
+    );
+    assert_eq!(
+        run.segment(1).segment_history().try_get_max_index(),
+        Some(1)
+    );
+}
+
+#[test]
+fn relocated_history_stays_below_renumbered_attempts() {
+    let mut run = run_from(&[&[3.0, 6.0], &[2.5, 5.5]]);
+    set_started(&mut run, &[100, 300]);
+    for segment in run.segments_mut() {
+        segment.segment_history_mut().remove(1);
+    }
+    for segment in run.segments() {
+        assert_eq!(segment.segment_history().try_get_min_index(), Some(2));
+    }
+
+    let mut other = run_from(&[&[2.0, 7.0]]);
+    set_started(&mut other, &[50]);
+    other
+        .segment_mut(0)
+        .segment_history_mut()
+        .insert(5, Default::default());
+
+    run.merge(&other, TimingMethod::GameTime).unwrap();
+
+    let history = run.segment(0).segment_history();
+    assert_eq!(history.get(1).unwrap().game_time, Some(span(2.0)));
+    assert_eq!(history.get(3).unwrap().game_time, Some(span(2.5)));
+    assert_eq!(history.try_get_min_index(), Some(0));
+    assert_eq!(history.get(0), Some(Default::default()));
+}
+
+#[test]
+fn keeps_attempts_in_both_runs_once() {
+    let mut run = run_from(&[&[3.0, 6.0], &[2.5, 5.5]]);
+    set_started(&mut run, &[100, 300]);
+    let mut other = run.clone();
+    let expected = run.clone();
+
+    run.merge(&other, TimingMethod::GameTime).unwrap();
+    assert_eq!(run.attempt_history(), expected.attempt_history());
+    assert_eq!(run.attempt_count(), 2);
+    for (segment, expected) in run.segments().iter().zip(expected.segments()) {
+        assert_eq!(segment.segment_history(), expected.segment_history());
+    }
+
+    let mut newer = run_from(&[&[2.0, 5.0]]);
+    set_started(&mut newer, &[500]);
+    other
+        .attempt_history
+        .extend(newer.attempt_history().iter().map(|attempt| {
+            Attempt::new(
+                3,
+                attempt.time(),
+                attempt.started(),
+                attempt.ended(),
+                attempt.pause_time(),
+            )
+        }));
+    other.set_attempt_count(3);
+
+    run.merge(&other, TimingMethod::GameTime).unwrap();
+    assert_eq!(run.attempt_history().len(), 3);
+    assert_eq!(run.attempt_count(), 3);
+}
+
+/// Creates a run the way old versions of LiveSplit stored it, without any
+/// dates for the attempts.
+fn old_format_run(attempts: &[[Option<f64>; 2]]) -> Run {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.push_segment(Segment::new("B"));
+    for (index, segment_times) in (1..).zip(attempts) {
+        let final_time = segment_times[0]
+            .zip(segment_times[1])
+            .map(|(a, b)| span(a + b));
+        run.add_attempt_with_index(
+            Time::new().with_game_time(final_time),
+            index,
+            None,
+            None,
+            None,
+        );
+        for (segment, time) in run.segments_mut().iter_mut().zip(segment_times) {
+            segment
+                .segment_history_mut()
+                .insert(index, Time::new().with_game_time(time.map(span)));
+        }
+    }
+    run.set_attempt_count(attempts.len() as u32);
+    run
+}
+
+#[test]
+fn keeps_all_attempts_without_dates() {
+    let mut run = old_format_run(&[[Some(3.0), None], [None, None]]);
+    let other = old_format_run(&[[Some(2.0), None], [None, None]]);
+
+    run.merge(&other, TimingMethod::GameTime).unwrap();
+
+    assert_eq!(run.attempt_history().len(), 4);
+    assert_eq!(run.attempt_count(), 4);
+
+    let history = run.segment(0).segment_history();
+    assert_eq!(history.get(1).unwrap().game_time, Some(span(3.0)));
+    assert_eq!(history.get(3).unwrap().game_time, Some(span(2.0)));
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(2.0))
+    );
+}
+
+#[test]
+fn rejects_mismatched_segments() {
+    let mut run = run_from(&[&[3.0, 6.0]]);
+    let unchanged = run.clone();
+
+    let mut timer = create_timer(&["A", "C"]);
+    run_with_splits(&mut timer, &[1.0, 2.0]);
+    let renamed = timer.into_run(true);
+    assert_eq!(
+        run.merge(&renamed, TimingMethod::GameTime),
+        Err(MergeError::SegmentName {
+            position: 2,
+            name: "B".into(),
+            other_name: "C".into(),
+        })
+    );
+
+    let shorter = create_timer(&["A"]).into_run(true);
+    assert_eq!(
+        run.merge(&shorter, TimingMethod::GameTime),
+        Err(MergeError::SegmentCount {
+            len: 2,
+            other_len: 1,
+        })
+    );
+
+    assert_eq!(run, unchanged);
+}
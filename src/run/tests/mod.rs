@@ -1,6 +1,8 @@
 mod comparison;
+mod diff;
 mod empty_run;
 mod extended_category_name;
 mod fixing;
 mod linked_layout;
+mod merge;
 mod metadata;
@@ -0,0 +1,64 @@
+use crate::platform::prelude::*;
+
+/// Stores the XML elements and attributes of a splits file that livesplit-core
+/// doesn't know about, such as the ones written by newer versions of LiveSplit
+/// or by third party tools. They are kept around as opaque XML, so that they
+/// can be written back out when the splits file gets saved again, instead of
+/// getting lost. The attributes are the ones of the element that the unknown
+/// elements are children of.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownElements {
+    xml: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl UnknownElements {
+    /// Creates a new empty list of unknown elements.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            xml: String::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Returns whether there are no unknown elements and attributes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.xml.is_empty() && self.attributes.is_empty()
+    }
+
+    /// Drops all the unknown elements and attributes.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.xml.clear();
+        self.attributes.clear();
+    }
+
+    /// Accesses the unknown elements encoded as XML.
+    #[inline]
+    pub(crate) fn xml(&self) -> &str {
+        &self.xml
+    }
+
+    /// Grants mutable access to the XML encoded unknown elements. They need to
+    /// stay valid as an interior of an XML element.
+    #[inline]
+    pub(crate) const fn xml_mut(&mut self) -> &mut String {
+        &mut self.xml
+    }
+
+    /// Accesses the unknown attributes as pairs of their names and their XML
+    /// escaped values.
+    #[inline]
+    pub(crate) fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    /// Grants mutable access to the unknown attributes. Their values need to
+    /// stay XML escaped.
+    #[inline]
+    pub(crate) const fn attributes_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.attributes
+    }
+}
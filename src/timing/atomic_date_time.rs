@@ -32,11 +32,18 @@ impl AtomicDateTime {
     /// this value is marked as synchronized. Otherwise the local system's timer
     /// is used.
     ///
+    /// The point in time is provided by the [`Clock`](super::Clock) that is in
+    /// use.
+    ///
     /// # Warning
     ///
     /// livesplit-core doesn't synchronize with any atomic clock yet.
     #[inline]
     pub fn now() -> Self {
+        #[cfg(feature = "std")]
+        if let Some(time) = super::clock::date_now() {
+            return AtomicDateTime::new(time, false);
+        }
         AtomicDateTime {
             time: utc_now(),
             synced_with_atomic_clock: false,
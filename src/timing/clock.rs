@@ -0,0 +1,171 @@
+//! The clock module provides a way of replacing the clock that the timer
+//! measures time with. By default the system's clock is used. A different clock
+//! allows for deterministically testing the timer, such as by replaying
+//! scripted sessions with the [`ManualClock`], where time only passes when
+//! explicitly advanced.
+
+use crate::{
+    TimeSpan,
+    platform::{Arc, DateTime, Duration, RwLock},
+};
+
+/// A clock provides the points in time that the [`Timer`](crate::Timer), its
+/// attempts and the [`AtomicDateTime`](crate::AtomicDateTime) are based on. On
+/// a `no_std` target, a clock needs to be registered as the global handler with
+/// `register_clock`. Otherwise the system's clock is used, unless a different
+/// clock is used for the current thread with [`with_clock`].
+pub trait Clock: 'static {
+    /// Returns the current point in time as a Duration. This is expected to be
+    /// a monotonic high precision time stamp and does not need to represent a
+    /// time based on a calendar.
+    fn now(&self) -> Duration;
+
+    /// Returns the current point in time as a DateTime. This is expected to
+    /// represent the current date and time of day. It does not need to be a
+    /// high precision time stamp and is allowed to suddenly change to due
+    /// synchronization with a time server. If there's no notion of a calendar
+    /// on the system, you may return a dummy value instead.
+    fn date_now(&self) -> DateTime;
+}
+
+/// A clock that stands still until it gets advanced manually. Clones of the
+/// clock share the same time, so one clone can be handed to the timer, while
+/// another one is used for advancing the time.
+///
+/// # Examples
+///
+/// ```
+/// use livesplit_core::{
+///     DateTime, Run, Segment, TimeSpan, Timer,
+///     timing::clock::{self, ManualClock},
+/// };
+///
+/// let clock = ManualClock::new(DateTime::UNIX_EPOCH);
+///
+/// clock::with_clock(clock.clone(), || {
+///     let mut run = Run::new();
+///     run.push_segment(Segment::new("Cap Kingdom"));
+///     let mut timer = Timer::new(run).unwrap();
+///
+///     timer.start().unwrap();
+///     clock.advance(TimeSpan::from_seconds(90.0));
+///     timer.split().unwrap();
+///
+///     assert_eq!(
+///         timer.run().segment(0).split_time().real_time,
+///         Some(TimeSpan::from_seconds(90.0)),
+///     );
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ManualClock {
+    state: Arc<RwLock<ManualState>>,
+}
+
+struct ManualState {
+    elapsed: Duration,
+    date: DateTime,
+}
+
+impl ManualClock {
+    /// Creates a new Manual Clock that starts at the date and time provided.
+    pub fn new(date: DateTime) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ManualState {
+                elapsed: Duration::ZERO,
+                date,
+            })),
+        }
+    }
+
+    /// Lets the amount of time provided pass. This moves both the high
+    /// precision time stamps and the date and time forward.
+    pub fn advance(&self, time: TimeSpan) {
+        let mut state = self.state.write().unwrap();
+        let time = time.to_duration();
+        state.elapsed += time;
+        state.date += time;
+    }
+
+    /// Changes the date and time without affecting the high precision time
+    /// stamps. This can be used for simulating the system's clock getting
+    /// adjusted, such as when it synchronizes with a time server.
+    pub fn set_date(&self, date: DateTime) {
+        self.state.write().unwrap().date = date;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.state.read().unwrap().elapsed
+    }
+
+    fn date_now(&self) -> DateTime {
+        self.state.read().unwrap().date
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::thread::with_clock;
+
+#[cfg(feature = "std")]
+pub(super) use self::thread::{date_now, now};
+
+#[cfg(feature = "std")]
+mod thread {
+    use super::Clock;
+    use crate::platform::{DateTime, Duration, Instant};
+    use std::cell::RefCell;
+
+    struct ThreadClock {
+        clock: Box<dyn Clock>,
+        base: Instant,
+        origin: Duration,
+    }
+
+    std::thread_local! {
+        static CLOCK: RefCell<Option<ThreadClock>> = const { RefCell::new(None) };
+    }
+
+    /// Restores the clock that was used before, even if the closure panics.
+    struct Restore(Option<ThreadClock>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CLOCK.with_borrow_mut(|clock| *clock = self.0.take());
+        }
+    }
+
+    /// Uses the clock provided instead of the system's clock for everything
+    /// that happens on the current thread while the closure is running. This
+    /// also applies to the timers that are created outside of the closure.
+    /// Other threads, such as the ones of the hotkey system or the auto
+    /// splitting runtime, keep using the system's clock.
+    pub fn with_clock<R>(clock: impl Clock, f: impl FnOnce() -> R) -> R {
+        let origin = clock.now();
+        let previous = CLOCK.with_borrow_mut(|current| {
+            current.replace(ThreadClock {
+                clock: Box::new(clock),
+                base: Instant::now(),
+                origin,
+            })
+        });
+        let _restore = Restore(previous);
+        f()
+    }
+
+    /// Returns the current point in time according to the clock used for the
+    /// current thread, if there is one.
+    pub fn now() -> Option<Instant> {
+        CLOCK.with_borrow(|clock| {
+            let clock = clock.as_ref()?;
+            Some(clock.base + (clock.clock.now() - clock.origin))
+        })
+    }
+
+    /// Returns the current date and time according to the clock used for the
+    /// current thread, if there is one.
+    pub fn date_now() -> Option<DateTime> {
+        CLOCK.with_borrow(|clock| Some(clock.as_ref()?.clock.date_now()))
+    }
+}
@@ -2,6 +2,7 @@
 //! measuring them.
 
 mod atomic_date_time;
+pub mod clock;
 pub mod formatter;
 mod time;
 mod time_span;
@@ -11,13 +12,17 @@ mod timer_phase;
 mod timing_method;
 
 #[cfg(feature = "std")]
-pub use self::timer::SharedTimer;
+pub use self::timer::{SharedTimer, SharedTimerGroup};
 pub use self::{
     atomic_date_time::AtomicDateTime,
-    time::{GameTime, RealTime, Time},
+    clock::Clock,
+    time::{GameTime, LoadRemovedTime, RealTime, Time},
     time_span::{ParseError, TimeSpan},
     time_stamp::TimeStamp,
-    timer::{CreationError as TimerCreationError, Snapshot, Timer},
+    timer::{
+        AttemptJournal, CreationError as TimerCreationError, GapPolicy, GroupCreationError,
+        RestoreError, Snapshot, SubscriptionId, Timer, TimerGroup,
+    },
     timer_phase::TimerPhase,
     timing_method::TimingMethod,
 };
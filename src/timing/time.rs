@@ -1,34 +1,41 @@
 use crate::{TimeSpan, TimingMethod};
 use core::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
 
-/// A time that can store a Real Time and a Game Time. Both of them are
-/// optional.
+/// A time that can store a Real Time, a Game Time and a Load Removed Time. All
+/// of them are optional. Creating a Time with [`Time::new`] and the `with_`
+/// methods, rather than a struct literal, keeps working if more timing methods
+/// get added.
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq)]
 pub struct Time {
     /// The Real Time value.
     pub real_time: Option<TimeSpan>,
     /// The Game Time value.
     pub game_time: Option<TimeSpan>,
+    /// The Load Removed Time value.
+    pub load_removed_time: Option<TimeSpan>,
 }
 
 impl Time {
-    /// Creates a new Time with empty Real Time and Game Time.
+    /// Creates a new Time with empty Real Time, Game Time and Load Removed
+    /// Time.
     #[inline]
     pub const fn new() -> Self {
         Time {
             real_time: None,
             game_time: None,
+            load_removed_time: None,
         }
     }
 
-    /// Creates a new Time where Real Time and Game Time are zero. Keep in mind
-    /// that a zero Time Span is not the same as a `None` Time Span as created
-    /// by `Time::new()`.
+    /// Creates a new Time where Real Time, Game Time and Load Removed Time are
+    /// zero. Keep in mind that a zero Time Span is not the same as a `None`
+    /// Time Span as created by `Time::new()`.
     #[inline]
     pub const fn zero() -> Self {
         Time {
             real_time: Some(TimeSpan::zero()),
             game_time: Some(TimeSpan::zero()),
+            load_removed_time: Some(TimeSpan::zero()),
         }
     }
 
@@ -46,6 +53,16 @@ impl Time {
         Time { game_time, ..self }
     }
 
+    /// Creates a new Time based on the current one where the Load Removed Time
+    /// is replaced by the given Time Span.
+    #[inline]
+    pub const fn with_load_removed_time(self, load_removed_time: Option<TimeSpan>) -> Self {
+        Time {
+            load_removed_time,
+            ..self
+        }
+    }
+
     /// Creates a new Time based on the current one where the specified timing
     /// method is replaced by the given Time Span.
     #[inline]
@@ -58,8 +75,8 @@ impl Time {
         self
     }
 
-    /// Applies an operation to both Timing Methods of the two times provided
-    /// and creates a new Time from the result.
+    /// Applies an operation to all the Timing Methods of the two times
+    /// provided and creates a new Time from the result.
     pub fn op<F>(a: Time, b: Time, mut f: F) -> Time
     where
         F: FnMut(TimeSpan, TimeSpan) -> TimeSpan,
@@ -67,6 +84,7 @@ impl Time {
         Time {
             real_time: catch! { f(a.real_time?, b.real_time?) },
             game_time: catch! { f(a.game_time?, b.game_time?) },
+            load_removed_time: catch! { f(a.load_removed_time?, b.load_removed_time?) },
         }
     }
 }
@@ -89,6 +107,15 @@ impl From<GameTime> for Time {
     }
 }
 
+/// Represents a [`TimeSpan`](crate::TimeSpan) intended to be used for describing load removed time.
+pub struct LoadRemovedTime(pub Option<TimeSpan>);
+
+impl From<LoadRemovedTime> for Time {
+    fn from(t: LoadRemovedTime) -> Time {
+        Time::new().with_load_removed_time(t.0)
+    }
+}
+
 impl Add for Time {
     type Output = Time;
 
@@ -124,6 +151,7 @@ impl Index<TimingMethod> for Time {
         match timing_method {
             TimingMethod::RealTime => &self.real_time,
             TimingMethod::GameTime => &self.game_time,
+            TimingMethod::LoadRemovedTime => &self.load_removed_time,
         }
     }
 }
@@ -133,6 +161,7 @@ impl IndexMut<TimingMethod> for Time {
         match timing_method {
             TimingMethod::RealTime => &mut self.real_time,
             TimingMethod::GameTime => &mut self.game_time,
+            TimingMethod::LoadRemovedTime => &mut self.load_removed_time,
         }
     }
 }
@@ -8,9 +8,14 @@ use core::ops::Sub;
 pub struct TimeStamp(Instant);
 
 impl TimeStamp {
-    /// Creates a new `TimeStamp`, representing the current point in time.
+    /// Creates a new `TimeStamp`, representing the current point in time. The
+    /// point in time is provided by the [`Clock`](super::Clock) that is in use.
     #[inline]
     pub fn now() -> Self {
+        #[cfg(feature = "std")]
+        if let Some(now) = super::clock::now() {
+            return TimeStamp(now);
+        }
         TimeStamp(Instant::now())
     }
 }
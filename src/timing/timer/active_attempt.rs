@@ -1,6 +1,8 @@
 use crate::{
-    AtomicDateTime, Run, Time, TimeSpan, TimeStamp, TimingMethod,
+    AtomicDateTime, Attempt, Run, Time, TimeSpan, TimeStamp, TimingMethod,
     event::{Error, Event, Result},
+    platform::prelude::*,
+    run::{HistoryChanges, Pause},
 };
 
 #[derive(Debug, Clone)]
@@ -10,12 +12,25 @@ pub struct ActiveAttempt {
     pub attempt_started: AtomicDateTime,
     /// The time stamp when the attempt started.
     pub start_time: TimeStamp,
+    /// The time the attempt had already been running for when it got restored
+    /// from a journal. Time stamps can't be carried over to another session,
+    /// so the start time is the time stamp of the restoration instead.
+    pub restored_elapsed: TimeSpan,
     /// The original offset gets kept around to undo the pauses.
     pub original_offset: TimeSpan,
     /// The adjusted offset gets modified as pauses get accumulated.
     pub adjusted_offset: TimeSpan,
+    /// The pauses that already ended, along with the index of the segment that
+    /// was active when they ended. That is the first segment whose split time
+    /// includes the pause.
+    pub pauses: Vec<(usize, Pause)>,
     pub game_time_paused_at: Option<TimeSpan>,
     pub loading_times: Option<TimeSpan>,
+    pub load_removed_time_paused_at: Option<TimeSpan>,
+    /// The loading times that get removed from the Real Time to determine the
+    /// Load Removed Time. They are tracked separately from the loading times
+    /// of the Game Time.
+    pub removed_loading_times: Option<TimeSpan>,
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +47,7 @@ pub enum State {
 pub struct TimerTime {
     pub real_time: TimeSpan,
     pub game_time: Option<TimeSpan>,
+    pub load_removed_time: Option<TimeSpan>,
 }
 
 impl From<TimerTime> for Time {
@@ -39,35 +55,63 @@ impl From<TimerTime> for Time {
         Time {
             real_time: Some(time.real_time),
             game_time: time.game_time,
+            load_removed_time: time.load_removed_time,
         }
     }
 }
 
 impl ActiveAttempt {
+    /// Returns the time that passed since the attempt started, without the
+    /// offset and the pauses being applied.
+    pub fn elapsed(&self) -> TimeSpan {
+        self.elapsed_at(TimeStamp::now())
+    }
+
+    /// Returns the time that passed between the start of the attempt and the
+    /// point in time provided, without the offset and the pauses being
+    /// applied.
+    pub fn elapsed_at(&self, now: TimeStamp) -> TimeSpan {
+        now - self.start_time + self.restored_elapsed
+    }
+
     pub fn current_time(&self, run: &Run) -> TimerTime {
         let real_time = match self.state {
             State::Ended { .. } => {
                 let Time {
                     real_time,
                     game_time,
+                    load_removed_time,
                 } = run.segments().last().unwrap().split_time();
 
                 return TimerTime {
                     real_time: real_time.unwrap_or_default(),
                     game_time,
+                    load_removed_time,
                 };
             }
-            State::NotEnded { time_paused_at, .. } => time_paused_at
-                .unwrap_or_else(|| TimeStamp::now() - self.start_time + self.adjusted_offset),
+            State::NotEnded { time_paused_at, .. } => {
+                time_paused_at.unwrap_or_else(|| self.elapsed() + self.adjusted_offset)
+            }
         };
 
+        self.time_at(real_time)
+    }
+
+    /// Derives the Game Time and the Load Removed Time at the Real Time
+    /// provided.
+    fn time_at(&self, real_time: TimeSpan) -> TimerTime {
         let game_time = self
             .game_time_paused_at
             .or_else(|| Some(real_time - self.loading_times?));
 
+        let load_removed_time = self
+            .load_removed_time_paused_at
+            .or_else(|| Some(real_time - self.removed_loading_times?));
+
         TimerTime {
             real_time,
             game_time,
+            load_removed_time,
         }
     }
 
@@ -77,7 +121,7 @@ impl ActiveAttempt {
             ..
         } = self.state
         {
-            return Some(TimeStamp::now() - self.start_time + self.original_offset - pause_time);
+            return Some(self.elapsed() + self.original_offset - pause_time);
         }
 
         if self.original_offset != self.adjusted_offset {
@@ -87,6 +131,76 @@ impl ActiveAttempt {
         }
     }
 
+    /// Returns the pause that is currently ongoing, ending right now.
+    pub fn ongoing_pause(&self) -> Option<Pause> {
+        if let State::NotEnded {
+            time_paused_at: Some(pause_time),
+            ..
+        } = self.state
+        {
+            Some(Pause {
+                start: pause_time - self.adjusted_offset,
+                end: self.elapsed(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Ends the ongoing pause, if there is one.
+    pub fn unpause(&mut self) -> Result<(), Error> {
+        let pause = self.ongoing_pause();
+
+        let State::NotEnded {
+            current_split_index,
+            time_paused_at,
+        } = &mut self.state
+        else {
+            return Err(Error::RunFinished);
+        };
+
+        if let (Some(pause_time), Some(pause)) = (*time_paused_at, pause) {
+            self.adjusted_offset = pause_time - pause.end;
+            self.pauses.push((*current_split_index, pause));
+            *time_paused_at = None;
+            Ok(())
+        } else {
+            Err(Error::NotPaused)
+        }
+    }
+
+    /// Removes all the pauses that already ended from the split times and the
+    /// current time, as if the attempt had never been paused. Returns whether
+    /// any split times got changed.
+    pub fn undo_pauses(&mut self, run: &mut Run) -> bool {
+        let split_count = self.current_split_index_overflowing(run);
+        let mut changed = false;
+        for (segment_index, pause) in self.pauses.drain(..) {
+            changed |= segment_index < split_count;
+            let pause_time = Some(pause.duration());
+            for segment in run.segments_mut()[..split_count]
+                .iter_mut()
+                .skip(segment_index)
+            {
+                *segment.split_time_mut() += Time::new()
+                    .with_real_time(pause_time)
+                    .with_game_time(pause_time)
+                    .with_load_removed_time(pause_time);
+            }
+        }
+        self.adjusted_offset = self.original_offset;
+        changed
+    }
+
+    /// Makes sure that no pause is associated with a segment that doesn't
+    /// have a split time anymore, as its split time is going to include the
+    /// pause once it gets split again.
+    pub fn undo_split(&mut self, split_index: usize) {
+        for (segment_index, _) in &mut self.pauses {
+            *segment_index = (*segment_index).min(split_index);
+        }
+    }
+
     pub fn set_loading_times(&mut self, time: TimeSpan, run: &Run) {
         self.loading_times = Some(time);
         if self.game_time_paused_at.is_some() {
@@ -94,7 +208,16 @@ impl ActiveAttempt {
         }
     }
 
-    pub fn prepare_split(&mut self, run: &Run) -> Result<(usize, Time, Event)> {
+    /// Advances the attempt to the next split at the points in time provided.
+    /// The attempt ends at those points in time if it's the last split.
+    pub fn prepare_split(
+        &mut self,
+        run: &Run,
+        now: TimeStamp,
+        attempt_ended: AtomicDateTime,
+    ) -> Result<(usize, Time, Event)> {
+        let time = self.time_at(self.elapsed_at(now) + self.adjusted_offset);
+
         let State::NotEnded {
             current_split_index,
             time_paused_at,
@@ -107,36 +230,21 @@ impl ActiveAttempt {
             return Err(Error::TimerPaused);
         }
 
-        let real_time = TimeStamp::now() - self.start_time + self.adjusted_offset;
-
-        if real_time < TimeSpan::zero() {
+        if time.real_time < TimeSpan::zero() {
             return Err(Error::NegativeTime);
         }
 
-        let game_time = self
-            .game_time_paused_at
-            .or_else(|| Some(real_time - self.loading_times?));
-
         let previous_split_index = *current_split_index;
         *current_split_index += 1;
 
         let event = if *current_split_index == run.len() {
-            self.state = State::Ended {
-                attempt_ended: AtomicDateTime::now(),
-            };
+            self.state = State::Ended { attempt_ended };
             Event::Finished
         } else {
             Event::Splitted
         };
 
-        Ok((
-            previous_split_index,
-            Time {
-                real_time: Some(real_time),
-                game_time,
-            },
-            event,
-        ))
+        Ok((previous_split_index, time.into(), event))
     }
 
     pub const fn current_split_index(&self) -> Option<usize> {
@@ -169,11 +277,16 @@ impl ActiveAttempt {
         }
     }
 
-    pub fn update_times(&self, run: &mut Run, timing_method: TimingMethod) {
+    pub fn update_times(
+        &self,
+        run: &mut Run,
+        timing_method: TimingMethod,
+        changes: &mut HistoryChanges,
+    ) {
         self.update_attempt_history(run);
         update_best_segments(run);
-        update_pb_splits(run, timing_method);
-        run.update_segment_history(self.current_split_index_overflowing(run));
+        update_pb_splits(run, timing_method, changes);
+        run.update_segment_history_recording(self.current_split_index_overflowing(run), changes);
     }
 
     pub fn update_attempt_history(&self, run: &mut Run) {
@@ -185,50 +298,45 @@ impl ActiveAttempt {
         };
 
         let pause_time = self.get_pause_time();
+        let pauses = self
+            .pauses
+            .iter()
+            .map(|&(_, pause)| pause)
+            .chain(self.ongoing_pause())
+            .collect();
 
-        run.add_attempt(
-            time,
-            Some(self.attempt_started),
-            Some(attempt_ended),
-            pause_time,
+        run.push_attempt(
+            Attempt::new(
+                run.next_attempt_index(),
+                time,
+                Some(self.attempt_started),
+                Some(attempt_ended),
+                pause_time,
+            )
+            .with_pauses(pauses),
         );
     }
 }
 
 fn update_best_segments(run: &mut Run) {
-    let mut previous_split_time_rta = Some(TimeSpan::zero());
-    let mut previous_split_time_game_time = Some(TimeSpan::zero());
-
-    for split in run.segments_mut() {
-        let mut new_best_segment = split.best_segment_time();
-        if let Some(split_time) = split.split_time().real_time {
-            let current_segment = previous_split_time_rta.map(|previous| split_time - previous);
-            previous_split_time_rta = Some(split_time);
-            if split
-                .best_segment_time()
-                .real_time
-                .is_none_or(|b| current_segment.is_some_and(|c| c < b))
-            {
-                new_best_segment.real_time = current_segment;
-            }
-        }
-        if let Some(split_time) = split.split_time().game_time {
-            let current_segment =
-                previous_split_time_game_time.map(|previous| split_time - previous);
-            previous_split_time_game_time = Some(split_time);
-            if split
-                .best_segment_time()
-                .game_time
-                .is_none_or(|b| current_segment.is_some_and(|c| c < b))
-            {
-                new_best_segment.game_time = current_segment;
+    for method in TimingMethod::all() {
+        let mut previous_split_time = Some(TimeSpan::zero());
+
+        for split in run.segments_mut() {
+            if let Some(split_time) = split.split_time()[method] {
+                let current_segment = previous_split_time.map(|previous| split_time - previous);
+                previous_split_time = Some(split_time);
+                if split.best_segment_time()[method]
+                    .is_none_or(|b| current_segment.is_some_and(|c| c < b))
+                {
+                    split.best_segment_time_mut()[method] = current_segment;
+                }
             }
         }
-        split.set_best_segment_time(new_best_segment);
     }
 }
 
-fn update_pb_splits(run: &mut Run, method: TimingMethod) {
+fn update_pb_splits(run: &mut Run, method: TimingMethod, changes: &mut HistoryChanges) {
     let (split_time, pb_split_time) = {
         let last_segment = run.segments().last().unwrap();
         (
@@ -237,6 +345,6 @@ fn update_pb_splits(run: &mut Run, method: TimingMethod) {
         )
     };
     if split_time.is_some_and(|s| pb_split_time.is_none_or(|pb| s < pb)) {
-        super::set_run_as_pb(run);
+        super::set_run_as_pb(run, changes);
     }
 }
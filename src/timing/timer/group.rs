@@ -0,0 +1,214 @@
+use super::{Result, Timer};
+use crate::{
+    AtomicDateTime, TimeSpan, TimeStamp,
+    TimerPhase::*,
+    event::{Error, Event},
+    platform::prelude::*,
+};
+
+/// A `TimerGroup` owns multiple [`Timer`]s that are meant to be controlled
+/// together, for example for local co-op or for relay races. Each timer keeps
+/// its own run. Starting, pausing, resuming and resetting the group applies to
+/// all of its timers at once. Either all the timers are affected, or none of
+/// them are, if any of the timers is in a state where the command can't be
+/// applied. The timers are all checked before any of them gets changed, so a
+/// command never gets applied to only some of the timers.
+///
+/// In a relay, only one runner is running at a time. Starting the group only
+/// starts the first timer and the final split of a runner starts the timer of
+/// the next runner.
+///
+/// # Examples
+///
+/// ```
+/// use livesplit_core::{Run, Segment, Timer, TimerGroup, TimerPhase};
+///
+/// let mut run = Run::new();
+/// run.push_segment(Segment::new("Level 1"));
+///
+/// let timers = vec![Timer::new(run.clone()).unwrap(), Timer::new(run).unwrap()];
+/// let mut group = TimerGroup::new(timers).unwrap();
+/// group.set_relay(true);
+///
+/// group.start().unwrap();
+/// assert_eq!(group.timers()[1].current_phase(), TimerPhase::NotRunning);
+///
+/// // The final split of the first runner hands off to the second runner.
+/// group.split(0).unwrap();
+/// assert_eq!(group.timers()[0].current_phase(), TimerPhase::Ended);
+/// assert_eq!(group.timers()[1].current_phase(), TimerPhase::Running);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimerGroup {
+    timers: Vec<Timer>,
+    is_relay: bool,
+}
+
+/// A `SharedTimerGroup` is a wrapper around the [`TimerGroup`] that can be
+/// shared across multiple threads with multiple owners.
+#[cfg(feature = "std")]
+pub type SharedTimerGroup = alloc::sync::Arc<std::sync::RwLock<TimerGroup>>;
+
+/// The Error type for creating a new Timer Group.
+#[derive(Debug, snafu::Snafu)]
+pub enum CreationError {
+    /// The Timer Group couldn't be created, because no timers were provided.
+    Empty,
+}
+
+impl TimerGroup {
+    /// Creates a new Timer Group that owns the timers provided. The group
+    /// needs to consist of at least one timer. The order of the timers
+    /// determines the order of the runners in a relay.
+    pub fn new(timers: Vec<Timer>) -> Result<Self, CreationError> {
+        if timers.is_empty() {
+            return Err(CreationError::Empty);
+        }
+        Ok(Self {
+            timers,
+            is_relay: false,
+        })
+    }
+
+    /// Consumes the Timer Group and creates a Shared Timer Group that can be
+    /// shared across multiple threads with multiple owners.
+    #[cfg(feature = "std")]
+    pub fn into_shared(self) -> SharedTimerGroup {
+        alloc::sync::Arc::new(std::sync::RwLock::new(self))
+    }
+
+    /// Consumes the Timer Group and gives back the timers.
+    pub fn into_timers(self) -> Vec<Timer> {
+        self.timers
+    }
+
+    /// Accesses the timers of the group.
+    pub fn timers(&self) -> &[Timer] {
+        &self.timers
+    }
+
+    /// Grants mutable access to the timers of the group. This can be used for
+    /// controlling the timers individually, such as splitting in a co-op run
+    /// where every runner splits on their own.
+    pub fn timers_mut(&mut self) -> &mut [Timer] {
+        &mut self.timers
+    }
+
+    /// Adds another timer to the end of the group.
+    pub fn push(&mut self, timer: Timer) {
+        self.timers.push(timer);
+    }
+
+    /// Returns whether the group is a relay, where only one runner is running
+    /// at a time.
+    pub const fn is_relay(&self) -> bool {
+        self.is_relay
+    }
+
+    /// Sets whether the group is a relay, where only one runner is running at
+    /// a time.
+    pub const fn set_relay(&mut self, is_relay: bool) {
+        self.is_relay = is_relay;
+    }
+
+    /// Starts all the timers at exactly the same point in time. In a relay,
+    /// only the first timer is started. None of the timers are started if any
+    /// of them already has an attempt in progress.
+    pub fn start(&mut self) -> Result {
+        if self.timers.iter().any(|t| t.current_phase() != NotRunning) {
+            return Err(Error::RunAlreadyInProgress);
+        }
+
+        let (start_time, attempt_started) = (TimeStamp::now(), AtomicDateTime::now());
+        let count = if self.is_relay { 1 } else { self.timers.len() };
+        for timer in &mut self.timers[..count] {
+            // None of the timers have an attempt in progress, so starting them
+            // can't fail.
+            let _ = timer.start_at(start_time, attempt_started, TimeSpan::zero());
+        }
+
+        Ok(Event::Started)
+    }
+
+    /// Splits the timer at the index provided. In a relay, the final split of
+    /// a runner starts the timer of the next runner at exactly the same time.
+    /// An error is returned if the index is out of bounds.
+    pub fn split(&mut self, index: usize) -> Result {
+        let timer = self.timers.get_mut(index).ok_or(Error::InvalidIndex)?;
+        let (now, date_time) = (TimeStamp::now(), AtomicDateTime::now());
+        let event = timer.split_at(now, date_time)?;
+
+        if self.is_relay && event == Event::Finished {
+            if let Some(next) = self
+                .timers
+                .get_mut(index + 1)
+                .filter(|t| t.current_phase() == NotRunning)
+            {
+                // The next runner has no attempt in progress, so starting the
+                // timer can't fail.
+                let _ = next.start_at(now, date_time, TimeSpan::zero());
+            }
+        }
+
+        Ok(event)
+    }
+
+    /// Pauses all the timers that are running at exactly the same point in
+    /// time. None of the timers are paused if there is no timer that is
+    /// running.
+    pub fn pause(&mut self) -> Result {
+        if !self.timers.iter().any(|t| t.current_phase() == Running) {
+            return Err(if self.timers.iter().any(|t| t.current_phase() == Paused) {
+                Error::AlreadyPaused
+            } else {
+                Error::NoRunInProgress
+            });
+        }
+
+        let now = TimeStamp::now();
+        for timer in &mut self.timers {
+            // Only the timers that are running get paused, which can't fail.
+            if timer.current_phase() == Running {
+                let _ = timer.pause_at(now);
+            }
+        }
+
+        Ok(Event::Paused)
+    }
+
+    /// Resumes all the timers that are paused. None of the timers are resumed
+    /// if there is no timer that is paused.
+    pub fn resume(&mut self) -> Result {
+        if !self.timers.iter().any(|t| t.current_phase() == Paused) {
+            return Err(Error::NotPaused);
+        }
+
+        for timer in &mut self.timers {
+            // Only the timers that are paused get resumed, which can't fail.
+            if timer.current_phase() == Paused {
+                let _ = timer.resume();
+            }
+        }
+
+        Ok(Event::Resumed)
+    }
+
+    /// Resets all the timers that have an attempt in progress. If the splits
+    /// are to be updated, all the information of the attempts is stored in the
+    /// history of each timer's run. Otherwise the information is discarded.
+    pub fn reset(&mut self, update_splits: bool) -> Result {
+        if self.timers.iter().all(|t| t.current_phase() == NotRunning) {
+            return Err(Error::NoRunInProgress);
+        }
+
+        for timer in &mut self.timers {
+            // Only the timers with an attempt in progress get reset, which
+            // can't fail.
+            if timer.current_phase() != NotRunning {
+                let _ = timer.reset(update_splits);
+            }
+        }
+
+        Ok(Event::Reset)
+    }
+}
@@ -0,0 +1,250 @@
+use super::active_attempt::ActiveAttempt;
+use crate::{
+    Run, Segment, Time,
+    platform::prelude::*,
+    run::{Attempt, HistoryChanges},
+};
+use alloc::collections::VecDeque;
+use core::mem;
+use hashbrown::HashMap;
+
+/// The maximum amount of operations that can be undone.
+const CAPACITY: usize = 32;
+
+/// The kind of operation that got recorded in the history.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operation {
+    Reset,
+    SkipSplit,
+    Pause,
+    Resume,
+    GameTime,
+    LoadRemovedTime,
+    Retime,
+    Comparison,
+}
+
+/// The state of the timer before an operation got applied. Only the parts of
+/// the timer that the operation changes are stored.
+#[derive(Debug, Clone)]
+pub enum State {
+    Attempt(Option<ActiveAttempt>),
+    Comparison(String),
+    Run {
+        run: RunChanges,
+        active_attempt: Option<ActiveAttempt>,
+    },
+}
+
+/// The changes that restore a [`Run`] to an earlier state. Only the parts of
+/// the Run that an operation changed are stored, as resetting an attempt for
+/// example only adds the attempt to the history and possibly changes the best
+/// segments and the Personal Best. The changes are recorded while the
+/// operation gets applied, so the Run never needs to be cloned.
+#[derive(Debug, Clone)]
+pub struct RunChanges {
+    kept_attempts: usize,
+    attempts: Vec<Attempt>,
+    segments: Vec<SegmentChanges>,
+    history: HistoryChanges,
+    run_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct SegmentChanges {
+    index: usize,
+    split_time: Time,
+    best_segment_time: Time,
+    comparisons: Vec<(String, Time)>,
+    variables: HashMap<String, String>,
+}
+
+impl RunChanges {
+    /// Starts recording the changes of an operation that is about to be
+    /// applied to the Run. The times and variables of the segments are small,
+    /// so they are captured up front, while the changes to the Segment
+    /// Histories need to be recorded into [`history_mut`](Self::history_mut)
+    /// while the operation gets applied. The operation may only add attempts
+    /// to the Attempt History.
+    pub fn record(run: &Run) -> Self {
+        Self {
+            kept_attempts: run.attempt_history().len(),
+            attempts: Vec::new(),
+            segments: run
+                .segments()
+                .iter()
+                .enumerate()
+                .map(|(index, segment)| SegmentChanges {
+                    index,
+                    split_time: segment.split_time(),
+                    best_segment_time: segment.best_segment_time(),
+                    comparisons: run
+                        .custom_comparisons()
+                        .iter()
+                        .map(|comparison| (comparison.clone(), segment.comparison(comparison)))
+                        .collect(),
+                    variables: segment.variables().clone(),
+                })
+                .collect(),
+            history: HistoryChanges::default(),
+            run_id: Some(run.metadata().run_id().into()),
+        }
+    }
+
+    /// Grants access to the changes of the Segment Histories that are being
+    /// recorded.
+    pub const fn history_mut(&mut self) -> &mut HistoryChanges {
+        &mut self.history
+    }
+
+    /// Finishes recording the changes once the operation got applied. Only
+    /// the parts of the segments that actually changed are kept.
+    pub fn finish(mut self, run: &Run) -> Self {
+        self.segments
+            .retain_mut(|changes| changes.finish(run.segment(changes.index)));
+        if self.run_id.as_deref() == Some(run.metadata().run_id()) {
+            self.run_id = None;
+        }
+        self
+    }
+
+    /// Applies the changes to the Run and returns the changes that revert
+    /// them.
+    pub fn apply(self, run: &mut Run) -> Self {
+        let attempts = run.attempt_history_mut().split_off(self.kept_attempts);
+        run.attempt_history_mut().extend(self.attempts);
+
+        let run_id = self.run_id.map(|run_id| {
+            let previous = run.metadata().run_id().into();
+            run.metadata_mut().set_run_id(run_id);
+            previous
+        });
+
+        let segments = self
+            .segments
+            .into_iter()
+            .map(|changes| changes.apply(run))
+            .collect();
+
+        let history = self.history.revert(run.segments_mut());
+
+        Self {
+            kept_attempts: self.kept_attempts,
+            attempts,
+            segments,
+            history,
+            run_id,
+        }
+    }
+}
+
+impl SegmentChanges {
+    /// Drops the parts that the segment provided still has the same way.
+    /// Returns whether anything changed at all.
+    fn finish(&mut self, segment: &Segment) -> bool {
+        self.comparisons
+            .retain(|(comparison, time)| segment.comparison(comparison) != *time);
+
+        !self.comparisons.is_empty()
+            || segment.split_time() != self.split_time
+            || segment.best_segment_time() != self.best_segment_time
+            || *segment.variables() != self.variables
+    }
+
+    fn apply(self, run: &mut Run) -> Self {
+        let segment = run.segment_mut(self.index);
+
+        let comparisons = self
+            .comparisons
+            .into_iter()
+            .map(|(comparison, time)| {
+                let previous = mem::replace(segment.comparison_mut(&comparison), time);
+                (comparison, previous)
+            })
+            .collect();
+
+        Self {
+            index: self.index,
+            split_time: mem::replace(segment.split_time_mut(), self.split_time),
+            best_segment_time: mem::replace(
+                segment.best_segment_time_mut(),
+                self.best_segment_time,
+            ),
+            comparisons,
+            variables: mem::replace(segment.variables_mut(), self.variables),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub operation: Operation,
+    pub state: State,
+}
+
+/// A bounded history of the operations applied to the timer that can be
+/// undone and redone.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo: VecDeque<Entry>,
+    redo: Vec<Entry>,
+}
+
+impl History {
+    /// Returns whether the next operation of the kind provided would be merged
+    /// into the most recent entry. Consecutive changes of the game time or the
+    /// load removed time are merged, as auto splitters may change them many
+    /// times per second.
+    pub fn merges(&self, operation: Operation) -> bool {
+        matches!(operation, Operation::GameTime | Operation::LoadRemovedTime)
+            && self
+                .undo
+                .back()
+                .is_some_and(|entry| entry.operation == operation)
+    }
+
+    /// Records the state of the timer before the operation got applied. This
+    /// discards all the operations that could be redone.
+    pub fn record(&mut self, operation: Operation, state: State) {
+        self.redo.clear();
+        if self.merges(operation) {
+            return;
+        }
+        if self.undo.len() == CAPACITY {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(Entry { operation, state });
+    }
+
+    pub fn pop_undo(&mut self) -> Option<Entry> {
+        self.undo.pop_back()
+    }
+
+    pub fn push_undo(&mut self, entry: Entry) {
+        self.undo.push_back(entry);
+    }
+
+    pub fn pop_redo(&mut self) -> Option<Entry> {
+        self.redo.pop()
+    }
+
+    pub fn push_redo(&mut self, entry: Entry) {
+        self.redo.push(entry);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets all the operations. This is necessary whenever the timer gets
+    /// changed in a way that isn't recorded, as the recorded states would
+    /// otherwise overwrite these changes.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
@@ -0,0 +1,334 @@
+use super::active_attempt::{ActiveAttempt, State};
+use crate::{
+    AtomicDateTime, Run, Time, TimeSpan, TimeStamp,
+    platform::{DateTime, Duration, prelude::*},
+    run::Pause,
+};
+use serde_derive::{Deserialize, Serialize};
+
+/// A journal of the attempt that is currently in progress. It stores
+/// everything that is necessary to continue the attempt in case the
+/// application crashes or gets closed, such as the split times, the pauses,
+/// the game time and the custom variables. It is meant to be written whenever
+/// the timer changes, for example by [subscribing](super::Timer::subscribe) to
+/// the timer's events, and then restored on the next launch with
+/// [`Timer::restore_attempt`](super::Timer::restore_attempt).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AttemptJournal {
+    written: Date,
+    attempt_count: u32,
+    game_name: String,
+    category_name: String,
+    segment_names: Vec<String>,
+    attempt_started: Date,
+    attempt_ended: Option<Date>,
+    elapsed: Span,
+    original_offset: Span,
+    adjusted_offset: Span,
+    time_paused_at: Option<Span>,
+    pauses: Vec<Interval>,
+    game_time_paused_at: Option<Span>,
+    loading_times: Option<Span>,
+    load_removed_time_paused_at: Option<Span>,
+    removed_loading_times: Option<Span>,
+    splits: Vec<Split>,
+    custom_variables: Vec<Variable>,
+}
+
+/// Describes how the time between writing an [`AttemptJournal`] and restoring
+/// it is treated. The time is unaccounted for, as the timer wasn't running
+/// during that time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GapPolicy {
+    /// The time is counted as a pause, so the timer continues at the time it
+    /// was at when the journal got written.
+    Paused,
+    /// The time is counted as if the timer was running, so the timer continues
+    /// as if it never stopped.
+    Running,
+}
+
+/// The error type for restoring an attempt from an [`AttemptJournal`].
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum RestoreError {
+    /// There is already an attempt in progress.
+    RunAlreadyInProgress,
+    /// The journal belongs to a run with a different amount of segments.
+    #[snafu(display("The journal has {journal} segments, but the run has {run} segments."))]
+    SegmentCount {
+        /// The amount of segments of the run the journal belongs to.
+        journal: usize,
+        /// The amount of segments of the run of the timer.
+        run: usize,
+    },
+    /// The journal belongs to a run with a different game name, category name
+    /// or segment names.
+    DifferentRun,
+    /// The journal has more or less split times than its state allows for.
+    InvalidSplits,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Split {
+    real_time: Option<Span>,
+    game_time: Option<Span>,
+    load_removed_time: Option<Span>,
+    variables: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Variable {
+    name: String,
+    value: String,
+    is_permanent: bool,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+struct Interval {
+    segment_index: usize,
+    start: Span,
+    end: Span,
+}
+
+/// A lossless representation of a [`TimeSpan`] as its whole seconds and the
+/// nanoseconds past the last full second.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+struct Span(i64, i32);
+
+/// A representation of an [`AtomicDateTime`] as a Unix timestamp.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+struct Date {
+    seconds: i64,
+    nanoseconds: u32,
+    synced_with_atomic_clock: bool,
+}
+
+impl From<TimeSpan> for Span {
+    fn from(time: TimeSpan) -> Self {
+        let (seconds, nanoseconds) = time.to_seconds_and_subsec_nanoseconds();
+        Self(seconds, nanoseconds)
+    }
+}
+
+impl From<Span> for TimeSpan {
+    fn from(Span(seconds, nanoseconds): Span) -> Self {
+        Duration::new(seconds, nanoseconds).into()
+    }
+}
+
+impl From<AtomicDateTime> for Date {
+    fn from(date: AtomicDateTime) -> Self {
+        Self {
+            seconds: date.time.unix_timestamp(),
+            nanoseconds: date.time.nanosecond(),
+            synced_with_atomic_clock: date.synced_with_atomic_clock,
+        }
+    }
+}
+
+impl From<Date> for AtomicDateTime {
+    fn from(date: Date) -> Self {
+        let time = DateTime::from_unix_timestamp(date.seconds).unwrap_or(DateTime::UNIX_EPOCH)
+            + Duration::nanoseconds(date.nanoseconds.into());
+        AtomicDateTime::new(time, date.synced_with_atomic_clock)
+    }
+}
+
+impl AttemptJournal {
+    pub(super) fn new(active_attempt: &ActiveAttempt, run: &Run) -> Self {
+        let (current_split_index, attempt_ended, time_paused_at) = match active_attempt.state {
+            State::NotEnded {
+                current_split_index,
+                time_paused_at,
+            } => (current_split_index, None, time_paused_at),
+            State::Ended { attempt_ended } => (run.len(), Some(attempt_ended.into()), None),
+        };
+
+        Self {
+            written: AtomicDateTime::now().into(),
+            attempt_count: run.attempt_count(),
+            game_name: run.game_name().to_owned(),
+            category_name: run.category_name().to_owned(),
+            segment_names: run
+                .segments()
+                .iter()
+                .map(|segment| segment.name().to_owned())
+                .collect(),
+            attempt_started: active_attempt.attempt_started.into(),
+            attempt_ended,
+            elapsed: active_attempt.elapsed().into(),
+            original_offset: active_attempt.original_offset.into(),
+            adjusted_offset: active_attempt.adjusted_offset.into(),
+            time_paused_at: time_paused_at.map(Into::into),
+            pauses: active_attempt
+                .pauses
+                .iter()
+                .map(|&(segment_index, pause)| Interval {
+                    segment_index,
+                    start: pause.start.into(),
+                    end: pause.end.into(),
+                })
+                .collect(),
+            game_time_paused_at: active_attempt.game_time_paused_at.map(Into::into),
+            loading_times: active_attempt.loading_times.map(Into::into),
+            load_removed_time_paused_at: active_attempt.load_removed_time_paused_at.map(Into::into),
+            removed_loading_times: active_attempt.removed_loading_times.map(Into::into),
+            splits: run.segments()[..current_split_index]
+                .iter()
+                .map(|segment| {
+                    let time = segment.split_time();
+                    Split {
+                        real_time: time.real_time.map(Into::into),
+                        game_time: time.game_time.map(Into::into),
+                        load_removed_time: time.load_removed_time.map(Into::into),
+                        variables: segment
+                            .variables()
+                            .iter()
+                            .map(|(name, value)| (name.clone(), value.clone()))
+                            .collect(),
+                    }
+                })
+                .collect(),
+            custom_variables: run
+                .metadata()
+                .custom_variables()
+                .map(|(name, variable)| Variable {
+                    name: name.to_owned(),
+                    value: variable.value.clone(),
+                    is_permanent: variable.is_permanent,
+                })
+                .collect(),
+        }
+    }
+
+    /// Restores the attempt into the run and returns the active attempt.
+    pub(super) fn restore(
+        &self,
+        run: &mut Run,
+        gap_policy: GapPolicy,
+    ) -> Result<ActiveAttempt, RestoreError> {
+        if self.segment_names.len() != run.len() {
+            return Err(RestoreError::SegmentCount {
+                journal: self.segment_names.len(),
+                run: run.len(),
+            });
+        }
+
+        if self.game_name != run.game_name()
+            || self.category_name != run.category_name()
+            || self
+                .segment_names
+                .iter()
+                .zip(run.segments())
+                .any(|(name, segment)| name != segment.name())
+        {
+            return Err(RestoreError::DifferentRun);
+        }
+
+        let state = match self.attempt_ended {
+            Some(attempt_ended) if self.splits.len() == run.len() => State::Ended {
+                attempt_ended: attempt_ended.into(),
+            },
+            None if self.splits.len() < run.len() => State::NotEnded {
+                current_split_index: self.splits.len(),
+                time_paused_at: self.time_paused_at.map(Into::into),
+            },
+            _ => return Err(RestoreError::InvalidSplits),
+        };
+
+        let gap =
+            (AtomicDateTime::now() - AtomicDateTime::from(self.written)).max(TimeSpan::zero());
+
+        let elapsed = TimeSpan::from(self.elapsed);
+        let mut adjusted_offset = self.adjusted_offset.into();
+        let mut pauses: Vec<_> = self
+            .pauses
+            .iter()
+            .map(|interval| {
+                (
+                    interval.segment_index,
+                    Pause {
+                        start: interval.start.into(),
+                        end: interval.end.into(),
+                    },
+                )
+            })
+            .collect();
+
+        if let (
+            GapPolicy::Paused,
+            State::NotEnded {
+                current_split_index,
+                time_paused_at: None,
+            },
+        ) = (gap_policy, &state)
+        {
+            adjusted_offset -= gap;
+            pauses.push((
+                *current_split_index,
+                Pause {
+                    start: elapsed,
+                    end: elapsed + gap,
+                },
+            ));
+        }
+
+        for (segment, split) in run.segments_mut().iter_mut().zip(&self.splits) {
+            segment.set_split_time(Time {
+                real_time: split.real_time.map(Into::into),
+                game_time: split.game_time.map(Into::into),
+                load_removed_time: split.load_removed_time.map(Into::into),
+            });
+            *segment.variables_mut() = split.variables.iter().cloned().collect();
+        }
+
+        for variable in &self.custom_variables {
+            let custom_variable = run
+                .metadata_mut()
+                .custom_variable_mut(variable.name.as_str());
+            custom_variable.set_value(variable.value.as_str());
+            if variable.is_permanent {
+                custom_variable.permanent();
+            }
+        }
+
+        if run.attempt_count() < self.attempt_count {
+            run.set_attempt_count(self.attempt_count);
+        }
+        run.mark_as_modified();
+
+        Ok(ActiveAttempt {
+            state,
+            attempt_started: self.attempt_started.into(),
+            start_time: TimeStamp::now(),
+            restored_elapsed: elapsed + gap,
+            original_offset: self.original_offset.into(),
+            adjusted_offset,
+            pauses,
+            game_time_paused_at: self.game_time_paused_at.map(Into::into),
+            loading_times: self.loading_times.map(Into::into),
+            load_removed_time_paused_at: self.load_removed_time_paused_at.map(Into::into),
+            removed_loading_times: self.removed_loading_times.map(Into::into),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl AttemptJournal {
+    /// Decodes the journal from JSON.
+    pub fn from_json<R>(reader: R) -> serde_json::Result<Self>
+    where
+        R: std::io::Read,
+    {
+        serde_json::from_reader(reader)
+    }
+
+    /// Encodes the journal as JSON.
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+}
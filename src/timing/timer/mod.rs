@@ -6,6 +6,7 @@ use crate::{
     comparison::personal_best,
     event::{Error, Event},
     platform::prelude::*,
+    run::HistoryChanges,
     util::PopulateString,
 };
 use core::{mem, ops::Deref};
@@ -14,7 +15,19 @@ use core::{mem, ops::Deref};
 mod tests;
 
 mod active_attempt;
+mod group;
+mod history;
+mod journal;
+mod subscribers;
 use active_attempt::{ActiveAttempt, State};
+use history::{Entry, History, Operation};
+use subscribers::Subscribers;
+
+#[cfg(feature = "std")]
+pub use group::SharedTimerGroup;
+pub use group::{CreationError as GroupCreationError, TimerGroup};
+pub use journal::{AttemptJournal, GapPolicy, RestoreError};
+pub use subscribers::SubscriptionId;
 
 /// A `Timer` provides all the capabilities necessary for doing speedrun attempts.
 ///
@@ -54,6 +67,27 @@ pub struct Timer {
     current_comparison: String,
     current_timing_method: TimingMethod,
     active_attempt: Option<ActiveAttempt>,
+    scheduled_start: Option<ScheduledStart>,
+    history: History,
+    subscribers: Subscribers,
+}
+
+/// A start of the timer that is scheduled for a specific point in time. The
+/// countdown is measured with the monotonic clock from the moment the start
+/// got scheduled, so adjustments of the system's clock don't affect it.
+#[derive(Debug, Copy, Clone)]
+struct ScheduledStart {
+    at: AtomicDateTime,
+    scheduled_at: TimeStamp,
+    delay: TimeSpan,
+}
+
+impl ScheduledStart {
+    /// Returns the time that is left until the timer is supposed to start.
+    /// This is negative if that point in time has already passed.
+    fn remaining(&self) -> TimeSpan {
+        self.delay - (TimeStamp::now() - self.scheduled_at)
+    }
 }
 
 /// A snapshot represents a specific point in time that the timer was observed
@@ -62,14 +96,27 @@ pub struct Timer {
 pub struct Snapshot<'timer> {
     timer: &'timer Timer,
     time: Time,
+    countdown: Option<TimeSpan>,
 }
 
 impl Snapshot<'_> {
     /// Returns the time the timer was at when the snapshot was taken. The Game
-    /// Time is [`None`] if the Game Time has not been initialized.
+    /// Time is [`None`] if the Game Time has not been initialized. The same
+    /// applies to the Load Removed Time. While counting down to a scheduled
+    /// start, this is the negative time that is left until the start, on top
+    /// of the Run's offset. Once the scheduled point in time has passed, it
+    /// keeps counting up as if the attempt was already running.
     pub const fn current_time(&self) -> Time {
         self.time
     }
+
+    /// Returns the time that was left until the scheduled start of the timer
+    /// when the snapshot was taken. This is [`None`] if no start is scheduled.
+    /// Once the scheduled point in time has been reached, this is zero until
+    /// the attempt gets started by the next command the timer receives.
+    pub const fn countdown(&self) -> Option<TimeSpan> {
+        self.countdown
+    }
 }
 
 impl Deref for Snapshot<'_> {
@@ -111,6 +158,9 @@ impl Timer {
             current_comparison: personal_best::NAME.into(),
             current_timing_method: TimingMethod::RealTime,
             active_attempt: None,
+            scheduled_start: None,
+            history: History::default(),
+            subscribers: Subscribers::default(),
         })
     }
 
@@ -149,6 +199,7 @@ impl Timer {
 
         run.fix_splits();
         run.regenerate_comparisons();
+        self.history.clear();
 
         Ok(mem::replace(&mut self.run, run))
     }
@@ -198,18 +249,28 @@ impl Timer {
     /// work with an entirely consistent view of the timer without the current
     /// time changing underneath.
     pub fn snapshot(&self) -> Snapshot<'_> {
+        let remaining = self
+            .scheduled_start
+            .map(|scheduled_start| scheduled_start.remaining());
+        let countdown = remaining.map(|remaining| remaining.max(TimeSpan::zero()));
+
         let time = match &self.active_attempt {
             Some(active_attempt) => active_attempt.current_time(&self.run).into(),
             None => {
-                let offset = Some(self.run.offset());
+                let offset = Some(self.run.offset() - remaining.unwrap_or_default());
                 Time {
                     real_time: offset,
                     game_time: offset,
+                    load_removed_time: offset,
                 }
             }
         };
 
-        Snapshot { timer: self, time }
+        Snapshot {
+            timer: self,
+            time,
+            countdown,
+        }
     }
 
     /// Returns the currently selected timing method.
@@ -220,17 +281,24 @@ impl Timer {
 
     /// Sets the current timing method to the timing method provided.
     #[inline]
-    pub const fn set_current_timing_method(&mut self, method: TimingMethod) {
+    pub fn set_current_timing_method(&mut self, method: TimingMethod) {
         self.current_timing_method = method;
+        self.notify(Event::TimingMethodChanged);
     }
 
-    /// Toggles between the `Real Time` and `Game Time` timing methods.
+    /// Toggles between the `Real Time` and `Game Time` timing methods. If the
+    /// `Load Removed Time` is the current timing method, it switches to `Real
+    /// Time`. The `Load Removed Time` is not part of the cycle, as most runs
+    /// don't track it, so toggling would regularly switch to a timing method
+    /// without any times. It can be selected with
+    /// [`set_current_timing_method`](Self::set_current_timing_method) instead.
     #[inline]
-    pub const fn toggle_timing_method(&mut self) {
+    pub fn toggle_timing_method(&mut self) {
         self.current_timing_method = match self.current_timing_method {
             TimingMethod::RealTime => TimingMethod::GameTime,
-            TimingMethod::GameTime => TimingMethod::RealTime,
+            TimingMethod::GameTime | TimingMethod::LoadRemovedTime => TimingMethod::RealTime,
         };
+        self.notify(Event::TimingMethodChanged);
     }
 
     /// Returns the current comparison that is being compared against. This may
@@ -247,8 +315,11 @@ impl Timer {
     pub fn set_current_comparison<S: PopulateString>(&mut self, comparison: S) -> Result {
         let as_str = comparison.as_str();
         if self.run.comparisons().any(|c| c == as_str) {
+            let previous = self.current_comparison.clone();
             comparison.populate(&mut self.current_comparison);
-            Ok(Event::ComparisonChanged)
+            self.history
+                .record(Operation::Comparison, history::State::Comparison(previous));
+            Ok(self.notify(Event::ComparisonChanged))
         } else {
             Err(Error::ComparisonDoesntExist)
         }
@@ -280,9 +351,23 @@ impl Timer {
     /// Starts the Timer if there is no attempt in progress. If that's not the
     /// case, nothing happens.
     pub fn start(&mut self) -> Result {
+        if let Some(event) = self.poll_scheduled_start() {
+            return Ok(event);
+        }
+        self.start_at(TimeStamp::now(), AtomicDateTime::now(), TimeSpan::zero())
+    }
+
+    /// Starts the Timer as if it got started at the points in time provided.
+    /// This allows multiple timers to start at exactly the same time. The time
+    /// that has already elapsed before the start time is added on top, so the
+    /// attempt can be started retroactively.
+    fn start_at(
+        &mut self,
+        start_time: TimeStamp,
+        attempt_started: AtomicDateTime,
+        elapsed: TimeSpan,
+    ) -> Result {
         if self.active_attempt.is_none() {
-            let attempt_started = AtomicDateTime::now();
-            let start_time = TimeStamp::now();
             let offset = self.run.offset();
 
             self.active_attempt = Some(ActiveAttempt {
@@ -292,25 +377,98 @@ impl Timer {
                 },
                 attempt_started,
                 start_time,
+                restored_elapsed: elapsed,
                 original_offset: offset,
                 adjusted_offset: offset,
+                pauses: Vec::new(),
                 game_time_paused_at: None,
                 loading_times: None,
+                load_removed_time_paused_at: None,
+                removed_loading_times: None,
             });
             self.run.start_next_run();
+            self.scheduled_start = None;
+            self.history.clear();
 
-            Ok(Event::Started)
+            Ok(self.notify(Event::Started))
         } else {
             Err(Error::RunAlreadyInProgress)
         }
     }
 
+    /// Schedules the Timer to start at the point in time provided, such as
+    /// the start of a race or a marathon slot. Until then the Timer counts
+    /// down, which is reflected by the [`Snapshot`]. Scheduling a start
+    /// replaces any start that was scheduled before. This requires that there
+    /// is no attempt in progress. Starting the Timer in any other way before
+    /// the scheduled point in time cancels the scheduled start.
+    ///
+    /// Once the scheduled point in time has passed, the next command the Timer
+    /// receives starts the attempt first, as if it started exactly at the
+    /// scheduled point in time. To start it without waiting for a command,
+    /// [`poll_scheduled_start`](Self::poll_scheduled_start) can be called
+    /// regularly, such as every frame. The same is possible through a
+    /// [`CommandSink`](crate::event::CommandSink).
+    pub fn schedule_start(&mut self, at: AtomicDateTime) -> Result {
+        if self.active_attempt.is_some() {
+            return Err(Error::RunAlreadyInProgress);
+        }
+
+        self.scheduled_start = Some(ScheduledStart {
+            at,
+            scheduled_at: TimeStamp::now(),
+            delay: at - AtomicDateTime::now(),
+        });
+
+        Ok(self.notify(Event::StartScheduled))
+    }
+
+    /// Cancels the start that is scheduled, if there is one.
+    pub fn cancel_scheduled_start(&mut self) -> Result {
+        self.poll_scheduled_start();
+        if self.scheduled_start.take().is_some() {
+            Ok(self.notify(Event::ScheduledStartCancelled))
+        } else {
+            Err(Error::NoStartScheduled)
+        }
+    }
+
+    /// Returns the point in time the Timer is scheduled to start at. If no
+    /// start is scheduled, [`None`] is returned instead.
+    pub fn scheduled_start(&self) -> Option<AtomicDateTime> {
+        Some(self.scheduled_start.as_ref()?.at)
+    }
+
+    /// Starts the Timer if a start is scheduled and its point in time has been
+    /// reached. The attempt is treated as if it started exactly at the
+    /// scheduled point in time, regardless of how late this is called. If the
+    /// attempt got started, the [`Started`](Event::Started) event is returned.
+    /// Otherwise nothing happens and [`None`] is returned.
+    pub fn poll_scheduled_start(&mut self) -> Option<Event> {
+        let scheduled_start = self.scheduled_start?;
+        let remaining = scheduled_start.remaining();
+        if remaining > TimeSpan::zero() {
+            return None;
+        }
+        self.start_at(TimeStamp::now(), scheduled_start.at, -remaining)
+            .ok()
+    }
+
     /// If an attempt is in progress, stores the current time as the time of the
     /// current split. The attempt ends if the last split time is stored.
     pub fn split(&mut self) -> Result {
+        self.poll_scheduled_start();
+        self.split_at(TimeStamp::now(), AtomicDateTime::now())
+    }
+
+    /// Stores the time at the points in time provided as the time of the
+    /// current split. This allows splitting at exactly the same time another
+    /// timer gets started.
+    fn split_at(&mut self, now: TimeStamp, attempt_ended: AtomicDateTime) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
-        let (split_index, current_time, event) = active_attempt.prepare_split(&self.run)?;
+        let (split_index, current_time, event) =
+            active_attempt.prepare_split(&self.run, now, attempt_ended)?;
 
         // FIXME: We shouldn't need to collect here.
         let variables = self
@@ -325,8 +483,9 @@ impl Timer {
         *segment.variables_mut() = variables;
 
         self.run.mark_as_modified();
+        self.history.clear();
 
-        Ok(event)
+        Ok(self.notify(event))
     }
 
     /// Starts a new attempt or stores the current time as the time of the
@@ -342,6 +501,8 @@ impl Timer {
     /// Skips the current split if an attempt is in progress and the
     /// current split is not the last split.
     pub fn skip_split(&mut self) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.active_attempt.clone();
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         let Some(current_split_index) = active_attempt.current_split_index_mut() else {
@@ -356,8 +517,10 @@ impl Timer {
             *current_split_index += 1;
 
             self.run.mark_as_modified();
+            self.history
+                .record(Operation::SkipSplit, history::State::Attempt(previous));
 
-            Ok(Event::SplitSkipped)
+            Ok(self.notify(Event::SplitSkipped))
         } else {
             Err(Error::CantSkipLastSplit)
         }
@@ -367,6 +530,7 @@ impl Timer {
     /// and there is a previous split. The Timer Phase also switches to
     /// [`Running`] if it previously was [`Ended`].
     pub fn undo_split(&mut self) -> Result {
+        self.poll_scheduled_start();
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if let Some(previous_split_index) = active_attempt
@@ -382,14 +546,16 @@ impl Timer {
                 current_split_index: previous_split_index,
                 time_paused_at,
             };
+            active_attempt.undo_split(previous_split_index);
 
             self.run
                 .segment_mut(previous_split_index)
                 .clear_split_info();
 
             self.run.mark_as_modified();
+            self.history.clear();
 
-            Ok(Event::SplitUndone)
+            Ok(self.notify(Event::SplitUndone))
         } else {
             Err(Error::CantUndoFirstSplit)
         }
@@ -432,12 +598,13 @@ impl Timer {
     }
 
     /// Checks whether the current attempt has new best segment times in any of
-    /// the segments (for both [`TimingMethods`](TimingMethod)) or a new
+    /// the segments (for any of the [`TimingMethods`](TimingMethod)) or a new
     /// Personal Best (for the current [`TimingMethod`]). This can be used to
     /// ask the user whether to update the splits when resetting.
     pub fn current_attempt_has_new_best_times(&self) -> bool {
-        self.current_attempt_has_new_best_segments(TimingMethod::RealTime)
-            || self.current_attempt_has_new_best_segments(TimingMethod::GameTime)
+        TimingMethod::all()
+            .into_iter()
+            .any(|method| self.current_attempt_has_new_best_segments(method))
             || self.current_attempt_has_new_personal_best(self.current_timing_method)
     }
 
@@ -446,10 +613,14 @@ impl Timer {
     /// in the Run's history. Otherwise the current attempt's information is
     /// discarded.
     pub fn reset(&mut self, update_splits: bool) -> Result {
+        self.poll_scheduled_start();
         if self.active_attempt.is_some() {
-            self.reset_state(update_splits);
-            self.reset_splits();
-            Ok(Event::Reset)
+            let active_attempt = self.active_attempt.clone();
+            let mut changes = history::RunChanges::record(&self.run);
+            self.reset_state(update_splits, changes.history_mut());
+            self.reset_splits(changes.history_mut());
+            self.record_run_change(Operation::Reset, changes, active_attempt);
+            Ok(self.notify(Event::Reset))
         } else {
             Err(Error::NoRunInProgress)
         }
@@ -459,48 +630,81 @@ impl Timer {
     /// updated such that the current attempt's split times are being stored as
     /// the new Personal Best.
     pub fn reset_and_set_attempt_as_pb(&mut self) -> Result {
+        self.poll_scheduled_start();
         if self.active_attempt.is_some() {
-            self.reset_state(true);
-            set_run_as_pb(&mut self.run);
-            self.reset_splits();
-            Ok(Event::Reset)
+            let active_attempt = self.active_attempt.clone();
+            let mut changes = history::RunChanges::record(&self.run);
+            self.reset_state(true, changes.history_mut());
+            set_run_as_pb(&mut self.run, changes.history_mut());
+            self.reset_splits(changes.history_mut());
+            self.record_run_change(Operation::Reset, changes, active_attempt);
+            Ok(self.notify(Event::Reset))
         } else {
             Err(Error::NoRunInProgress)
         }
     }
 
-    fn reset_state(&mut self, update_times: bool) {
+    /// Records the changes of an operation that changed the Run. The changes
+    /// need to have been recorded while the operation got applied and the
+    /// active attempt provided is the one before the operation got applied.
+    fn record_run_change(
+        &mut self,
+        operation: Operation,
+        changes: history::RunChanges,
+        active_attempt: Option<ActiveAttempt>,
+    ) {
+        let run = changes.finish(&self.run);
+        self.history.record(
+            operation,
+            history::State::Run {
+                run,
+                active_attempt,
+            },
+        );
+    }
+
+    fn reset_state(&mut self, update_times: bool, changes: &mut HistoryChanges) {
         let Some(active_attempt) = self.active_attempt.take() else {
             return;
         };
 
         if update_times {
-            active_attempt.update_times(&mut self.run, self.current_timing_method);
+            active_attempt.update_times(&mut self.run, self.current_timing_method, changes);
         }
     }
 
-    fn reset_splits(&mut self) {
+    fn reset_splits(&mut self, changes: &mut HistoryChanges) {
         // Reset Splits
         for segment in self.run.segments_mut() {
             segment.clear_split_info();
         }
 
-        self.run.fix_splits();
+        self.run.fix_splits_recording(changes);
         self.run.regenerate_comparisons();
     }
 
     /// Pauses an active attempt that is not paused.
     pub fn pause(&mut self) -> Result {
+        self.poll_scheduled_start();
+        self.pause_at(TimeStamp::now())
+    }
+
+    /// Pauses an active attempt that is not paused at the point in time
+    /// provided. This allows pausing at exactly the same time as other timers.
+    fn pause_at(&mut self, now: TimeStamp) -> Result {
+        let previous = self.active_attempt.clone();
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+        let current_time = active_attempt.elapsed_at(now) + active_attempt.adjusted_offset;
 
         let State::NotEnded { time_paused_at, .. } = &mut active_attempt.state else {
             return Err(Error::RunFinished);
         };
 
         if time_paused_at.is_none() {
-            *time_paused_at =
-                Some(TimeStamp::now() - active_attempt.start_time + active_attempt.adjusted_offset);
-            Ok(Event::Paused)
+            *time_paused_at = Some(current_time);
+            self.history
+                .record(Operation::Pause, history::State::Attempt(previous));
+            Ok(self.notify(Event::Paused))
         } else {
             Err(Error::AlreadyPaused)
         }
@@ -508,24 +712,24 @@ impl Timer {
 
     /// Resumes an attempt that is paused.
     pub fn resume(&mut self) -> Result {
-        let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
-
-        let State::NotEnded { time_paused_at, .. } = &mut active_attempt.state else {
-            return Err(Error::RunFinished);
-        };
+        self.poll_scheduled_start();
+        let previous = self.active_attempt.clone();
+        self.unpause()?;
+        self.history
+            .record(Operation::Resume, history::State::Attempt(previous));
+        Ok(self.notify(Event::Resumed))
+    }
 
-        if let Some(pause_time) = *time_paused_at {
-            active_attempt.adjusted_offset =
-                pause_time - (TimeStamp::now() - active_attempt.start_time);
-            *time_paused_at = None;
-            Ok(Event::Resumed)
-        } else {
-            Err(Error::NotPaused)
-        }
+    fn unpause(&mut self) -> Result<(), Error> {
+        self.active_attempt
+            .as_mut()
+            .ok_or(Error::NoRunInProgress)?
+            .unpause()
     }
 
     /// Toggles an active attempt between `Paused` and `Running`.
     pub fn toggle_pause(&mut self) -> Result {
+        self.poll_scheduled_start();
         match self.current_phase() {
             Running => self.pause(),
             Paused => self.resume(),
@@ -546,47 +750,80 @@ impl Timer {
     }
 
     /// Removes all the pause times from the current time. If the current
-    /// attempt is paused, it also resumes that attempt. Additionally, if the
-    /// attempt is finished, the final split time is adjusted to not include the
-    /// pause times as well.
-    ///
-    /// # Warning
-    ///
-    /// This behavior is not entirely optimal, as generally only the final split
-    /// time is modified, while all other split times are left unmodified, which
-    /// may not be what actually happened during the run.
+    /// attempt is paused, it also resumes that attempt. Every split time that
+    /// got split after a pause is adjusted to include the time the attempt was
+    /// paused for, as if the attempt had never been paused.
     pub fn undo_all_pauses(&mut self) -> Result {
-        let event = match self.current_phase() {
-            Paused => {
-                self.resume()?;
-                Event::PausesUndoneAndResumed
-            }
-            Ended => {
-                let pause_time = Some(self.get_pause_time().unwrap_or_default());
-
-                let split_time = self
-                    .run
-                    .segments_mut()
-                    .iter_mut()
-                    .last()
-                    .unwrap()
-                    .split_time_mut();
-
-                *split_time += Time::new()
-                    .with_real_time(pause_time)
-                    .with_game_time(pause_time);
-
-                Event::PausesUndone
-            }
-            _ => Event::PausesUndone,
+        self.poll_scheduled_start();
+        let event = if self.current_phase() == Paused {
+            self.unpause()?;
+            Event::PausesUndoneAndResumed
+        } else {
+            Event::PausesUndone
         };
 
-        if let Some(active_attempt) = &mut self.active_attempt {
-            active_attempt.adjusted_offset = active_attempt.original_offset;
-            Ok(event)
-        } else {
-            Err(Error::NoRunInProgress)
+        let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+        if active_attempt.undo_pauses(&mut self.run) {
+            self.run.mark_as_modified();
+        }
+        self.history.clear();
+        Ok(self.notify(event))
+    }
+
+    /// Corrects the split time of a segment of an attempt that has ended, for
+    /// example to account for loads that got retimed from a recording of the
+    /// attempt. The corrected split time replaces the split time of the
+    /// [`TimingMethod`] specified, so once the attempt gets reset, it is what
+    /// gets stored in the segment history and compared against the best
+    /// segments and the Personal Best. The split time can't be earlier than
+    /// any of the split times before it or later than any of the split times
+    /// after it, as that would result in negative segment times. The split time
+    /// of the last segment can't be cleared, as it is the final time of the
+    /// attempt. An error is returned if the segment index provided is out of
+    /// bounds.
+    pub fn retime_split(
+        &mut self,
+        segment_index: usize,
+        timing_method: TimingMethod,
+        split_time: Option<TimeSpan>,
+    ) -> Result {
+        match self.current_phase() {
+            Ended => {}
+            NotRunning => return Err(Error::NoRunInProgress),
+            Running | Paused => return Err(Error::RunNotFinished),
+        }
+
+        let segments = self.run.segments();
+        if segment_index >= segments.len() {
+            return Err(Error::InvalidIndex);
+        }
+
+        if split_time.is_none() && segment_index + 1 == segments.len() {
+            return Err(Error::CantSkipLastSplit);
         }
+
+        if let Some(split_time) = split_time {
+            let previous = segments[..segment_index]
+                .iter()
+                .rev()
+                .find_map(|s| s.split_time()[timing_method])
+                .unwrap_or_default();
+            let next = segments[segment_index + 1..]
+                .iter()
+                .find_map(|s| s.split_time()[timing_method]);
+
+            if split_time < previous || next.is_some_and(|next| split_time > next) {
+                return Err(Error::NegativeTime);
+            }
+        }
+
+        let changes = history::RunChanges::record(&self.run);
+        self.run.segment_mut(segment_index).split_time_mut()[timing_method] = split_time;
+        self.run.mark_as_modified();
+        let active_attempt = self.active_attempt.clone();
+        self.record_run_change(Operation::Retime, changes, active_attempt);
+
+        Ok(self.notify(Event::SplitRetimed))
     }
 
     /// Switches the current comparison to the next comparison in the list.
@@ -597,11 +834,15 @@ impl Timer {
             .position(|c| c == self.current_comparison)
             .unwrap();
         let index = (index + 1) % len;
+        let previous = self.current_comparison.clone();
         self.run
             .comparisons()
             .nth(index)
             .unwrap()
             .populate(&mut self.current_comparison);
+        self.history
+            .record(Operation::Comparison, history::State::Comparison(previous));
+        self.notify(Event::ComparisonChanged);
     }
 
     /// Switches the current comparison to the previous comparison in the list.
@@ -612,11 +853,15 @@ impl Timer {
             .position(|c| c == self.current_comparison)
             .unwrap();
         let index = (index + len - 1) % len;
+        let previous = self.current_comparison.clone();
         self.run
             .comparisons()
             .nth(index)
             .unwrap()
             .populate(&mut self.current_comparison);
+        self.history
+            .record(Operation::Comparison, history::State::Comparison(previous));
+        self.notify(Event::ComparisonChanged);
     }
 
     /// Returns the total duration of the current attempt. This is not affected
@@ -634,7 +879,7 @@ impl Timer {
         if let State::Ended { attempt_ended } = active_attempt.state {
             attempt_ended - active_attempt.attempt_started
         } else {
-            TimeStamp::now() - active_attempt.start_time
+            active_attempt.elapsed()
         }
     }
 
@@ -658,11 +903,14 @@ impl Timer {
     /// gets uninitialized for each new attempt.
     #[inline]
     pub fn initialize_game_time(&mut self) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::GameTime);
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.loading_times.is_none() {
             active_attempt.loading_times = Some(TimeSpan::zero());
-            Ok(Event::GameTimeInitialized)
+            self.record_game_time_change(previous);
+            Ok(self.notify(Event::GameTimeInitialized))
         } else {
             Err(Error::GameTimeAlreadyInitialized)
         }
@@ -670,9 +918,11 @@ impl Timer {
 
     /// Deinitializes Game Time for the current attempt.
     #[inline]
-    pub const fn deinitialize_game_time(&mut self) {
+    pub fn deinitialize_game_time(&mut self) {
+        let previous = self.attempt_before(Operation::GameTime);
         if let Some(active_attempt) = &mut self.active_attempt {
             active_attempt.loading_times = None;
+            self.record_game_time_change(previous);
         }
     }
 
@@ -689,6 +939,8 @@ impl Timer {
     /// Pauses the Game Timer such that it doesn't automatically increment
     /// similar to Real Time.
     pub fn pause_game_time(&mut self) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::GameTime);
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.game_time_paused_at.is_none() {
@@ -696,8 +948,9 @@ impl Timer {
 
             active_attempt.game_time_paused_at =
                 current_time.game_time.or(Some(current_time.real_time));
+            self.record_game_time_change(previous);
 
-            Ok(Event::GameTimePaused)
+            Ok(self.notify(Event::GameTimePaused))
         } else {
             Err(Error::GameTimeAlreadyPaused)
         }
@@ -706,6 +959,8 @@ impl Timer {
     /// Resumes the Game Timer such that it automatically increments similar to
     /// Real Time, starting from the Game Time it was paused at.
     pub fn resume_game_time(&mut self) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::GameTime);
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.game_time_paused_at.is_some() {
@@ -714,8 +969,9 @@ impl Timer {
             let diff = catch! { current_time.real_time - current_time.game_time? };
             active_attempt.set_loading_times(diff.unwrap_or_default(), &self.run);
             active_attempt.game_time_paused_at = None;
+            self.record_game_time_change(previous);
 
-            Ok(Event::GameTimeResumed)
+            Ok(self.notify(Event::GameTimeResumed))
         } else {
             Err(Error::GameTimeNotPaused)
         }
@@ -727,6 +983,8 @@ impl Timer {
     /// the Game Timer never shows any time that is not coming from the game.
     #[inline]
     pub fn set_game_time(&mut self, game_time: TimeSpan) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::GameTime);
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.game_time_paused_at.is_some() {
@@ -734,8 +992,9 @@ impl Timer {
         }
         active_attempt.loading_times =
             Some(active_attempt.current_time(&self.run).real_time - game_time);
+        self.record_game_time_change(previous);
 
-        Ok(Event::GameTimeSet)
+        Ok(self.notify(Event::GameTimeSet))
     }
 
     /// Accesses the loading times. Loading times are defined as Game Time - Real Time.
@@ -752,14 +1011,96 @@ impl Timer {
     /// is then automatically determined by Real Time - Loading Times.
     #[inline]
     pub fn set_loading_times(&mut self, time: TimeSpan) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::GameTime);
         if let Some(active_attempt) = &mut self.active_attempt {
             active_attempt.set_loading_times(time, &self.run);
-            Ok(Event::LoadingTimesSet)
+            self.record_game_time_change(previous);
+            Ok(self.notify(Event::LoadingTimesSet))
         } else {
             Err(Error::NoRunInProgress)
         }
     }
 
+    /// Returns whether the Load Removed Time is currently initialized. The Load
+    /// Removed Time automatically gets uninitialized for each new attempt.
+    #[inline]
+    pub const fn is_load_removed_time_initialized(&self) -> bool {
+        match &self.active_attempt {
+            Some(active_attempt) => active_attempt.removed_loading_times.is_some(),
+            None => false,
+        }
+    }
+
+    /// Initializes the Load Removed Time for the current attempt. The Load
+    /// Removed Time automatically gets uninitialized for each new attempt.
+    pub fn initialize_load_removed_time(&mut self) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::LoadRemovedTime);
+        let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+
+        if active_attempt.removed_loading_times.is_none() {
+            active_attempt.removed_loading_times = Some(TimeSpan::zero());
+            self.record_load_removed_time_change(previous);
+            Ok(self.notify(Event::LoadRemovedTimeInitialized))
+        } else {
+            Err(Error::LoadRemovedTimeAlreadyInitialized)
+        }
+    }
+
+    /// Returns whether the Load Removed Time is currently paused, which is the
+    /// case while the game is loading. If it is not paused, it automatically
+    /// increments similar to Real Time.
+    #[inline]
+    pub const fn is_load_removed_time_paused(&self) -> bool {
+        match &self.active_attempt {
+            Some(active_attempt) => active_attempt.load_removed_time_paused_at.is_some(),
+            None => false,
+        }
+    }
+
+    /// Pauses the Load Removed Time when the game starts loading, such that it
+    /// doesn't automatically increment similar to Real Time.
+    pub fn pause_load_removed_time(&mut self) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::LoadRemovedTime);
+        let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+
+        if active_attempt.load_removed_time_paused_at.is_none() {
+            let current_time = active_attempt.current_time(&self.run);
+
+            active_attempt.load_removed_time_paused_at = current_time
+                .load_removed_time
+                .or(Some(current_time.real_time));
+            self.record_load_removed_time_change(previous);
+
+            Ok(self.notify(Event::LoadRemovedTimePaused))
+        } else {
+            Err(Error::LoadRemovedTimeAlreadyPaused)
+        }
+    }
+
+    /// Resumes the Load Removed Time when the game stops loading, such that it
+    /// automatically increments similar to Real Time, starting from the Load
+    /// Removed Time it was paused at.
+    pub fn resume_load_removed_time(&mut self) -> Result {
+        self.poll_scheduled_start();
+        let previous = self.attempt_before(Operation::LoadRemovedTime);
+        let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+
+        if let Some(paused_at) = active_attempt.load_removed_time_paused_at {
+            let current_time = active_attempt.current_time(&self.run);
+
+            active_attempt.removed_loading_times = Some(current_time.real_time - paused_at);
+            active_attempt.load_removed_time_paused_at = None;
+            self.record_load_removed_time_change(previous);
+
+            Ok(self.notify(Event::LoadRemovedTimeResumed))
+        } else {
+            Err(Error::LoadRemovedTimeNotPaused)
+        }
+    }
+
     /// Sets the value of a custom variable with the name specified. If the
     /// variable does not exist, a temporary variable gets created that will not
     /// be stored in the splits file.
@@ -773,6 +1114,7 @@ impl Timer {
         if var.is_permanent {
             self.run.mark_as_modified();
         }
+        self.notify(Event::CustomVariableSet);
     }
 
     /// Notifies the `Timer` that the currently loaded [`Layout`](crate::Layout)
@@ -791,9 +1133,164 @@ impl Timer {
     }
 }
 
-fn set_run_as_pb(run: &mut Run) {
-    run.import_pb_into_segment_history();
-    run.fix_splits();
+impl Timer {
+    /// Subscribes to all the events of the timer. The subscriber gets called
+    /// with every [`Event`] right after the change happened, along with a
+    /// [`Snapshot`] of the timer, so it can look up the relevant information,
+    /// such as the current split index or the current time. This also includes
+    /// changes that are made directly on the timer rather than through a
+    /// [`CommandSink`](crate::event::CommandSink). Any amount of subscribers
+    /// can be subscribed at once. For a [`SharedTimer`], the subscriber is
+    /// called while the timer is locked, so it must not try to lock the timer
+    /// itself. A clone of the timer does not notify the subscribers of the
+    /// original timer.
+    pub fn subscribe<F>(&mut self, subscriber: F) -> SubscriptionId
+    where
+        F: FnMut(Event, &Snapshot<'_>) + Send + Sync + 'static,
+    {
+        self.subscribers.add(Box::new(subscriber))
+    }
+
+    /// Unsubscribes the subscriber with the identifier provided from the events
+    /// of the timer. Returns whether the subscriber was still subscribed.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        self.subscribers.remove(id)
+    }
+
+    fn notify(&mut self, event: Event) -> Event {
+        if !self.subscribers.is_empty() {
+            let mut subscribers = mem::take(&mut self.subscribers);
+            subscribers.notify(event, &self.snapshot());
+            self.subscribers = subscribers;
+        }
+        event
+    }
+}
+
+impl Timer {
+    /// Creates a journal of the attempt in progress, so that it can be
+    /// restored with [`restore_attempt`](Self::restore_attempt) in case the
+    /// application crashes or gets closed. If there is no attempt in progress,
+    /// [`None`] is returned instead.
+    pub fn attempt_journal(&self) -> Option<AttemptJournal> {
+        let active_attempt = self.active_attempt.as_ref()?;
+        Some(AttemptJournal::new(active_attempt, &self.run))
+    }
+
+    /// Restores the attempt stored in the journal provided, so it can be
+    /// continued. This requires that there is no attempt in progress and that
+    /// the Run has the same game name, category name and segments as the Run
+    /// the journal was created with. The split times and custom variables stored in the
+    /// journal are applied to the Run. The time between creating the journal
+    /// and restoring it is treated according to the [`GapPolicy`] provided.
+    pub fn restore_attempt(
+        &mut self,
+        journal: &AttemptJournal,
+        gap_policy: GapPolicy,
+    ) -> Result<Event, RestoreError> {
+        if self.active_attempt.is_some() {
+            return Err(RestoreError::RunAlreadyInProgress);
+        }
+
+        self.active_attempt = Some(journal.restore(&mut self.run, gap_policy)?);
+        self.scheduled_start = None;
+        self.history.clear();
+
+        Ok(self.notify(Event::AttemptRestored))
+    }
+
+    /// Undoes the most recent operation that can be undone. These are
+    /// resetting the attempt, skipping a split, pausing and resuming the
+    /// attempt, changing the game time, retiming a split and changing the
+    /// comparison. Undoing a reset restores the attempt, as well as the Run as
+    /// it was before the reset. Consecutive changes of the game time are
+    /// undone together. All other changes to the timer, such as starting an
+    /// attempt or splitting, can't be undone this way and discard the
+    /// operations that could be undone or redone. Only a limited amount of
+    /// operations is kept.
+    pub fn undo(&mut self) -> Result {
+        let entry = self.history.pop_undo().ok_or(Error::NothingToUndo)?;
+        let entry = self.restore(entry);
+        self.history.push_redo(entry);
+        Ok(self.notify(Event::OperationUndone))
+    }
+
+    /// Redoes the most recent operation that got undone. Any new operation
+    /// discards the operations that could be redone.
+    pub fn redo(&mut self) -> Result {
+        let entry = self.history.pop_redo().ok_or(Error::NothingToRedo)?;
+        let entry = self.restore(entry);
+        self.history.push_undo(entry);
+        Ok(self.notify(Event::OperationRedone))
+    }
+
+    /// Returns whether there is an operation that can be undone.
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    /// Returns whether there is an operation that can be redone.
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Captures the active attempt before a change of the game time or the
+    /// load removed time. If the change gets merged into the most recent entry
+    /// of the history, the captured state would be discarded anyway, so the
+    /// active attempt doesn't get cloned then.
+    fn attempt_before(&self, operation: Operation) -> Option<ActiveAttempt> {
+        if self.history.merges(operation) {
+            None
+        } else {
+            self.active_attempt.clone()
+        }
+    }
+
+    fn record_game_time_change(&mut self, previous: Option<ActiveAttempt>) {
+        self.history
+            .record(Operation::GameTime, history::State::Attempt(previous));
+    }
+
+    fn record_load_removed_time_change(&mut self, previous: Option<ActiveAttempt>) {
+        self.history.record(
+            Operation::LoadRemovedTime,
+            history::State::Attempt(previous),
+        );
+    }
+
+    /// Restores the state stored in the entry and returns an entry with the
+    /// state that got replaced, so the restoring can be reverted.
+    fn restore(&mut self, Entry { operation, state }: Entry) -> Entry {
+        let state = match state {
+            history::State::Attempt(active_attempt) => {
+                history::State::Attempt(mem::replace(&mut self.active_attempt, active_attempt))
+            }
+            history::State::Comparison(comparison) => {
+                history::State::Comparison(mem::replace(&mut self.current_comparison, comparison))
+            }
+            history::State::Run {
+                run,
+                active_attempt,
+            } => {
+                let run = run.apply(&mut self.run);
+                self.run.regenerate_comparisons();
+                self.run.mark_as_modified();
+                history::State::Run {
+                    run,
+                    active_attempt: mem::replace(&mut self.active_attempt, active_attempt),
+                }
+            }
+        };
+        if self.active_attempt.is_some() {
+            self.scheduled_start = None;
+        }
+        Entry { operation, state }
+    }
+}
+
+fn set_run_as_pb(run: &mut Run, changes: &mut HistoryChanges) {
+    run.import_pb_into_segment_history_recording(changes);
+    run.fix_splits_recording(changes);
     for segment in run.segments_mut() {
         let split_time = segment.split_time();
         segment.set_personal_best_split_time(split_time);
@@ -0,0 +1,58 @@
+use super::Snapshot;
+use crate::{event::Event, platform::prelude::*};
+use core::fmt;
+
+type Subscriber = Box<dyn FnMut(Event, &Snapshot<'_>) + Send + Sync>;
+
+/// Identifies a subscription to the events of a [`Timer`](super::Timer). It
+/// can be used to unsubscribe again.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// The subscribers that get notified about every event of a timer. Cloning
+/// them results in no subscribers, as the subscribers only ever observe the
+/// timer they subscribed to.
+#[derive(Default)]
+pub struct Subscribers {
+    next_id: u64,
+    list: Vec<(SubscriptionId, Subscriber)>,
+}
+
+impl Subscribers {
+    pub fn add(&mut self, subscriber: Subscriber) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+        self.list.push((id, subscriber));
+        id
+    }
+
+    pub fn remove(&mut self, id: SubscriptionId) -> bool {
+        let len = self.list.len();
+        self.list.retain(|(other, _)| *other != id);
+        self.list.len() != len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn notify(&mut self, event: Event, snapshot: &Snapshot<'_>) {
+        for (_, subscriber) in &mut self.list {
+            subscriber(event, snapshot);
+        }
+    }
+}
+
+impl Clone for Subscribers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for Subscribers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscribers")
+            .field("len", &self.list.len())
+            .finish()
+    }
+}
@@ -0,0 +1,85 @@
+use super::timer;
+use crate::{
+    AtomicDateTime, DateTime, TimeSpan,
+    platform::Duration,
+    timing::clock::{ManualClock, with_clock},
+};
+
+fn secs(seconds: f64) -> TimeSpan {
+    TimeSpan::from_seconds(seconds)
+}
+
+#[test]
+fn splits_are_deterministic() {
+    let clock = ManualClock::new(DateTime::UNIX_EPOCH);
+
+    with_clock(clock.clone(), || {
+        let mut timer = timer();
+        timer.start().unwrap();
+        clock.advance(secs(10.0));
+        timer.split().unwrap();
+
+        timer.pause().unwrap();
+        clock.advance(secs(5.0));
+        timer.resume().unwrap();
+
+        clock.advance(secs(2.5));
+        timer.split().unwrap();
+        clock.advance(secs(7.5));
+        timer.split().unwrap();
+
+        let run = timer.run();
+        assert_eq!(run.segment(0).split_time().real_time, Some(secs(10.0)));
+        assert_eq!(run.segment(1).split_time().real_time, Some(secs(12.5)));
+        assert_eq!(run.segment(2).split_time().real_time, Some(secs(20.0)));
+        assert_eq!(timer.get_pause_time(), Some(secs(5.0)));
+
+        timer.reset(true).unwrap();
+        let attempt = timer.run().attempt_history().last().unwrap();
+        assert_eq!(
+            attempt.started(),
+            Some(AtomicDateTime::new(DateTime::UNIX_EPOCH, false))
+        );
+        assert_eq!(
+            attempt.ended(),
+            Some(AtomicDateTime::new(
+                DateTime::UNIX_EPOCH + Duration::seconds(25),
+                false
+            ))
+        );
+    });
+}
+
+#[test]
+fn adjusting_the_date_does_not_affect_the_time() {
+    let clock = ManualClock::new(DateTime::UNIX_EPOCH);
+
+    with_clock(clock.clone(), || {
+        let mut timer = timer();
+        timer.start().unwrap();
+        clock.advance(secs(3.0));
+        clock.set_date(DateTime::UNIX_EPOCH - Duration::hours(1));
+        assert_eq!(timer.snapshot().current_time().real_time, Some(secs(3.0)));
+        assert_eq!(
+            AtomicDateTime::now().time,
+            DateTime::UNIX_EPOCH - Duration::hours(1)
+        );
+    });
+}
+
+#[test]
+fn the_previous_clock_gets_restored() {
+    let outer = ManualClock::new(DateTime::UNIX_EPOCH);
+    let inner = ManualClock::new(DateTime::UNIX_EPOCH + Duration::days(1));
+
+    with_clock(outer, || {
+        with_clock(inner, || {
+            assert_eq!(
+                AtomicDateTime::now().time,
+                DateTime::UNIX_EPOCH + Duration::days(1)
+            );
+        });
+        assert_eq!(AtomicDateTime::now().time, DateTime::UNIX_EPOCH);
+    });
+    assert_ne!(AtomicDateTime::now().time, DateTime::UNIX_EPOCH);
+}
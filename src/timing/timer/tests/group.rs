@@ -0,0 +1,107 @@
+use super::{super::State, timer};
+use crate::{
+    TimerGroup,
+    TimerPhase::*,
+    event::{Error, Event},
+};
+
+fn group(len: usize) -> TimerGroup {
+    TimerGroup::new((0..len).map(|_| timer()).collect()).unwrap()
+}
+
+fn phases(group: &TimerGroup) -> Vec<crate::TimerPhase> {
+    group.timers().iter().map(|t| t.current_phase()).collect()
+}
+
+#[test]
+fn needs_at_least_one_timer() {
+    assert!(TimerGroup::new(Vec::new()).is_err());
+}
+
+#[test]
+fn starts_all_timers_at_the_same_time() {
+    let mut group = group(3);
+    assert_eq!(group.start(), Ok(Event::Started));
+    assert_eq!(phases(&group), [Running, Running, Running]);
+
+    let start_times: Vec<_> = group
+        .timers()
+        .iter()
+        .map(|t| t.active_attempt.as_ref().unwrap().start_time)
+        .collect();
+    assert!(start_times.iter().all(|&t| t == start_times[0]));
+
+    assert_eq!(group.pause(), Ok(Event::Paused));
+    let pause_times: Vec<_> = group
+        .timers()
+        .iter()
+        .map(|t| match t.active_attempt.as_ref().unwrap().state {
+            State::NotEnded { time_paused_at, .. } => time_paused_at.unwrap(),
+            State::Ended { .. } => panic!("The attempt should not have ended"),
+        })
+        .collect();
+    assert!(pause_times.iter().all(|&t| t == pause_times[0]));
+}
+
+#[test]
+fn commands_apply_to_all_timers_or_none() {
+    let mut group = group(2);
+    group.timers_mut()[1].start().unwrap();
+
+    assert_eq!(group.start(), Err(Error::RunAlreadyInProgress));
+    assert_eq!(phases(&group), [NotRunning, Running]);
+
+    assert_eq!(group.pause(), Ok(Event::Paused));
+    assert_eq!(phases(&group), [NotRunning, Paused]);
+    assert_eq!(group.pause(), Err(Error::AlreadyPaused));
+
+    assert_eq!(group.resume(), Ok(Event::Resumed));
+    assert_eq!(group.resume(), Err(Error::NotPaused));
+
+    assert_eq!(group.reset(true), Ok(Event::Reset));
+    assert_eq!(phases(&group), [NotRunning, NotRunning]);
+    assert_eq!(group.reset(true), Err(Error::NoRunInProgress));
+    assert_eq!(group.pause(), Err(Error::NoRunInProgress));
+    assert_eq!(group.split(2), Err(Error::InvalidIndex));
+}
+
+#[test]
+fn relay_hands_off_to_the_next_runner() {
+    let mut group = group(2);
+    group.set_relay(true);
+
+    group.start().unwrap();
+    assert_eq!(phases(&group), [Running, NotRunning]);
+
+    group.split(0).unwrap();
+    group.split(0).unwrap();
+    assert_eq!(phases(&group), [Running, NotRunning]);
+
+    assert_eq!(group.split(0), Ok(Event::Finished));
+    assert_eq!(phases(&group), [Ended, Running]);
+
+    let (finished, next) = (&group.timers()[0], &group.timers()[1]);
+    let finished_attempt = finished.active_attempt.as_ref().unwrap();
+    let next_attempt = next.active_attempt.as_ref().unwrap();
+    let State::Ended { attempt_ended } = finished_attempt.state else {
+        panic!("The attempt of the first runner should have ended");
+    };
+    assert_eq!(next_attempt.attempt_started, attempt_ended);
+    assert_eq!(
+        Some(next_attempt.start_time - finished_attempt.start_time),
+        finished.run().segment(2).split_time().real_time,
+    );
+
+    for _ in 0..3 {
+        group.split(1).unwrap();
+    }
+    assert_eq!(phases(&group), [Ended, Ended]);
+
+    group.reset(true).unwrap();
+    assert!(
+        group
+            .timers()
+            .iter()
+            .all(|t| t.run().attempt_history().len() == 1)
+    );
+}
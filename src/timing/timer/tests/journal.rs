@@ -0,0 +1,195 @@
+use super::{run, timer};
+use crate::{
+    TimeSpan, Timer, TimerPhase,
+    event::Event,
+    timing::{AttemptJournal, GapPolicy, RestoreError},
+    util::tests_helper::start_run,
+};
+
+pub(super) fn journal_written_a_minute_ago(timer: &Timer) -> AttemptJournal {
+    let mut json = Vec::new();
+    timer
+        .attempt_journal()
+        .unwrap()
+        .write_json(&mut json)
+        .unwrap();
+    let mut value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let seconds = &mut value["written"]["seconds"];
+    *seconds = (seconds.as_i64().unwrap() - 60).into();
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn no_journal_without_an_attempt() {
+    assert!(timer().attempt_journal().is_none());
+}
+
+#[test]
+fn restores_the_attempt() {
+    let mut original = run();
+    original
+        .metadata_mut()
+        .custom_variable_mut("Route")
+        .permanent()
+        .set_value("Any%");
+    let mut timer = Timer::new(original).unwrap();
+    start_run(&mut timer);
+    timer.set_custom_variable("Coins", "3");
+    timer.set_game_time(TimeSpan::from_seconds(5.0)).unwrap();
+    timer.split().unwrap();
+    timer.skip_split().unwrap();
+    timer.pause().unwrap();
+
+    let mut json = Vec::new();
+    timer
+        .attempt_journal()
+        .unwrap()
+        .write_json(&mut json)
+        .unwrap();
+    let journal = AttemptJournal::from_json(&*json).unwrap();
+
+    let mut restored = Timer::new(run()).unwrap();
+    assert_eq!(
+        restored
+            .restore_attempt(&journal, GapPolicy::Running)
+            .unwrap(),
+        Event::AttemptRestored,
+    );
+
+    assert_eq!(restored.current_phase(), TimerPhase::Paused);
+    assert_eq!(restored.current_split_index(), Some(2));
+    assert_eq!(restored.run().attempt_count(), 1);
+    assert!(restored.run().has_been_modified());
+    assert!(restored.is_game_time_initialized());
+    assert!(restored.is_game_time_paused());
+    assert_eq!(
+        restored.run().segment(0).split_time(),
+        timer.run().segment(0).split_time(),
+    );
+    assert_eq!(restored.run().segment(0).variables()["Coins"], "3");
+    assert_eq!(restored.run().segment(1).split_time().real_time, None);
+    let metadata = restored.run().metadata();
+    assert_eq!(metadata.custom_variable_value("Coins"), Some("3"));
+    assert!(!metadata.custom_variable("Coins").unwrap().is_permanent);
+    assert_eq!(metadata.custom_variable_value("Route"), Some("Any%"));
+    assert!(metadata.custom_variable("Route").unwrap().is_permanent);
+    assert_eq!(
+        restored.snapshot().current_time(),
+        timer.snapshot().current_time(),
+    );
+
+    restored.resume().unwrap();
+    restored.split().unwrap();
+    assert_eq!(restored.current_phase(), TimerPhase::Ended);
+}
+
+#[test]
+fn restores_a_finished_attempt() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    for seconds in [1.0, 2.0, 3.0] {
+        timer
+            .set_game_time(TimeSpan::from_seconds(seconds))
+            .unwrap();
+        timer.split().unwrap();
+    }
+
+    let mut restored = Timer::new(run()).unwrap();
+    restored
+        .restore_attempt(&timer.attempt_journal().unwrap(), GapPolicy::Paused)
+        .unwrap();
+
+    assert_eq!(restored.current_phase(), TimerPhase::Ended);
+    assert_eq!(
+        restored.snapshot().current_time().game_time,
+        Some(TimeSpan::from_seconds(3.0)),
+    );
+}
+
+#[test]
+fn gap_counted_as_running() {
+    let mut timer = timer();
+    timer.start().unwrap();
+
+    let mut restored = Timer::new(run()).unwrap();
+    restored
+        .restore_attempt(&journal_written_a_minute_ago(&timer), GapPolicy::Running)
+        .unwrap();
+
+    assert_eq!(restored.current_phase(), TimerPhase::Running);
+    assert!(restored.snapshot().current_time().real_time.unwrap() >= TimeSpan::from_seconds(60.0));
+    assert_eq!(restored.get_pause_time(), None);
+}
+
+#[test]
+fn gap_counted_as_paused() {
+    let mut timer = timer();
+    timer.start().unwrap();
+
+    let mut restored = Timer::new(run()).unwrap();
+    restored
+        .restore_attempt(&journal_written_a_minute_ago(&timer), GapPolicy::Paused)
+        .unwrap();
+
+    assert_eq!(restored.current_phase(), TimerPhase::Running);
+    assert!(restored.snapshot().current_time().real_time.unwrap() < TimeSpan::from_seconds(60.0));
+    assert!(restored.get_pause_time().unwrap() >= TimeSpan::from_seconds(60.0));
+
+    restored.undo_all_pauses().unwrap();
+    assert!(restored.snapshot().current_time().real_time.unwrap() >= TimeSpan::from_seconds(60.0));
+}
+
+#[test]
+fn attempt_already_in_progress() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    let journal = timer.attempt_journal().unwrap();
+
+    assert!(matches!(
+        timer.restore_attempt(&journal, GapPolicy::Running),
+        Err(RestoreError::RunAlreadyInProgress),
+    ));
+}
+
+#[test]
+fn different_segment_count() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    let journal = timer.attempt_journal().unwrap();
+
+    let mut run = run();
+    run.push_segment(crate::Segment::new("D"));
+    let mut restored = Timer::new(run).unwrap();
+
+    assert!(matches!(
+        restored.restore_attempt(&journal, GapPolicy::Running),
+        Err(RestoreError::SegmentCount { journal: 3, run: 4 }),
+    ));
+    assert_eq!(restored.current_phase(), TimerPhase::NotRunning);
+}
+
+#[test]
+fn different_run() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    let journal = timer.attempt_journal().unwrap();
+
+    let mut renamed = run();
+    renamed.segment_mut(1).set_name("Renamed");
+    let mut restored = Timer::new(renamed).unwrap();
+
+    assert!(matches!(
+        restored.restore_attempt(&journal, GapPolicy::Running),
+        Err(RestoreError::DifferentRun),
+    ));
+
+    let mut renamed = run();
+    renamed.set_category_name("Other Category");
+    let mut restored = Timer::new(renamed).unwrap();
+
+    assert!(matches!(
+        restored.restore_attempt(&journal, GapPolicy::Running),
+        Err(RestoreError::DifferentRun),
+    ));
+    assert_eq!(restored.current_phase(), TimerPhase::NotRunning);
+}
@@ -0,0 +1,97 @@
+use super::{run, timer};
+use crate::{
+    Time, TimeSpan, Timer, TimingMethod,
+    event::{Error, Event},
+    util::tests_helper::start_run,
+};
+
+#[test]
+fn is_not_initialized_by_default() {
+    let mut timer = timer();
+    start_run(&mut timer);
+
+    assert!(!timer.is_load_removed_time_initialized());
+    assert_eq!(timer.snapshot().current_time().load_removed_time, None);
+
+    timer.split().unwrap();
+    assert_eq!(timer.run().segment(0).split_time().load_removed_time, None);
+}
+
+#[test]
+fn is_tracked_separately_from_game_time() {
+    let mut timer = timer();
+    start_run(&mut timer);
+
+    assert_eq!(
+        timer.initialize_load_removed_time(),
+        Ok(Event::LoadRemovedTimeInitialized),
+    );
+    assert_eq!(
+        timer.initialize_load_removed_time(),
+        Err(Error::LoadRemovedTimeAlreadyInitialized),
+    );
+    timer.set_game_time(TimeSpan::from_seconds(5.0)).unwrap();
+
+    assert_eq!(
+        timer.pause_load_removed_time(),
+        Ok(Event::LoadRemovedTimePaused),
+    );
+    assert!(timer.is_load_removed_time_paused());
+    assert_eq!(
+        timer.pause_load_removed_time(),
+        Err(Error::LoadRemovedTimeAlreadyPaused),
+    );
+    let paused_at = timer.snapshot().current_time().load_removed_time.unwrap();
+
+    timer.split().unwrap();
+    let split_time = timer.run().segment(0).split_time();
+    assert_eq!(split_time.load_removed_time, Some(paused_at));
+    assert_eq!(split_time.game_time, Some(TimeSpan::from_seconds(5.0)));
+
+    assert_eq!(
+        timer.resume_load_removed_time(),
+        Ok(Event::LoadRemovedTimeResumed),
+    );
+    assert_eq!(
+        timer.resume_load_removed_time(),
+        Err(Error::LoadRemovedTimeNotPaused),
+    );
+
+    let time = timer.snapshot().current_time();
+    assert!(time.load_removed_time.unwrap() >= paused_at);
+    assert!(time.load_removed_time.unwrap() <= time.real_time.unwrap());
+}
+
+#[test]
+fn updates_the_best_segments_on_reset() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.initialize_load_removed_time().unwrap();
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+
+    assert!(timer.run().segment(0).best_segment_time()[TimingMethod::LoadRemovedTime].is_some());
+}
+
+#[test]
+fn new_best_segments_are_new_best_times() {
+    let mut run = run();
+    run.segment_mut(0)
+        .set_best_segment_time(Time::zero().with_load_removed_time(None));
+    let mut timer = Timer::new(run).unwrap();
+    start_run(&mut timer);
+    timer.initialize_load_removed_time().unwrap();
+    timer.split().unwrap();
+
+    assert!(!timer.current_attempt_has_new_best_segments(TimingMethod::RealTime));
+    assert!(!timer.current_attempt_has_new_best_segments(TimingMethod::GameTime));
+    assert!(timer.current_attempt_has_new_best_times());
+}
+
+#[test]
+fn toggling_the_timing_method_goes_back_to_real_time() {
+    let mut timer = timer();
+    timer.set_current_timing_method(TimingMethod::LoadRemovedTime);
+    timer.toggle_timing_method();
+    assert_eq!(timer.current_timing_method(), TimingMethod::RealTime);
+}
@@ -6,8 +6,17 @@ use crate::{
     Run, Segment, TimeSpan, Timer, TimerPhase, TimingMethod,
 };
 
+mod clock;
 mod events;
+mod group;
+mod journal;
+mod load_removed_time;
 mod mark_as_modified;
+mod pauses;
+mod retime;
+mod scheduled_start;
+mod subscriptions;
+mod undo;
 mod variables;
 
 fn run() -> Run {
@@ -0,0 +1,108 @@
+use super::{journal::journal_written_a_minute_ago, run, timer};
+use crate::{TimeSpan, Timer, TimerPhase, timing::GapPolicy};
+
+/// Continues the attempt of the timer after a pause of a minute.
+fn pause_for_a_minute(timer: &Timer) -> Timer {
+    let mut restored = Timer::new(run()).unwrap();
+    restored
+        .restore_attempt(&journal_written_a_minute_ago(timer), GapPolicy::Paused)
+        .unwrap();
+    restored
+}
+
+fn split_times(timer: &Timer) -> Vec<Option<TimeSpan>> {
+    timer
+        .run()
+        .segments()
+        .iter()
+        .map(|segment| segment.split_time().real_time)
+        .collect()
+}
+
+#[test]
+fn undoing_pauses_only_adjusts_the_splits_after_the_pause() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.split().unwrap();
+
+    let mut timer = pause_for_a_minute(&timer);
+    timer.split().unwrap();
+    timer.split().unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Ended);
+
+    let before = split_times(&timer);
+    assert!(
+        before
+            .iter()
+            .all(|t| t.unwrap() < TimeSpan::from_seconds(60.0))
+    );
+
+    timer.undo_all_pauses().unwrap();
+    let after = split_times(&timer);
+    assert_eq!(after[0], before[0]);
+    for (before, after) in before.iter().zip(&after).skip(1) {
+        assert!(after.unwrap() - before.unwrap() >= TimeSpan::from_seconds(60.0));
+    }
+    assert_eq!(timer.get_pause_time(), None);
+    assert!(timer.run().has_been_modified());
+}
+
+#[test]
+fn undoing_a_split_moves_the_pause_to_the_split_again() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.split().unwrap();
+    timer.split().unwrap();
+
+    let mut timer = pause_for_a_minute(&timer);
+    timer.undo_split().unwrap();
+    timer.undo_split().unwrap();
+    timer.split().unwrap();
+
+    timer.undo_all_pauses().unwrap();
+    assert!(timer.run().segment(0).split_time().real_time.unwrap() >= TimeSpan::from_seconds(60.0));
+}
+
+#[test]
+fn undoing_pauses_while_running_keeps_the_splits_before_the_pause() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.split().unwrap();
+
+    let mut timer = pause_for_a_minute(&timer);
+    let before = split_times(&timer);
+
+    timer.undo_all_pauses().unwrap();
+    assert_eq!(split_times(&timer), before);
+    assert!(timer.snapshot().current_time().real_time.unwrap() >= TimeSpan::from_seconds(60.0));
+}
+
+#[test]
+fn pauses_are_stored_in_the_attempt_history() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.pause().unwrap();
+    timer.resume().unwrap();
+    timer.pause().unwrap();
+    timer.reset(true).unwrap();
+
+    let pauses = timer.run().attempt_history()[0].pauses();
+    assert_eq!(pauses.len(), 2);
+    assert!(pauses[0].start <= pauses[0].end);
+    assert!(pauses[0].end <= pauses[1].start);
+    assert!(pauses[1].start <= pauses[1].end);
+}
+
+#[test]
+fn pauses_are_journaled() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.split().unwrap();
+
+    let mut timer = pause_for_a_minute(&pause_for_a_minute(&timer));
+    timer.reset(true).unwrap();
+
+    let pauses = timer.run().attempt_history()[0].pauses();
+    assert_eq!(pauses.len(), 2);
+    assert!(pauses[1].duration() >= TimeSpan::from_seconds(60.0));
+}
@@ -0,0 +1,145 @@
+use super::timer;
+use crate::{
+    Timer, TimerPhase, TimingMethod,
+    event::{Error, Event},
+    util::tests_helper::{make_progress_run_with_splits_opt, span, start_run},
+};
+
+fn ended_timer() -> Timer {
+    let mut timer = timer();
+    start_run(&mut timer);
+    make_progress_run_with_splits_opt(&mut timer, &[Some(1.0), Some(2.0), Some(3.0)]);
+    timer
+}
+
+#[test]
+fn requires_an_attempt_that_has_ended() {
+    let mut timer = timer();
+    assert_eq!(
+        timer.retime_split(0, TimingMethod::GameTime, Some(span(1.0))),
+        Err(Error::NoRunInProgress),
+    );
+
+    start_run(&mut timer);
+    timer.split().unwrap();
+    assert_eq!(
+        timer.retime_split(0, TimingMethod::GameTime, Some(span(1.0))),
+        Err(Error::RunNotFinished),
+    );
+}
+
+#[test]
+fn segment_index_needs_to_be_in_bounds() {
+    let mut timer = ended_timer();
+    assert_eq!(
+        timer.retime_split(3, TimingMethod::GameTime, Some(span(4.0))),
+        Err(Error::InvalidIndex),
+    );
+    assert!(!timer.can_undo());
+}
+
+#[test]
+fn split_times_need_to_stay_in_order() {
+    let mut timer = ended_timer();
+
+    assert_eq!(
+        timer.retime_split(1, TimingMethod::GameTime, Some(span(0.5))),
+        Err(Error::NegativeTime),
+    );
+    assert_eq!(
+        timer.retime_split(1, TimingMethod::GameTime, Some(span(3.5))),
+        Err(Error::NegativeTime),
+    );
+    assert_eq!(
+        timer.retime_split(1, TimingMethod::GameTime, Some(span(3.0))),
+        Ok(Event::SplitRetimed),
+    );
+}
+
+#[test]
+fn final_time_cant_be_cleared() {
+    let mut timer = ended_timer();
+    assert_eq!(
+        timer.retime_split(2, TimingMethod::GameTime, None),
+        Err(Error::CantSkipLastSplit),
+    );
+    assert_eq!(timer.current_phase(), TimerPhase::Ended);
+    assert_eq!(
+        timer.run().segment(2).split_time().game_time,
+        Some(span(3.0)),
+    );
+    assert!(!timer.can_undo());
+}
+
+#[test]
+fn retimed_split_times_get_stored_when_resetting() {
+    let mut timer = ended_timer();
+    timer.mark_as_unmodified();
+
+    timer
+        .retime_split(1, TimingMethod::GameTime, Some(span(1.5)))
+        .unwrap();
+    assert!(timer.run().has_been_modified());
+    assert_eq!(
+        timer.run().segment(1).split_time().game_time,
+        Some(span(1.5)),
+    );
+
+    timer.reset(true).unwrap();
+    let run = timer.run();
+    let index = run.attempt_history()[0].index();
+
+    assert_eq!(
+        run.segment(1)
+            .segment_history()
+            .get(index)
+            .unwrap()
+            .game_time,
+        Some(span(0.5)),
+    );
+    assert_eq!(
+        run.segment(2)
+            .segment_history()
+            .get(index)
+            .unwrap()
+            .game_time,
+        Some(span(1.5)),
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(0.5))
+    );
+    assert_eq!(
+        run.segment(1).personal_best_split_time().game_time,
+        Some(span(1.5)),
+    );
+}
+
+#[test]
+fn retiming_the_last_split_changes_the_final_time() {
+    let mut timer = ended_timer();
+
+    timer
+        .retime_split(2, TimingMethod::GameTime, Some(span(2.5)))
+        .unwrap();
+    timer.reset(true).unwrap();
+
+    assert_eq!(
+        timer.run().attempt_history()[0].time().game_time,
+        Some(span(2.5)),
+    );
+}
+
+#[test]
+fn can_be_undone() {
+    let mut timer = ended_timer();
+
+    timer.retime_split(1, TimingMethod::GameTime, None).unwrap();
+    assert_eq!(timer.run().segment(1).split_time().game_time, None);
+
+    timer.undo().unwrap();
+    assert_eq!(
+        timer.run().segment(1).split_time().game_time,
+        Some(span(2.0)),
+    );
+}
@@ -0,0 +1,106 @@
+use super::timer;
+use crate::{
+    AtomicDateTime, TimeSpan,
+    TimerPhase::*,
+    event::{Error, Event},
+    platform::Duration,
+};
+
+fn in_seconds(seconds: i64) -> AtomicDateTime {
+    AtomicDateTime::new(
+        AtomicDateTime::now().time + Duration::seconds(seconds),
+        false,
+    )
+}
+
+#[test]
+fn counts_down_to_the_scheduled_start() {
+    let mut timer = timer();
+    assert_eq!(
+        timer.schedule_start(in_seconds(3600)),
+        Ok(Event::StartScheduled)
+    );
+    assert!(timer.scheduled_start().is_some());
+
+    let snapshot = timer.snapshot();
+    let countdown = snapshot.countdown().unwrap();
+    assert!(countdown > TimeSpan::from_seconds(3590.0));
+    assert!(countdown <= TimeSpan::from_seconds(3600.0));
+    assert_eq!(snapshot.current_time().real_time, Some(-countdown));
+
+    assert_eq!(timer.poll_scheduled_start(), None);
+    assert_eq!(timer.split(), Err(Error::NoRunInProgress));
+    assert_eq!(timer.current_phase(), NotRunning);
+}
+
+#[test]
+fn starts_at_the_scheduled_point_in_time() {
+    let mut timer = timer();
+    timer.schedule_start(in_seconds(-10)).unwrap();
+    let snapshot = timer.snapshot();
+    assert_eq!(snapshot.countdown(), Some(TimeSpan::zero()));
+    assert!(snapshot.current_time().real_time.unwrap() >= TimeSpan::from_seconds(10.0));
+
+    assert_eq!(timer.poll_scheduled_start(), Some(Event::Started));
+    assert_eq!(timer.current_phase(), Running);
+    assert_eq!(timer.scheduled_start(), None);
+    assert_eq!(timer.snapshot().countdown(), None);
+
+    // The attempt is treated as if it started when it was scheduled to.
+    let time = timer.snapshot().current_time().real_time.unwrap();
+    assert!(time >= TimeSpan::from_seconds(10.0));
+    assert!(time < TimeSpan::from_seconds(20.0));
+}
+
+#[test]
+fn gets_started_by_the_next_command() {
+    let mut timer = timer();
+    timer.schedule_start(in_seconds(-10)).unwrap();
+
+    assert_eq!(timer.split(), Ok(Event::Splitted));
+    assert_eq!(timer.scheduled_start(), None);
+    assert_eq!(timer.current_split_index(), Some(1));
+
+    // The split happened after the attempt started at the scheduled point in
+    // time.
+    let split_time = timer.run().segment(0).split_time().real_time.unwrap();
+    assert!(split_time >= TimeSpan::from_seconds(10.0));
+    assert!(split_time < TimeSpan::from_seconds(20.0));
+}
+
+#[test]
+fn starting_after_the_scheduled_point_in_time_keeps_it() {
+    let mut timer = timer();
+    timer.schedule_start(in_seconds(-10)).unwrap();
+
+    assert_eq!(timer.start(), Ok(Event::Started));
+    let time = timer.snapshot().current_time().real_time.unwrap();
+    assert!(time >= TimeSpan::from_seconds(10.0));
+}
+
+#[test]
+fn can_be_cancelled() {
+    let mut timer = timer();
+    assert_eq!(timer.cancel_scheduled_start(), Err(Error::NoStartScheduled));
+
+    timer.schedule_start(in_seconds(3600)).unwrap();
+    assert_eq!(
+        timer.cancel_scheduled_start(),
+        Ok(Event::ScheduledStartCancelled)
+    );
+    assert_eq!(timer.scheduled_start(), None);
+    assert_eq!(timer.poll_scheduled_start(), None);
+    assert_eq!(timer.current_phase(), NotRunning);
+}
+
+#[test]
+fn starting_manually_cancels_the_scheduled_start() {
+    let mut timer = timer();
+    timer.schedule_start(in_seconds(3600)).unwrap();
+    timer.start().unwrap();
+    assert_eq!(timer.scheduled_start(), None);
+    assert_eq!(
+        timer.schedule_start(in_seconds(3600)),
+        Err(Error::RunAlreadyInProgress)
+    );
+}
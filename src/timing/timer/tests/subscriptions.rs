@@ -0,0 +1,153 @@
+use super::timer;
+use crate::{
+    TimeSpan, Timer, TimerPhase, TimingMethod,
+    event::{CommandSink, Event},
+    timing::SubscriptionId,
+};
+use std::sync::{Arc, Mutex};
+
+type Log = Arc<Mutex<Vec<(Event, TimerPhase, Option<usize>)>>>;
+
+fn subscribe(timer: &mut Timer) -> (Log, SubscriptionId) {
+    let log = Log::default();
+    let id = timer.subscribe({
+        let log = log.clone();
+        move |event, snapshot| {
+            log.lock().unwrap().push((
+                event,
+                snapshot.current_phase(),
+                snapshot.current_split_index(),
+            ))
+        }
+    });
+    (log, id)
+}
+
+#[test]
+fn receives_changes_made_directly_on_the_timer() {
+    let mut timer = timer();
+    let (log, _) = subscribe(&mut timer);
+
+    timer.start().unwrap();
+    timer.split().unwrap();
+    timer.pause().unwrap();
+    timer.undo_all_pauses().unwrap();
+    timer.skip_split().unwrap();
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            (Event::Started, TimerPhase::Running, Some(0)),
+            (Event::Splitted, TimerPhase::Running, Some(1)),
+            (Event::Paused, TimerPhase::Paused, Some(1)),
+            (Event::PausesUndoneAndResumed, TimerPhase::Running, Some(1)),
+            (Event::SplitSkipped, TimerPhase::Running, Some(2)),
+            (Event::Finished, TimerPhase::Ended, Some(3)),
+            (Event::Reset, TimerPhase::NotRunning, None),
+        ],
+    );
+}
+
+#[test]
+fn failed_operations_are_not_reported() {
+    let mut timer = timer();
+    let (log, _) = subscribe(&mut timer);
+
+    timer.split().unwrap_err();
+    timer.set_current_comparison("Doesn't exist").unwrap_err();
+
+    assert!(log.lock().unwrap().is_empty());
+}
+
+#[test]
+fn snapshot_has_the_time_of_the_split() {
+    let mut timer = timer();
+    let times = Arc::new(Mutex::new(Vec::new()));
+    timer.subscribe({
+        let times = times.clone();
+        move |event, snapshot| {
+            if event == Event::Splitted {
+                times.lock().unwrap().push((
+                    snapshot.current_time(),
+                    snapshot.run().segment(0).split_time(),
+                ));
+            }
+        }
+    });
+
+    timer.start().unwrap();
+    timer.initialize_game_time().unwrap();
+    timer.pause_game_time().unwrap();
+    timer.set_game_time(TimeSpan::from_seconds(5.0)).unwrap();
+    timer.split().unwrap();
+
+    let times = times.lock().unwrap();
+    let (current_time, split_time) = times[0];
+    assert_eq!(current_time.game_time, split_time.game_time);
+    assert_eq!(split_time.game_time, Some(TimeSpan::from_seconds(5.0)));
+}
+
+#[test]
+fn multiple_subscribers() {
+    let mut timer = timer();
+    let (first, first_id) = subscribe(&mut timer);
+    let (second, _) = subscribe(&mut timer);
+
+    timer.switch_to_next_comparison();
+    assert!(timer.unsubscribe(first_id));
+    timer.switch_to_previous_comparison();
+    timer.start().unwrap();
+
+    assert_eq!(
+        *first.lock().unwrap(),
+        [(Event::ComparisonChanged, TimerPhase::NotRunning, None)],
+    );
+    assert_eq!(
+        *second.lock().unwrap(),
+        [
+            (Event::ComparisonChanged, TimerPhase::NotRunning, None),
+            (Event::ComparisonChanged, TimerPhase::NotRunning, None),
+            (Event::Started, TimerPhase::Running, Some(0)),
+        ],
+    );
+    assert!(!timer.unsubscribe(first_id));
+}
+
+#[test]
+fn clones_dont_notify_the_subscribers() {
+    let mut timer = timer();
+    let (log, _) = subscribe(&mut timer);
+
+    let mut clone = timer.clone();
+    clone.start().unwrap();
+
+    assert!(log.lock().unwrap().is_empty());
+}
+
+#[test]
+fn timing_method_changes_are_reported() {
+    let shared = timer().into_shared();
+    let (log, _) = subscribe(&mut shared.write().unwrap());
+
+    shared.write().unwrap().toggle_timing_method();
+    drop(CommandSink::toggle_timing_method(&shared));
+    drop(CommandSink::set_current_timing_method(
+        &shared,
+        TimingMethod::LoadRemovedTime,
+    ));
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            (Event::TimingMethodChanged, TimerPhase::NotRunning, None),
+            (Event::TimingMethodChanged, TimerPhase::NotRunning, None),
+            (Event::TimingMethodChanged, TimerPhase::NotRunning, None),
+        ],
+    );
+    assert_eq!(
+        shared.read().unwrap().current_timing_method(),
+        TimingMethod::LoadRemovedTime,
+    );
+}
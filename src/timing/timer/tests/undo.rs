@@ -0,0 +1,184 @@
+use super::timer;
+use crate::{
+    TimeSpan, TimerPhase,
+    comparison::{best_segments, personal_best},
+    event::{Error, Event},
+    util::tests_helper::{make_progress_run_with_splits_opt, run_with_splits, start_run},
+};
+
+#[test]
+fn nothing_to_undo_or_redo() {
+    let mut timer = timer();
+
+    assert!(!timer.can_undo());
+    assert!(!timer.can_redo());
+    assert_eq!(timer.undo(), Err(Error::NothingToUndo));
+    assert_eq!(timer.redo(), Err(Error::NothingToRedo));
+}
+
+#[test]
+fn reset_restores_the_attempt() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(TimeSpan::from_seconds(5.0)).unwrap();
+    timer.set_custom_variable("Route", "Glitchless");
+    timer.split().unwrap();
+
+    timer.reset(true).unwrap();
+    assert_eq!(timer.run().attempt_history().len(), 1);
+    assert!(timer.run().segment(0).variables().is_empty());
+
+    assert_eq!(timer.undo(), Ok(Event::OperationUndone));
+    assert_eq!(timer.current_phase(), TimerPhase::Running);
+    assert_eq!(timer.current_split_index(), Some(1));
+    assert_eq!(timer.run().attempt_history().len(), 0);
+    assert_eq!(
+        timer.run().segment(0).split_time().game_time,
+        Some(TimeSpan::from_seconds(5.0)),
+    );
+    assert_eq!(
+        timer.run().segment(0).variables().get("Route").unwrap(),
+        "Glitchless",
+    );
+    assert!(timer.run().has_been_modified());
+
+    assert_eq!(timer.redo(), Ok(Event::OperationRedone));
+    assert_eq!(timer.current_phase(), TimerPhase::NotRunning);
+    assert_eq!(timer.run().attempt_history().len(), 1);
+    assert_eq!(timer.run().segment(0).split_time().game_time, None);
+}
+
+#[test]
+fn undoing_a_personal_best_reset_restores_the_run() {
+    let mut timer = timer();
+    run_with_splits(&mut timer, &[5.0, 10.0, 15.0]);
+
+    let mut run = timer.run().clone();
+    run.metadata_mut().set_run_id("34567");
+    timer.set_run(run).unwrap();
+
+    start_run(&mut timer);
+    make_progress_run_with_splits_opt(&mut timer, &[Some(4.0), None, Some(12.0)]);
+    let before = timer.run().clone();
+
+    timer.reset(true).unwrap();
+    assert_eq!(timer.run().metadata().run_id(), "");
+    let after = timer.run().clone();
+
+    timer.undo().unwrap();
+    assert_eq!(*timer.run(), before);
+
+    timer.redo().unwrap();
+    assert_eq!(*timer.run(), after);
+}
+
+#[test]
+fn skip_split() {
+    let mut timer = timer();
+    start_run(&mut timer);
+
+    timer.skip_split().unwrap();
+    assert_eq!(timer.current_split_index(), Some(1));
+
+    timer.undo().unwrap();
+    assert_eq!(timer.current_split_index(), Some(0));
+
+    timer.redo().unwrap();
+    assert_eq!(timer.current_split_index(), Some(1));
+}
+
+#[test]
+fn pause_and_resume() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.pause().unwrap();
+    timer.resume().unwrap();
+
+    timer.undo().unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Paused);
+    timer.undo().unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Running);
+    assert!(!timer.can_undo());
+
+    timer.redo().unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Paused);
+}
+
+#[test]
+fn consecutive_game_time_changes_are_undone_together() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.initialize_game_time().unwrap();
+    timer.pause_game_time().unwrap();
+    timer.set_game_time(TimeSpan::from_seconds(1.0)).unwrap();
+    timer.switch_to_next_comparison();
+    timer.set_game_time(TimeSpan::from_seconds(5.0)).unwrap();
+    timer.set_game_time(TimeSpan::from_seconds(6.0)).unwrap();
+
+    timer.undo().unwrap();
+    assert_eq!(
+        timer.snapshot().current_time().game_time,
+        Some(TimeSpan::from_seconds(1.0)),
+    );
+
+    timer.undo().unwrap();
+    assert_eq!(timer.current_comparison(), personal_best::NAME);
+
+    timer.undo().unwrap();
+    assert!(!timer.is_game_time_initialized());
+    assert!(!timer.can_undo());
+}
+
+#[test]
+fn comparison() {
+    let mut timer = timer();
+
+    timer.set_current_comparison(best_segments::NAME).unwrap();
+    timer.switch_to_previous_comparison();
+
+    timer.undo().unwrap();
+    assert_eq!(timer.current_comparison(), best_segments::NAME);
+    timer.undo().unwrap();
+    assert_eq!(timer.current_comparison(), personal_best::NAME);
+}
+
+#[test]
+fn new_operations_discard_the_redo_history() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.pause().unwrap();
+
+    timer.undo().unwrap();
+    assert!(timer.can_redo());
+
+    timer.skip_split().unwrap();
+    assert!(!timer.can_redo());
+}
+
+#[test]
+fn changes_that_cant_be_undone_discard_the_history() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    timer.skip_split().unwrap();
+    timer.pause().unwrap();
+    timer.undo().unwrap();
+
+    timer.split().unwrap();
+    assert!(!timer.can_undo());
+    assert!(!timer.can_redo());
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut timer = timer();
+
+    for _ in 0..100 {
+        timer.switch_to_next_comparison();
+    }
+
+    let mut undone = 0;
+    while timer.undo().is_ok() {
+        undone += 1;
+    }
+    assert!(undone < 100);
+}
@@ -1,7 +1,8 @@
 use serde_derive::{Deserialize, Serialize};
 
 /// A `TimingMethod` describes which form of timing is used. This can either be
-/// [`TimingMethod::RealTime`] or [`TimingMethod::GameTime`].
+/// [`TimingMethod::RealTime`], [`TimingMethod::GameTime`] or
+/// [`TimingMethod::LoadRemovedTime`].
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[repr(u8)]
 pub enum TimingMethod {
@@ -12,11 +13,20 @@ pub enum TimingMethod {
     /// being run. This is entirely optional and may either be `Real Time` with
     /// loading times removed or some time provided by the game.
     GameTime = 1,
+    /// `Load Removed Time` describes `Real Time` with the loading times
+    /// removed. It is tracked separately from `Game Time`, so that both can be
+    /// used at the same time, for example if the game provides its own in-game
+    /// timer that differs from the `Real Time` without the loading times.
+    LoadRemovedTime = 2,
 }
 
 impl TimingMethod {
     /// Returns an array of all the timing methods.
-    pub const fn all() -> [TimingMethod; 2] {
-        [TimingMethod::RealTime, TimingMethod::GameTime]
+    pub const fn all() -> [TimingMethod; 3] {
+        [
+            TimingMethod::RealTime,
+            TimingMethod::GameTime,
+            TimingMethod::LoadRemovedTime,
+        ]
     }
 }
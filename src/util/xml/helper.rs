@@ -2,7 +2,7 @@ use crate::platform::prelude::*;
 use alloc::borrow::Cow;
 use core::{fmt, mem::MaybeUninit, str};
 
-use super::{Attributes, Event, Reader, TagName, Text, Writer};
+use super::{Attributes, Event, Reader, TagName, Text, Value, Writer};
 
 /// The Error type for XML-based splits files that couldn't be parsed.
 #[derive(Debug, snafu::Snafu)]
@@ -128,7 +128,7 @@ pub fn reencode_children(reader: &mut Reader<'_>, target_buf: &mut String) -> Re
                 writer
                     .just_start_tag(name.name(), |tag| {
                         for (k, v) in attributes.iter() {
-                            tag.attribute(k, v)?;
+                            tag.attribute(k, &*v.unescape_cow())?;
                         }
                         Ok(())
                     })
@@ -166,6 +166,34 @@ pub fn reencode_children(reader: &mut Reader<'_>, target_buf: &mut String) -> Re
     }
 }
 
+pub fn reencode_element(
+    reader: &mut Reader<'_>,
+    tag: TagName<'_>,
+    attributes: Attributes<'_>,
+    target_buf: &mut String,
+) -> Result<(), Error> {
+    Writer::new_skip_header(&mut *target_buf)
+        .just_start_tag(tag.name(), |writer| {
+            for (k, v) in attributes.iter() {
+                writer.attribute(k, &*v.unescape_cow())?;
+            }
+            Ok(())
+        })
+        .map_err(|fmt::Error| Error::Xml)?;
+    reencode_children(reader, target_buf)?;
+    Writer::new_skip_header(target_buf)
+        .just_end_tag(tag.name())
+        .map_err(|_| Error::Xml)
+}
+
+/// Re-escapes the value of an attribute, so that it can be written back out
+/// in double quotes, regardless of which quotes it was read in.
+pub fn reencode_attribute(value: Text<'_>) -> String {
+    let mut buf = String::new();
+    let _ = (&*value.unescape_cow()).write_escaped(&mut buf);
+    buf
+}
+
 pub fn end_tag<E>(reader: &mut Reader<'_>) -> Result<(), E>
 where
     E: From<Error>,
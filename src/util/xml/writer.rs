@@ -18,6 +18,14 @@ impl<T: fmt::Write> Writer<T> {
         Ok(Self { sink })
     }
 
+    pub const fn get_ref(&self) -> &T {
+        &self.sink
+    }
+
+    pub fn into_inner(self) -> T {
+        self.sink
+    }
+
     pub fn text(&mut self, text: impl Value) -> fmt::Result {
         text.write_escaped(&mut self.sink)
     }
@@ -46,3 +46,53 @@ mod parse {
         assert_eq!(columns[1].name, "+/−");
     }
 }
+
+mod json {
+    use crate::layout_files;
+    use livesplit_core::layout::{FromJsonError, Layout, LayoutSettings};
+
+    fn json(settings: &LayoutSettings) -> String {
+        let mut buf = Vec::new();
+        settings.write_json(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn unversioned() {
+        let migrated = LayoutSettings::from_json(layout_files::TEXT_SHADOW.as_bytes()).unwrap();
+        let decoded: LayoutSettings = serde_json::from_str(layout_files::TEXT_SHADOW).unwrap();
+        assert_eq!(json(&migrated), json(&decoded));
+    }
+
+    #[test]
+    fn round_trip() {
+        let settings = Layout::default_layout().settings();
+        let written = json(&settings);
+        assert!(written.starts_with(r#"{"version":1,"#));
+
+        let parsed = LayoutSettings::from_json(written.as_bytes()).unwrap();
+        assert_eq!(json(&parsed), written);
+    }
+
+    #[test]
+    fn newer_version() {
+        let written = json(&Layout::default_layout().settings()).replacen(
+            r#""version":1"#,
+            r#""version":999"#,
+            1,
+        );
+        let error = LayoutSettings::from_json(written.as_bytes()).err().unwrap();
+        assert!(matches!(
+            error,
+            FromJsonError::UnsupportedVersion { version: 999 }
+        ));
+    }
+
+    #[test]
+    fn invalid_version() {
+        let error = LayoutSettings::from_json(r#"{"version":"1"}"#.as_bytes())
+            .err()
+            .unwrap();
+        assert!(matches!(error, FromJsonError::InvalidVersion));
+    }
+}
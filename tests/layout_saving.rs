@@ -0,0 +1,128 @@
+mod layout_files;
+
+mod round_trip {
+    use crate::layout_files;
+    use livesplit_core::layout::{Layout, parser, saver};
+
+    fn json(layout: &Layout) -> String {
+        let mut buf = Vec::new();
+        layout.settings().write_json(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[track_caller]
+    fn livesplit(data: &str) {
+        let layout = parser::parse(data).unwrap();
+        let mut buf = String::new();
+        saver::save_layout(&layout, &mut buf).unwrap();
+        let parsed = parser::parse(&buf).unwrap();
+        assert_eq!(json(&parsed), json(&layout));
+    }
+
+    #[test]
+    fn all() {
+        livesplit(layout_files::ALL);
+    }
+
+    #[test]
+    fn dark() {
+        livesplit(layout_files::DARK);
+    }
+
+    #[test]
+    fn subsplits() {
+        livesplit(layout_files::SUBSPLITS);
+    }
+
+    #[test]
+    fn wsplit() {
+        livesplit(layout_files::WSPLIT);
+    }
+
+    #[test]
+    fn with_timer_delta_background() {
+        livesplit(layout_files::WITH_TIMER_DELTA_BACKGROUND);
+    }
+
+    #[test]
+    fn with_background_image() {
+        livesplit(layout_files::WITH_BACKGROUND_IMAGE);
+    }
+
+    #[test]
+    fn text_shadow() {
+        use livesplit_core::layout::LayoutSettings;
+
+        let settings = LayoutSettings::from_json(layout_files::TEXT_SHADOW.as_bytes()).unwrap();
+        let layout = Layout::from_settings(settings);
+
+        // The colors lose precision on the first save, so only saving it again
+        // is expected to be lossless.
+        let mut first = String::new();
+        saver::save_layout(&layout, &mut first).unwrap();
+        let parsed = parser::parse(&first).unwrap();
+        assert!(parsed.general_settings().text_shadow.is_some());
+        let mut second = String::new();
+        saver::save_layout(&parsed, &mut second).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn default_layout() {
+        // The colors lose precision on the first save, so only saving it again
+        // is expected to be lossless.
+        let mut first = String::new();
+        saver::save_layout(&Layout::default_layout(), &mut first).unwrap();
+        let mut second = String::new();
+        saver::save_layout(&parser::parse(&first).unwrap(), &mut second).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn active_runner_is_saved_as_text() {
+        use livesplit_core::{
+            component::{active_runner, text::Text},
+            layout::Component,
+        };
+
+        let mut layout = Layout::new();
+        layout.push(active_runner::Component::new());
+
+        let mut buf = String::new();
+        saver::save_layout(&layout, &mut buf).unwrap();
+        let parsed = parser::parse(&buf).unwrap();
+
+        let [Component::Text(component)] = &*parsed.components else {
+            panic!("The Active Runner component should be saved as a Text component");
+        };
+        assert!(matches!(
+            &component.settings().text,
+            Text::Center(text) if text == "Active Runner",
+        ));
+    }
+
+    #[test]
+    fn fonts() {
+        use livesplit_core::settings::{Font, FontStretch, FontStyle, FontWeight};
+
+        let mut layout = parser::parse(layout_files::ALL).unwrap();
+        let settings = layout.general_settings_mut();
+        settings.timer_font = Some(Font {
+            family: String::from("Bahnschrift"),
+            style: FontStyle::Normal,
+            weight: FontWeight::SemiLight,
+            stretch: FontStretch::Condensed,
+        });
+        settings.text_font = Some(Font {
+            family: String::from("Arial"),
+            style: FontStyle::Italic,
+            weight: FontWeight::Bold,
+            stretch: FontStretch::Normal,
+        });
+
+        let mut buf = String::new();
+        saver::save_layout(&layout, &mut buf).unwrap();
+        let parsed = parser::parse(&buf).unwrap();
+        assert_eq!(json(&parsed), json(&layout));
+    }
+}
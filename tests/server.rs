@@ -0,0 +1,563 @@
+#![cfg(feature = "server")]
+
+use livesplit_core::{
+    Layout, Run, Segment, SharedTimer, Time, TimeSpan, Timer, TimerPhase,
+    component::timer::Component as TimerComponent,
+    networking::{server::Server, server_protocol::MessageFormat},
+};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+};
+
+fn timer() -> SharedTimer {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("Cascade Kingdom"));
+    run.push_segment(Segment::new("Sand Kingdom"));
+    Timer::new(run).unwrap().into_shared()
+}
+
+fn server(timer: &SharedTimer) -> Server {
+    let server = Server::bind("127.0.0.1:0", timer.clone()).unwrap();
+    let events = server.event_sender();
+    timer
+        .write()
+        .unwrap()
+        .subscribe(move |event, _| events.send(event));
+    server
+}
+
+struct TcpClient {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl TcpClient {
+    fn connect(server: &Server) -> Self {
+        let stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream.set_nodelay(true).unwrap();
+        let reader = BufReader::new(stream.try_clone().unwrap());
+        Self { stream, reader }
+    }
+
+    fn send(&mut self, command: &str) {
+        self.stream.write_all(command.as_bytes()).unwrap();
+        self.stream.write_all(b"\n").unwrap();
+    }
+
+    fn receive(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_owned()
+    }
+}
+
+struct WebSocketClient {
+    stream: TcpStream,
+}
+
+impl WebSocketClient {
+    fn connect(server: &Server) -> (Self, String) {
+        Self::connect_with_headers(server, "")
+    }
+
+    fn connect_with_headers(server: &Server, headers: &str) -> (Self, String) {
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        write!(
+            stream,
+            "GET / HTTP/1.1\r\n\
+             Host: localhost\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+             Sec-WebSocket-Version: 13\r\n\
+             {headers}\r\n",
+        )
+        .unwrap();
+
+        let mut response = Vec::new();
+        while !response.ends_with(b"\r\n\r\n") {
+            let mut byte = [0];
+            stream.read_exact(&mut byte).unwrap();
+            response.push(byte[0]);
+        }
+
+        (Self { stream }, String::from_utf8(response).unwrap())
+    }
+
+    fn send(&mut self, opcode: u8, payload: &[u8]) {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().zip(mask.iter().cycle()).map(|(b, m)| b ^ m));
+        self.stream.write_all(&frame).unwrap();
+    }
+
+    fn receive(&mut self) -> (u8, Vec<u8>) {
+        let mut header = [0; 2];
+        self.stream.read_exact(&mut header).unwrap();
+        assert_eq!(header[1] & 0x80, 0);
+        let mut payload = vec![0; (header[1] & 0x7F) as usize];
+        self.stream.read_exact(&mut payload).unwrap();
+        (header[0], payload)
+    }
+}
+
+#[test]
+fn tcp_commands_get_responses() {
+    let timer = timer();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "command": "getCurrentState" }"#);
+    assert_eq!(client.receive(), r#"{"success":{"state":"NotRunning"}}"#);
+
+    client.send(r#"{ "command": "start" }"#);
+    assert_eq!(client.receive(), r#"{"event":"Started"}"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+    assert_eq!(timer.read().unwrap().current_phase(), TimerPhase::Running);
+
+    client.send(r#"{ "command": "start" }"#);
+    assert_eq!(
+        client.receive(),
+        r#"{"error":{"code":"RunAlreadyInProgress"}}"#,
+    );
+
+    client.send("not a command");
+    assert!(
+        client
+            .receive()
+            .starts_with(r#"{"error":{"code":"InvalidCommand""#)
+    );
+}
+
+#[test]
+fn scheduled_starts_get_polled_through_commands() {
+    let timer = timer();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "command": "pollScheduledStart" }"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+
+    client.send(r#"{ "command": "scheduleStart", "time": "2100-01-01T00:00:00Z" }"#);
+    assert_eq!(client.receive(), r#"{"event":"StartScheduled"}"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+
+    client.send(r#"{ "command": "pollScheduledStart" }"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+    assert_eq!(
+        timer.read().unwrap().current_phase(),
+        TimerPhase::NotRunning
+    );
+
+    client.send(r#"{ "command": "scheduleStart", "time": "2000-01-01T00:00:00Z" }"#);
+    assert_eq!(client.receive(), r#"{"event":"StartScheduled"}"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+
+    client.send(r#"{ "command": "pollScheduledStart" }"#);
+    assert_eq!(client.receive(), r#"{"event":"Started"}"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+    assert_eq!(timer.read().unwrap().current_phase(), TimerPhase::Running);
+    assert_eq!(timer.read().unwrap().scheduled_start(), None);
+}
+
+#[test]
+fn ids_are_sent_back_with_the_responses() {
+    let timer = timer();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "id": 1, "command": "ping" }"#);
+    client.send(r#"{ "command": "ping", "id": "second" }"#);
+    client.send(r#"{ "id": [3], "command": "getSegmentName", "index": 5 }"#);
+    client.send(r#"{ "id": 4, "command": "fly" }"#);
+
+    assert_eq!(client.receive(), r#"{"id":1,"success":null}"#);
+    assert_eq!(client.receive(), r#"{"id":"second","success":null}"#);
+    assert_eq!(
+        client.receive(),
+        r#"{"id":[3],"error":{"code":"InvalidIndex"}}"#,
+    );
+    assert!(
+        client
+            .receive()
+            .starts_with(r#"{"id":4,"error":{"code":"InvalidCommand""#)
+    );
+}
+
+#[test]
+fn tagged_messages_separate_events_from_responses() {
+    let timer = timer();
+    let server =
+        Server::bind_with_message_format("127.0.0.1:0", timer.clone(), MessageFormat::Tagged)
+            .unwrap();
+    let events = server.event_sender();
+    timer
+        .write()
+        .unwrap()
+        .subscribe(move |event, _| events.send(event));
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "id": 1, "command": "start" }"#);
+    assert_eq!(client.receive(), r#"{"type":"event","event":"Started"}"#);
+    assert_eq!(
+        client.receive(),
+        r#"{"type":"response","id":1,"success":null}"#,
+    );
+
+    client.send(r#"{ "command": "getCurrentState" }"#);
+    assert_eq!(
+        client.receive(),
+        r#"{"type":"response","success":{"state":"Running","index":0}}"#,
+    );
+}
+
+#[test]
+fn events_are_sent_to_all_clients() {
+    let timer = timer();
+    let server = server(&timer);
+
+    let mut first = TcpClient::connect(&server);
+    let mut second = TcpClient::connect(&server);
+    let (mut third, _) = WebSocketClient::connect(&server);
+
+    // Make sure that all the clients are connected before any events are sent.
+    first.send(r#"{ "command": "ping" }"#);
+    first.receive();
+    second.send(r#"{ "command": "ping" }"#);
+    second.receive();
+    third.send(0x1, br#"{ "command": "ping" }"#);
+    third.receive();
+    assert_eq!(server.client_count(), 3);
+
+    timer.write().unwrap().start().unwrap();
+    first.send(r#"{ "command": "split" }"#);
+
+    assert_eq!(first.receive(), r#"{"event":"Started"}"#);
+    assert_eq!(first.receive(), r#"{"event":"Splitted"}"#);
+    assert_eq!(first.receive(), r#"{"success":null}"#);
+
+    assert_eq!(second.receive(), r#"{"event":"Started"}"#);
+    assert_eq!(second.receive(), r#"{"event":"Splitted"}"#);
+
+    assert_eq!(third.receive(), (0x81, br#"{"event":"Started"}"#.to_vec()));
+    assert_eq!(third.receive(), (0x81, br#"{"event":"Splitted"}"#.to_vec()));
+
+    server.send_event(livesplit_core::event::Event::Reset);
+    assert_eq!(second.receive(), r#"{"event":"Reset"}"#);
+}
+
+#[test]
+fn websocket_commands_get_responses() {
+    let timer = timer();
+    let server = server(&timer);
+    let (mut client, response) = WebSocketClient::connect(&server);
+
+    assert!(response.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+    assert!(response.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+
+    client.send(0x1, br#"{ "command": "splitOrStart" }"#);
+    assert_eq!(client.receive(), (0x81, br#"{"event":"Started"}"#.to_vec()));
+    assert_eq!(client.receive(), (0x81, br#"{"success":null}"#.to_vec()));
+
+    client.send(0x9, b"hello");
+    assert_eq!(client.receive(), (0x8A, b"hello".to_vec()));
+
+    client.send(0x8, &[]);
+    assert_eq!(client.receive(), (0x88, Vec::new()));
+}
+
+#[test]
+fn websocket_origins_need_to_be_allowed() {
+    let timer = timer();
+    let server = server(&timer);
+
+    let (mut client, response) =
+        WebSocketClient::connect_with_headers(&server, "Origin: https://example.com\r\n");
+    assert!(response.starts_with("HTTP/1.1 403 Forbidden\r\n"));
+    assert_eq!(client.stream.read(&mut [0]).unwrap(), 0);
+
+    server.allow_origin("https://one.livesplit.org/");
+    let (mut client, response) =
+        WebSocketClient::connect_with_headers(&server, "Origin: https://one.livesplit.org\r\n");
+    assert!(response.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+
+    client.send(0x1, br#"{ "command": "ping" }"#);
+    assert_eq!(client.receive(), (0x81, br#"{"success":null}"#.to_vec()));
+}
+
+#[test]
+fn clients_that_fall_behind_get_disconnected() {
+    let timer = timer();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "command": "ping" }"#);
+    client.receive();
+    assert_eq!(server.client_count(), 1);
+
+    // The client doesn't read anything anymore, so once the buffers of the
+    // connection are full, the messages queue up until it gets disconnected.
+    for _ in 0..1_000_000 {
+        if server.client_count() == 0 {
+            break;
+        }
+        server.send_event(livesplit_core::event::Event::Reset);
+    }
+    assert_eq!(server.client_count(), 0);
+}
+
+#[test]
+fn connections_beyond_the_maximum_get_closed() {
+    let timer = timer();
+    let server = server(&timer);
+
+    let mut clients: Vec<_> = (0..64).map(|_| TcpClient::connect(&server)).collect();
+    for client in &mut clients {
+        client.send(r#"{ "command": "ping" }"#);
+        assert_eq!(client.receive(), r#"{"success":null}"#);
+    }
+    assert_eq!(server.client_count(), 64);
+
+    let mut client = TcpClient::connect(&server);
+    client.send(r#"{ "command": "ping" }"#);
+    let mut line = String::new();
+    assert!(matches!(client.reader.read_line(&mut line), Ok(0) | Err(_)));
+    assert_eq!(server.client_count(), 64);
+}
+
+#[test]
+fn clients_get_disconnected_when_the_server_is_dropped() {
+    let timer = timer();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "command": "ping" }"#);
+    client.receive();
+
+    drop(server);
+
+    assert_eq!(client.receive(), "");
+    timer.write().unwrap().start().unwrap();
+}
+
+#[test]
+fn legacy_commands_get_responses() {
+    let timer = timer();
+    let server = Server::bind_legacy("127.0.0.1:0", timer.clone()).unwrap();
+    let mut client = TcpClient::connect(&server);
+
+    client.send("getcurrenttimerphase");
+    assert_eq!(client.receive(), "NotRunning");
+    client.send("getsplitindex");
+    assert_eq!(client.receive(), "-1");
+    client.send("getdelta");
+    assert_eq!(client.receive(), "-");
+
+    client.send("starttimer");
+    client.send("initgametime");
+    client.send("pausegametime");
+    client.send("setgametime 1:23.45");
+    client.send("getcurrentgametime");
+    assert_eq!(client.receive(), "1:23.45");
+    client.send("getcurrentsplitname");
+    assert_eq!(client.receive(), "Cascade Kingdom");
+
+    // Commands that fail and commands that are unknown don't have a response.
+    client.send("resume");
+    client.send("nonexistentcommand");
+    client.send("switchto gametime");
+    client.send("split");
+    client.send("getsplitindex");
+    assert_eq!(client.receive(), "1");
+    client.send("getlastsplittime");
+    assert_eq!(client.receive(), "1:23.45");
+    client.send("getprevioussplitname");
+    assert_eq!(client.receive(), "Cascade Kingdom");
+    client.send("getattemptcount");
+    assert_eq!(client.receive(), "1");
+    client.send("ping");
+    assert_eq!(client.receive(), "pong");
+
+    // Events are not sent to the clients of the legacy protocol.
+    server.send_event(livesplit_core::event::Event::Reset);
+    client.send("getcurrenttimerphase");
+    assert_eq!(client.receive(), "Running");
+}
+
+#[test]
+fn run_and_analysis_queries() {
+    let mut run = Run::new();
+    run.set_game_name("Super Mario Odyssey");
+    run.set_category_name("Any%");
+    run.metadata_mut()
+        .custom_variable_mut("Controller")
+        .permanent()
+        .set_value("Joy-Con");
+    for (name, split_time, best_segment) in [
+        ("Cascade Kingdom", 60.0, 50.0),
+        ("Sand Kingdom", 150.0, 80.0),
+    ] {
+        let mut segment = Segment::new(name);
+        segment.set_personal_best_split_time(
+            Time::new()
+                .with_real_time(Some(TimeSpan::from_seconds(split_time)))
+                .with_load_removed_time(Some(TimeSpan::from_seconds(split_time - 5.0))),
+        );
+        segment.set_best_segment_time(
+            Time::new().with_real_time(Some(TimeSpan::from_seconds(best_segment))),
+        );
+        run.push_segment(segment);
+    }
+    let timer = Timer::new(run).unwrap().into_shared();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "command": "getAttemptCount" }"#);
+    assert_eq!(client.receive(), r#"{"success":0}"#);
+    client.send(r#"{ "command": "getSumOfBest" }"#);
+    assert_eq!(client.receive(), r#"{"success":"00:02:10.000000000"}"#);
+    client.send(r#"{ "command": "getSumOfBest", "timingMethod": "GameTime" }"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+    client.send(r#"{ "command": "getCurrentPace" }"#);
+    assert_eq!(client.receive(), r#"{"success":"00:02:30.000000000"}"#);
+    client.send(r#"{ "command": "getPossibleTimeSave", "index": 0 }"#);
+    assert_eq!(client.receive(), r#"{"success":"00:00:10.000000000"}"#);
+    client.send(r#"{ "command": "getPossibleTimeSave", "index": 0, "total": true }"#);
+    assert_eq!(client.receive(), r#"{"success":"00:00:20.000000000"}"#);
+    client.send(r#"{ "command": "getPossibleTimeSave" }"#);
+    assert_eq!(client.receive(), r#"{"error":{"code":"InvalidIndex"}}"#);
+
+    client.send(r#"{ "command": "getPbChance" }"#);
+    let response: serde_json::Value = serde_json::from_str(&client.receive()).unwrap();
+    let chance = response["success"].as_f64().unwrap();
+    assert!((0.0..=1.0).contains(&chance));
+
+    client.send(r#"{ "command": "getRun" }"#);
+    let response: serde_json::Value = serde_json::from_str(&client.receive()).unwrap();
+    let run = &response["success"];
+    assert_eq!(run["gameName"], "Super Mario Odyssey");
+    assert_eq!(run["categoryName"], "Any%");
+    assert_eq!(run["comparisons"][0], "Personal Best");
+    assert_eq!(run["segments"][1]["name"], "Sand Kingdom");
+    assert_eq!(
+        run["segments"][1]["comparisons"]["Personal Best"]["realTime"],
+        "00:02:30.000000000",
+    );
+    assert_eq!(
+        run["segments"][1]["comparisons"]["Personal Best"]["loadRemovedTime"],
+        "00:02:25.000000000",
+    );
+    assert_eq!(
+        run["segments"][1]["comparisons"]["Personal Best"]["gameTime"],
+        serde_json::Value::Null,
+    );
+    assert_eq!(
+        run["segments"][0]["bestSegmentTime"]["realTime"],
+        "00:00:50.000000000",
+    );
+    assert_eq!(
+        run["metadata"]["customVariables"]["Controller"]["value"],
+        "Joy-Con",
+    );
+    assert_eq!(
+        run["metadata"]["customVariables"]["Controller"]["isPermanent"],
+        true,
+    );
+}
+
+#[test]
+fn layout_state_query() {
+    let timer = timer();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "command": "getLayoutState" }"#);
+    let response: serde_json::Value = serde_json::from_str(&client.receive()).unwrap();
+    let components = response["success"]["components"].as_array().unwrap();
+    assert!(components.iter().any(|c| c.get("Timer").is_some()));
+
+    let mut layout = Vec::new();
+    let mut settings = Layout::new();
+    settings.push(TimerComponent::new());
+    settings.settings().write_json(&mut layout).unwrap();
+    let layout = String::from_utf8(layout).unwrap();
+
+    client.send(&format!(
+        r#"{{ "command": "getLayoutState", "layout": {layout} }}"#
+    ));
+    let response: serde_json::Value = serde_json::from_str(&client.receive()).unwrap();
+    let components = response["success"]["components"].as_array().unwrap();
+    assert_eq!(components.len(), 1);
+    assert!(components[0].get("Timer").is_some());
+
+    client.send(r#"{ "command": "getLayoutState", "layout": { "version": 1000 } }"#);
+    assert!(
+        client
+            .receive()
+            .starts_with(r#"{"error":{"code":"InvalidLayout""#)
+    );
+}
+
+#[test]
+fn clients_only_get_the_events_they_subscribed_to() {
+    let timer = timer();
+    let server = server(&timer);
+
+    let mut first = TcpClient::connect(&server);
+    let mut second = TcpClient::connect(&server);
+
+    first.send(r#"{ "command": "unsubscribeFromEvents" }"#);
+    assert_eq!(first.receive(), r#"{"success":null}"#);
+    first.send(r#"{ "command": "subscribeToEvents", "events": ["Splitted", "Reset"] }"#);
+    assert_eq!(first.receive(), r#"{"success":null}"#);
+    first.send(r#"{ "command": "unsubscribeFromEvents", "events": ["Reset"] }"#);
+    assert_eq!(first.receive(), r#"{"success":null}"#);
+    second.send(r#"{ "command": "ping" }"#);
+    second.receive();
+
+    first.send(r#"{ "command": "start" }"#);
+    assert_eq!(first.receive(), r#"{"success":null}"#);
+    first.send(r#"{ "command": "split" }"#);
+    assert_eq!(first.receive(), r#"{"event":"Splitted"}"#);
+    assert_eq!(first.receive(), r#"{"success":null}"#);
+    first.send(r#"{ "command": "reset" }"#);
+    assert_eq!(first.receive(), r#"{"success":null}"#);
+
+    assert_eq!(second.receive(), r#"{"event":"Started"}"#);
+    assert_eq!(second.receive(), r#"{"event":"Splitted"}"#);
+    assert_eq!(second.receive(), r#"{"event":"Reset"}"#);
+}
+
+#[test]
+fn time_updates_are_sent_periodically() {
+    let timer = timer();
+    let server = server(&timer);
+    let mut client = TcpClient::connect(&server);
+
+    client.send(r#"{ "command": "subscribeToTimeUpdates", "rate": 0 }"#);
+    assert_eq!(client.receive(), r#"{"error":{"code":"InvalidRate"}}"#);
+
+    timer.write().unwrap().start().unwrap();
+    assert_eq!(client.receive(), r#"{"event":"Started"}"#);
+
+    client.send(r#"{ "command": "subscribeToTimeUpdates", "rate": 100 }"#);
+    assert_eq!(client.receive(), r#"{"success":null}"#);
+    for _ in 0..3 {
+        let update = client.receive();
+        assert!(update.starts_with(r#"{"timeUpdate":{"currentTime":"00:00:00."#));
+        assert!(update.ends_with(r#","splitIndex":0,"delta":null}}"#));
+    }
+
+    client.send(r#"{ "command": "unsubscribeFromTimeUpdates" }"#);
+    while client.receive() != r#"{"success":null}"# {}
+
+    client
+        .stream
+        .set_read_timeout(Some(std::time::Duration::from_millis(100)))
+        .unwrap();
+    let mut line = String::new();
+    assert!(client.reader.read_line(&mut line).is_err());
+    assert!(line.is_empty());
+}
@@ -6,9 +6,9 @@ mod parse {
         Run, TimeSpan,
         analysis::total_playtime,
         run::parser::{
-            TimerKind, composite, flitter, livesplit, llanfair, llanfair_gered, portal2_live_timer,
-            source_live_timer, speedrun_igt, splitterino, splitterz, time_split_tracker, urn,
-            wsplit,
+            Diagnostics, TimerKind, composite, flitter, livesplit, llanfair, llanfair_gered,
+            portal2_live_timer, source_live_timer, speedrun_igt, splits_io, splitterino, splitterz,
+            time_split_tracker, urn, wsplit,
         },
     };
 
@@ -69,6 +69,39 @@ mod parse {
         assert!(playtime >= TimeSpan::zero());
     }
 
+    #[test]
+    fn livesplit_attempt_ended_bug_warns() {
+        let mut diagnostics = Diagnostics::new();
+        livesplit::parse_with_diagnostics(run_files::LIVESPLIT_ATTEMPT_ENDED_BUG, &mut diagnostics)
+            .unwrap();
+        assert_eq!(
+            diagnostics.warnings[0].to_string(),
+            "/Run/AttemptHistory/Attempt[@id=\"42\"]: \
+             the attempt ended before it started, the ended date got ignored",
+        );
+    }
+
+    #[test]
+    fn livesplit_invalid_date_is_ignored() {
+        let source = run_files::LIVESPLIT_1_6.replacen(
+            "ended=\"08/30/2015 19:34:04\"",
+            "ended=\"08/30/2015 25:34:04\"",
+            1,
+        );
+        let mut diagnostics = Diagnostics::new();
+        let run = livesplit::parse_with_diagnostics(&source, &mut diagnostics).unwrap();
+
+        assert_eq!(diagnostics.warnings.len(), 1);
+        assert_eq!(
+            diagnostics.warnings[0].to_string(),
+            "/Run/AttemptHistory/Attempt[@id=\"1\"]: the ended date is invalid, it got ignored",
+        );
+        let attempt = &run.attempt_history()[0];
+        assert_eq!(attempt.index(), 1);
+        assert!(attempt.ended().is_none());
+        assert!(attempt.started().is_some());
+    }
+
     #[test]
     fn llanfair() {
         llanfair::parse(run_files::LLANFAIR).unwrap();
@@ -135,6 +168,63 @@ mod parse {
         splitterino::parse(run_files::SPLITTERINO).unwrap();
     }
 
+    #[test]
+    fn splits_io() {
+        let run = splits_io::parse(run_files::SPLITS_IO).unwrap();
+        assert_eq!(run.game_name(), "Celeste");
+        assert_eq!(run.category_name(), "Any%");
+        assert_eq!(
+            run.metadata()
+                .custom_variable_value(splits_io::RUNNER_VARIABLE),
+            Some("Alice, Bob")
+        );
+        assert_eq!(run.attempt_count(), 5);
+        assert_eq!(run.attempt_history().len(), 3);
+        assert!(run.attempt_history()[0].started().is_some());
+        assert_eq!(
+            run.attempt_history()[1].time().game_time,
+            Some(TimeSpan::from_milliseconds(371480.0))
+        );
+        assert_eq!(run.len(), 3);
+        let segment = run.segment(1);
+        assert_eq!(segment.name(), "Old Site");
+        assert_eq!(
+            segment.personal_best_split_time().real_time,
+            Some(TimeSpan::from_milliseconds(250150.0))
+        );
+        assert_eq!(segment.segment_history().get(1), None);
+        assert_eq!(segment.segment_history().get(2), Some(Default::default()));
+    }
+
+    #[test]
+    fn splits_io_date_time_offsets() {
+        let run = splits_io::parse(
+            r#"{
+                "_schemaVersion": "v1.0.1",
+                "attempts": {
+                    "total": 1,
+                    "histories": [{
+                        "attemptNumber": 1,
+                        "startedAt": "2019-03-01T14:00:00+02:00",
+                        "endedAt": "2019-03-01T07:02:14.250-05:00"
+                    }]
+                },
+                "segments": [{ "name": "Forsaken City" }]
+            }"#,
+        )
+        .unwrap();
+
+        let attempt = &run.attempt_history()[0];
+        let started = attempt.started().unwrap().time;
+        let ended = attempt.ended().unwrap().time;
+        assert_eq!(started.offset(), time::UtcOffset::UTC);
+        assert_eq!(started.time(), time::Time::from_hms(12, 0, 0).unwrap());
+        assert_eq!(
+            ended.time(),
+            time::Time::from_hms_milli(12, 2, 14, 250).unwrap()
+        );
+    }
+
     #[test]
     fn urn() {
         urn::parse(run_files::URN).unwrap();
@@ -188,6 +278,12 @@ mod parse {
         assert_eq!(run.kind, TimerKind::Splitterino);
     }
 
+    #[test]
+    fn splits_io_prefers_parsing_as_itself() {
+        let run = composite::parse(run_files::SPLITS_IO.as_bytes(), None).unwrap();
+        assert_eq!(run.kind, TimerKind::SplitsIO);
+    }
+
     #[test]
     fn urn_prefers_parsing_as_itself() {
         let run = composite::parse(run_files::URN.as_bytes(), None).unwrap();
@@ -205,4 +301,33 @@ mod parse {
         let run = composite::parse(run_files::FLITTER.as_bytes(), None).unwrap();
         assert_eq!(run.kind, TimerKind::Flitter);
     }
+
+    #[test]
+    fn composite_reports_rejections() {
+        let run = composite::parse(run_files::SPLITS_IO.as_bytes(), None).unwrap();
+        assert!(run.diagnostics.warnings.is_empty());
+        let rejections = &run.diagnostics.rejections;
+        assert_eq!(rejections[0].kind, TimerKind::LiveSplit);
+        assert!(rejections.iter().all(|r| r.kind != TimerKind::SplitsIO));
+    }
+
+    #[test]
+    fn composite_reports_errors_of_all_parsers() {
+        let Err(composite::Error::NoParserParsedIt { rejections }) =
+            composite::parse(b"\xFFNot a splits file", None)
+        else {
+            panic!("Parsed an invalid splits file");
+        };
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections[0].kind, TimerKind::Llanfair);
+
+        let Err(composite::Error::NoParserParsedIt { rejections }) =
+            composite::parse(b"Not a splits file", None)
+        else {
+            panic!("Parsed an invalid splits file");
+        };
+        assert_eq!(rejections[0].kind, TimerKind::LiveSplit);
+        assert_eq!(rejections.last().unwrap().kind, TimerKind::Llanfair);
+        assert!(rejections.iter().all(|r| !r.error.is_empty()));
+    }
 }
@@ -0,0 +1,496 @@
+mod run_files;
+
+mod round_trip {
+    use crate::run_files;
+    use livesplit_core::{
+        Run, Segment,
+        run::{parser, saver},
+    };
+
+    #[track_caller]
+    fn round_trip(
+        run: Run,
+        save: fn(&Run, &mut String) -> core::fmt::Result,
+        parse: fn(&str) -> Run,
+    ) {
+        let mut buf = String::new();
+        save(&run, &mut buf).unwrap();
+        assert_eq!(parse(&buf), run);
+    }
+
+    #[test]
+    fn urn() {
+        let parse = |s: &str| parser::urn::parse(s).unwrap();
+        round_trip(
+            parse(run_files::URN),
+            |r, w| saver::urn::save_run(r, w),
+            parse,
+        );
+    }
+
+    #[test]
+    fn urn_names() {
+        let mut run = Run::new();
+        run.set_game_name("Super Mario Odyssey");
+        run.set_category_name("Any%");
+        run.push_segment(Segment::new("Cascade Kingdom"));
+
+        let mut buf = String::new();
+        saver::urn::save_run(&run, &mut buf).unwrap();
+        let parsed = parser::urn::parse(&buf).unwrap();
+
+        assert_eq!(parsed.category_name(), "Any%");
+        assert_eq!(parsed.segment(0).name(), "Cascade Kingdom");
+
+        let mut resaved = String::new();
+        saver::urn::save_run(&parsed, &mut resaved).unwrap();
+        assert_eq!(resaved, buf);
+    }
+
+    #[test]
+    fn splits_io() {
+        let parse = |s: &str| parser::splits_io::parse(s).unwrap();
+        round_trip(
+            parse(run_files::SPLITS_IO),
+            |r, w| saver::splits_io::save_run(r, w),
+            parse,
+        );
+    }
+
+    #[test]
+    fn splits_io_load_removed_time() {
+        use livesplit_core::{TimeSpan, TimingMethod};
+
+        let parse = |s: &str| parser::splits_io::parse(s).unwrap();
+        let mut run = parse(run_files::SPLITS_IO);
+        let segment = run.segment_mut(0);
+        segment.personal_best_split_time_mut()[TimingMethod::LoadRemovedTime] =
+            Some(TimeSpan::from_seconds(90.5));
+        segment.best_segment_time_mut()[TimingMethod::LoadRemovedTime] =
+            Some(TimeSpan::from_seconds(85.0));
+
+        let mut buf = String::new();
+        saver::splits_io::save_run(&run, &mut buf).unwrap();
+        assert!(buf.contains("\"loadRemovedTimeMS\": 90500.0"));
+
+        round_trip(run, |r, w| saver::splits_io::save_run(r, w), parse);
+    }
+
+    #[test]
+    fn splits_io_from_livesplit() {
+        let run = parser::livesplit::parse(run_files::CELESTE).unwrap();
+        let mut buf = String::new();
+        saver::splits_io::save_run(&run, &mut buf).unwrap();
+        let converted = parser::splits_io::parse(&buf).unwrap();
+
+        assert_eq!(converted.game_name(), run.game_name());
+        assert_eq!(converted.category_name(), run.category_name());
+        assert_eq!(converted.attempt_count(), run.attempt_count());
+        assert_eq!(
+            converted.attempt_history().len(),
+            run.attempt_history().len()
+        );
+        for (converted, segment) in converted.segments().iter().zip(run.segments()) {
+            assert_eq!(converted.name(), segment.name());
+            assert_eq!(
+                converted.segment_history().iter().count(),
+                segment.segment_history().iter().count()
+            );
+        }
+    }
+
+    #[test]
+    fn splitterino() {
+        let parse = |s: &str| parser::splitterino::parse(s).unwrap();
+        round_trip(
+            parse(run_files::SPLITTERINO),
+            |r, w| saver::splitterino::save_run(r, w),
+            parse,
+        );
+    }
+
+    #[test]
+    fn flitter() {
+        let parse = |s: &str| parser::flitter::parse(s).unwrap();
+        round_trip(
+            parse(run_files::FLITTER),
+            |r, w| saver::flitter::save_run(r, w),
+            parse,
+        );
+    }
+
+    #[test]
+    fn wsplit() {
+        let parse = |s: &str| parser::wsplit::parse(s, false).unwrap();
+        round_trip(
+            parse(run_files::WSPLIT),
+            |r, w| saver::wsplit::save_run(r, w),
+            parse,
+        );
+    }
+
+    #[test]
+    fn wsplit_goal() {
+        let mut run = parser::wsplit::parse(run_files::WSPLIT, false).unwrap();
+        run.metadata_mut()
+            .custom_variable_mut("Goal")
+            .permanent()
+            .set_value("sub 30m");
+        round_trip(
+            run,
+            |r, w| saver::wsplit::save_run(r, w),
+            |s| parser::wsplit::parse(s, false).unwrap(),
+        );
+    }
+
+    #[test]
+    fn wsplit_names() {
+        let mut run = Run::new();
+        run.set_game_name("Super Mario Odyssey");
+        run.set_category_name("Any%");
+        run.push_segment(Segment::new("Cascade, Sand"));
+        run.push_segment(Segment::new("Lake"));
+
+        let mut buf = String::new();
+        saver::wsplit::save_run(&run, &mut buf).unwrap();
+        let parsed = parser::wsplit::parse(&buf, false).unwrap();
+
+        assert_eq!(parsed.category_name(), "Any%");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.segment(0).name(), "Cascade‚ Sand");
+        assert_eq!(parsed.segment(1).name(), "Lake");
+
+        let mut resaved = String::new();
+        saver::wsplit::save_run(&parsed, &mut resaved).unwrap();
+        assert_eq!(resaved, buf);
+    }
+
+    #[test]
+    fn livesplit() {
+        let parse = |s: &str| parser::livesplit::parse(s).unwrap();
+        round_trip(
+            parse(run_files::CELESTE),
+            |r, w| saver::livesplit::save_run(r, w),
+            parse,
+        );
+    }
+
+    #[test]
+    fn livesplit_unknown_elements() {
+        let end = run_files::CELESTE.rfind("</Run>").unwrap();
+        let source = format!(
+            "{}<Future version=\"2\"><Inner>&amp;</Inner></Future>{}",
+            &run_files::CELESTE[..end],
+            &run_files::CELESTE[end..],
+        )
+        .replacen("</Metadata>", "<Emulator name=\"Foo\" /></Metadata>", 1)
+        .replacen("</Segment>", "<Notes><![CDATA[Jump]]></Notes></Segment>", 1);
+
+        let run = parser::livesplit::parse(&source).unwrap();
+        assert!(!run.unknown_elements().is_empty());
+        assert!(!run.metadata().unknown_elements().is_empty());
+        assert!(!run.segment(0).unknown_elements().is_empty());
+        assert!(run.segment(1).unknown_elements().is_empty());
+
+        let mut buf = String::new();
+        saver::livesplit::save_run(&run, &mut buf).unwrap();
+        assert!(buf.contains(
+            "</AutoSplitterSettings><Future version=\"2\"><Inner>&amp;</Inner></Future></Run>"
+        ));
+        assert!(buf.contains("<Emulator name=\"Foo\"></Emulator></Metadata>"));
+        assert!(buf.contains("<Notes><![CDATA[Jump]]></Notes></Segment>"));
+
+        round_trip(
+            run,
+            |r, w| saver::livesplit::save_run(r, w),
+            |s| parser::livesplit::parse(s).unwrap(),
+        );
+    }
+
+    #[test]
+    fn livesplit_unknown_attributes() {
+        let source = run_files::CELESTE
+            .replacen(
+                r#"<Run version="1.7.1">"#,
+                r#"<Run version="1.7.1" tool='Say "Hi" &amp; more'>"#,
+                1,
+            )
+            .replacen("<Metadata>", r#"<Metadata source="speedrun.com">"#, 1)
+            .replacen("<Segment>", r##"<Segment color="#FF0000">"##, 1);
+
+        let run = parser::livesplit::parse(&source).unwrap();
+        assert!(!run.unknown_elements().is_empty());
+        assert!(!run.metadata().unknown_elements().is_empty());
+        assert!(!run.segment(0).unknown_elements().is_empty());
+        assert!(run.segment(1).unknown_elements().is_empty());
+
+        let mut buf = String::new();
+        saver::livesplit::save_run(&run, &mut buf).unwrap();
+        assert!(buf.contains(r#"<Run version="1.8.0" tool="Say &quot;Hi&quot; &amp; more">"#));
+        assert!(buf.contains(r#"<Metadata source="speedrun.com">"#));
+        assert!(buf.contains(r##"<Segment color="#FF0000">"##));
+
+        round_trip(
+            run,
+            |r, w| saver::livesplit::save_run(r, w),
+            |s| parser::livesplit::parse(s).unwrap(),
+        );
+    }
+
+    #[test]
+    fn livesplit_unknown_element_attributes_are_escaped() {
+        let source = run_files::CELESTE.replacen(
+            "</Metadata>",
+            r#"<Emulator name='Say "Hi" &amp; more'><Core id='&lt;1&gt;' /></Emulator></Metadata>"#,
+            1,
+        );
+
+        let run = parser::livesplit::parse(&source).unwrap();
+
+        let mut buf = String::new();
+        saver::livesplit::save_run(&run, &mut buf).unwrap();
+        assert!(buf.contains(r#"<Emulator name="Say &quot;Hi&quot; &amp; more">"#));
+        assert!(buf.contains(r#"<Core id="&lt;1&gt;"></Core>"#));
+
+        round_trip(
+            run,
+            |r, w| saver::livesplit::save_run(r, w),
+            |s| parser::livesplit::parse(s).unwrap(),
+        );
+    }
+
+    #[test]
+    fn livesplit_load_removed_time() {
+        use livesplit_core::{TimeSpan, TimingMethod};
+
+        let mut run = parser::livesplit::parse(run_files::CELESTE).unwrap();
+        let segment = run.segment_mut(0);
+        segment.personal_best_split_time_mut()[TimingMethod::LoadRemovedTime] =
+            Some(TimeSpan::from_seconds(90.5));
+        segment.best_segment_time_mut()[TimingMethod::LoadRemovedTime] =
+            Some(TimeSpan::from_seconds(85.0));
+
+        let mut buf = String::new();
+        saver::livesplit::save_run(&run, &mut buf).unwrap();
+        assert!(buf.contains("<LoadRemovedTime>00:01:30.500000000</LoadRemovedTime>"));
+        assert!(buf.contains("<LoadRemovedTime>00:01:25.000000000</LoadRemovedTime>"));
+
+        round_trip(
+            run,
+            |r, w| saver::livesplit::save_run(r, w),
+            |s| parser::livesplit::parse(s).unwrap(),
+        );
+    }
+
+    #[test]
+    fn livesplit_pauses() {
+        use livesplit_core::{Attempt, Time, TimeSpan, run::Pause};
+
+        let mut run = parser::livesplit::parse(run_files::CELESTE).unwrap();
+        let index = run.next_attempt_index();
+        run.push_attempt(
+            Attempt::new(
+                index,
+                Time::new(),
+                None,
+                None,
+                Some(TimeSpan::from_seconds(3.5)),
+            )
+            .with_pauses(vec![
+                Pause {
+                    start: TimeSpan::from_seconds(1.0),
+                    end: TimeSpan::from_seconds(2.0),
+                },
+                Pause {
+                    start: TimeSpan::from_seconds(60.0),
+                    end: TimeSpan::from_seconds(62.5),
+                },
+            ]),
+        );
+
+        let mut buf = String::new();
+        saver::livesplit::save_run(&run, &mut buf).unwrap();
+        assert!(buf.contains(
+            "<Pauses><Pause start=\"00:00:01.000000000\" end=\"00:00:02.000000000\"/>\
+             <Pause start=\"00:01:00.000000000\" end=\"00:01:02.500000000\"/></Pauses>"
+        ));
+
+        round_trip(
+            run,
+            |r, w| saver::livesplit::save_run(r, w),
+            |s| parser::livesplit::parse(s).unwrap(),
+        );
+    }
+
+    #[test]
+    fn livesplit_runners() {
+        let mut run = parser::livesplit::parse(run_files::CELESTE).unwrap();
+        run.segment_mut(0).set_runner("Alice");
+        run.segment_mut(1).set_runner("Bob & Carol");
+
+        let mut buf = String::new();
+        saver::livesplit::save_run(&run, &mut buf).unwrap();
+        assert!(buf.contains("<Runner>Alice</Runner>"));
+        assert!(buf.contains("<Runner>Bob &amp; Carol</Runner>"));
+        assert_eq!(buf.matches("<Runner>").count(), 2);
+
+        round_trip(
+            run,
+            |r, w| saver::livesplit::save_run(r, w),
+            |s| parser::livesplit::parse(s).unwrap(),
+        );
+    }
+}
+
+mod csv {
+    use livesplit_core::{AtomicDateTime, DateTime, Run, Segment, Time, TimeSpan, run::saver::csv};
+
+    fn run() -> Run {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A, \"B\""));
+        run.push_segment(Segment::new("C"));
+
+        let started = AtomicDateTime::new(DateTime::from_unix_timestamp(0).unwrap(), false);
+        run.add_attempt_with_index(
+            Time::new()
+                .with_real_time(Some(TimeSpan::from_seconds(12.5)))
+                .with_game_time(Some(TimeSpan::from_seconds(10.0)))
+                .with_load_removed_time(Some(TimeSpan::from_seconds(11.0))),
+            1,
+            Some(started),
+            None,
+            Some(TimeSpan::from_seconds(2.0)),
+        );
+        run.add_attempt_with_index(Time::new(), 2, None, None, None);
+
+        run.segment_mut(0).segment_history_mut().insert(
+            1,
+            Time::new().with_real_time(Some(TimeSpan::from_seconds(5.0))),
+        );
+        run.segment_mut(1).segment_history_mut().insert(
+            1,
+            Time::new().with_real_time(Some(TimeSpan::from_seconds(7.5))),
+        );
+        run.segment_mut(0)
+            .segment_history_mut()
+            .insert(2, Time::new());
+
+        run
+    }
+
+    #[test]
+    fn attempt_history() {
+        let mut buf = String::new();
+        csv::save_attempt_history(&run(), &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            "Attempt,Started,Ended,Pause Time,Real Time,Game Time,Load Removed Time\r\n\
+             1,1970-01-01 00:00:00,,00:00:02.000000000,00:00:12.500000000,00:00:10.000000000,00:00:11.000000000\r\n\
+             2,,,,,,\r\n"
+        );
+    }
+
+    #[test]
+    fn segment_history() {
+        let mut buf = String::new();
+        csv::save_segment_history(&run(), &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            "Attempt,\"A, \"\"B\"\" (Real Time)\",\"A, \"\"B\"\" (Game Time)\",\"A, \"\"B\"\" (Load Removed Time)\",\
+             C (Real Time),C (Game Time),C (Load Removed Time)\r\n\
+             1,00:00:05.000000000,,,00:00:07.500000000,,\r\n\
+             2,,,,,,\r\n"
+        );
+    }
+}
+
+mod incremental {
+    use crate::run_files;
+    use livesplit_core::{
+        Run, Timer, TimingMethod,
+        run::{
+            parser,
+            saver::livesplit::{self, IncrementalSaver},
+        },
+        settings::Image,
+    };
+
+    #[track_caller]
+    fn check_run(saver: &mut IncrementalSaver, run: &Run) {
+        let mut incremental = String::new();
+        saver.save_run(run, &mut incremental).unwrap();
+        let mut full = String::new();
+        livesplit::save_run(run, &mut full).unwrap();
+        assert_eq!(incremental, full);
+    }
+
+    #[track_caller]
+    fn check_timer(saver: &mut IncrementalSaver, timer: &Timer) {
+        // An attempt in progress is saved with the current date as its end, so
+        // the incremental save happens in between two full saves. Dates are
+        // only stored with a precision of seconds, so it needs to match at
+        // least one of them.
+        let mut before = String::new();
+        livesplit::save_timer(timer, &mut before).unwrap();
+        let mut incremental = String::new();
+        saver.save_timer(timer, &mut incremental).unwrap();
+        let mut after = String::new();
+        livesplit::save_timer(timer, &mut after).unwrap();
+        if incremental != before {
+            assert_eq!(incremental, after);
+        }
+    }
+
+    fn run_attempt(timer: &mut Timer, splits: usize) {
+        timer.start().unwrap();
+        for _ in 0..splits {
+            timer.split().unwrap();
+        }
+        timer.reset(true).unwrap();
+    }
+
+    #[test]
+    fn matches_full_save_across_attempts() {
+        let run = parser::livesplit::parse(run_files::CELESTE).unwrap();
+        let mut timer = Timer::new(run).unwrap();
+        timer.set_current_timing_method(TimingMethod::RealTime);
+        let mut saver = IncrementalSaver::new();
+
+        check_timer(&mut saver, &timer);
+        check_timer(&mut saver, &timer);
+
+        run_attempt(&mut timer, 3);
+        check_timer(&mut saver, &timer);
+
+        timer.start().unwrap();
+        timer.split().unwrap();
+        check_timer(&mut saver, &timer);
+        timer.reset(true).unwrap();
+        check_timer(&mut saver, &timer);
+
+        let len = timer.run().len();
+        run_attempt(&mut timer, len);
+        check_timer(&mut saver, &timer);
+    }
+
+    #[test]
+    fn matches_full_save_after_editing() {
+        let mut run = parser::livesplit::parse(run_files::CELESTE).unwrap();
+        let mut saver = IncrementalSaver::new();
+        check_run(&mut saver, &run);
+
+        run.clear_history();
+        run.set_game_icon(Image::new([1, 2, 3].as_slice().into(), Image::ICON));
+        run.segment_mut(0)
+            .set_icon(Image::new([4, 5, 6].as_slice().into(), Image::ICON));
+        run.segments_mut().pop();
+        check_run(&mut saver, &run);
+
+        let icon = run.game_icon().clone();
+        run.segment_mut(1).set_icon(icon);
+        check_run(&mut saver, &run);
+
+        saver.clear();
+        check_run(&mut saver, &run);
+    }
+}